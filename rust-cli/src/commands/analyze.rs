@@ -0,0 +1,113 @@
+use anyhow::Result;
+use colored::*;
+use tokio::fs as async_fs;
+
+use crate::outln;
+use crate::utils::analysis::{Analyzer, Location, Usage, WordLists};
+use crate::utils::{output, ProjectManager};
+
+/// Locations listed per word before the rest are summarized
+const SHOWN_LOCATIONS: usize = 5;
+
+/// Report overused words, crutch and filter words, adverbs and repeated phrases
+pub async fn words(project: &ProjectManager, top: usize) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let config = project.get_config().await?;
+    let files = project.get_all_content_files().await?;
+    if files.is_empty() {
+        outln!("{}", "No content files found.".yellow());
+        outln!("{}", "💡 Create some files to get started!".bright_black());
+        return Ok(());
+    }
+
+    let mut analyzer = Analyzer::new(WordLists::from_settings(config.settings.as_ref()));
+    for file in &files {
+        let content = async_fs::read_to_string(file).await?;
+        analyzer.add_file(&project.display_path(file), &content);
+    }
+    let report = analyzer.report(top);
+
+    if output::is_json() {
+        return output::json(&report);
+    }
+
+    outln!("{}", "🔍 Word Analysis".cyan().bold());
+    outln!();
+    outln!(
+        "  {} words in {} files",
+        report.words.to_string().cyan(),
+        report.files.to_string().cyan()
+    );
+    outln!();
+
+    outln!("{}", "🔤 Most Frequent Words:".green().bold());
+    if report.frequent.is_empty() {
+        outln!("  {}", "None".bright_black());
+    }
+    let width = report
+        .frequent
+        .iter()
+        .map(|frequency| frequency.word.chars().count())
+        .max()
+        .unwrap_or(0);
+    for frequency in &report.frequent {
+        let per_thousand = frequency.count as f64 * 1000.0 / report.words.max(1) as f64;
+        outln!(
+            "  {}  {:>5}  {}",
+            format!("{:<width$}", frequency.word, width = width).cyan(),
+            frequency.count,
+            format!("{:.1} per 1000 words", per_thousand).bright_black()
+        );
+    }
+    outln!();
+
+    print_usages("🩹 Crutch Words:", &report.crutch_words);
+    print_usages("🐌 Adverbs (-ly):", &report.adverbs);
+    print_usages("👁️  Filter Words:", &report.filter_words);
+    print_usages("🔁 Repeated Phrases:", &report.phrases);
+
+    outln!(
+        "{}",
+        "💡 Set stopwords, crutch_words or filter_words under settings in writers.config.json to change the lists"
+            .bright_black()
+    );
+
+    Ok(())
+}
+
+fn print_usages(heading: &str, usages: &[Usage]) {
+    outln!("{}", heading.green().bold());
+    if usages.is_empty() {
+        outln!("  {}", "✓ None found".green());
+    }
+    for usage in usages {
+        outln!(
+            "  {} {}",
+            usage.text.cyan(),
+            format!("×{}", usage.count).yellow()
+        );
+        outln!("    {}", locations(&usage.locations).bright_black());
+    }
+    outln!();
+}
+
+/// The first few locations as `path:line`, then how many more there are
+fn locations(locations: &[Location]) -> String {
+    let mut shown: Vec<String> = locations
+        .iter()
+        .take(SHOWN_LOCATIONS)
+        .map(|location| format!("{}:{}", location.path, location.line))
+        .collect();
+    if locations.len() > SHOWN_LOCATIONS {
+        shown.push(format!("and {} more", locations.len() - SHOWN_LOCATIONS));
+    }
+    shown.join(", ")
+}
@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use tokio::fs as async_fs;
+
+use crate::outln;
+use crate::utils::{migrate, output};
+use crate::utils::{ProjectConfig, ProjectManager};
+use serde_json::json;
+
+/// Rewrite writers.config.json in the current schema, keeping a backup of the original
+pub async fn migrate(project: &ProjectManager, dry_run: bool) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let mut raw = project.get_raw_config().await?;
+    if !migrate::needs_migration(&raw) {
+        if output::is_json() {
+            return output::json(&json!({ "changes": [], "written": false }));
+        }
+        outln!(
+            "{}",
+            format!(
+                "✅ writers.config.json is already at schema version {}.",
+                migrate::SCHEMA_VERSION
+            )
+            .green()
+        );
+        return Ok(());
+    }
+
+    let changes = migrate::migrate(&mut raw);
+
+    // Make sure the result loads before touching the file
+    serde_json::from_value::<ProjectConfig>(raw.clone())
+        .map_err(|e| anyhow!("Migrated config is still invalid: {}", e))?;
+
+    if !output::is_json() {
+        outln!("{}", "🔧 Config changes:".cyan().bold());
+        for change in &changes {
+            outln!("  • {}", change);
+        }
+        outln!();
+    }
+
+    if dry_run {
+        if output::is_json() {
+            return output::json(&json!({ "changes": changes, "written": false }));
+        }
+        outln!("{}", "💡 Dry run, nothing was written.".yellow());
+        return Ok(());
+    }
+
+    let config_path = project.path("writers.config.json");
+    async_fs::copy(&config_path, project.path("writers.config.json.bak")).await?;
+    async_fs::write(&config_path, serde_json::to_string_pretty(&raw)?).await?;
+
+    if output::is_json() {
+        return output::json(&json!({ "changes": changes, "written": true }));
+    }
+
+    outln!("{}", "✅ Migrated writers.config.json".green());
+    outln!(
+        "{} {}",
+        "📁 Original saved to:".green(),
+        "writers.config.json.bak".cyan()
+    );
+
+    Ok(())
+}
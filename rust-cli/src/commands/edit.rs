@@ -1,15 +1,18 @@
 use anyhow::Result;
 use colored::*;
 use dialoguer::{theme::ColorfulTheme, Select};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::editor::sprint::Sprint;
 use crate::editor::WritersEditor;
-use crate::utils::ProjectManager;
+use crate::outln;
+use crate::utils::lint::Linter;
+use crate::utils::{goals, ProjectManager};
 
-pub async fn run(target: Option<String>) -> Result<()> {
+pub async fn run(project: &ProjectManager, target: Option<String>) -> Result<()> {
     // Check if in a writers project
-    if !ProjectManager::is_writers_project() {
-        println!(
+    if !project.is_writers_project() {
+        outln!(
             "{}",
             "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
         );
@@ -18,11 +21,11 @@ pub async fn run(target: Option<String>) -> Result<()> {
 
     let target_file = if let Some(target) = target {
         // Try to find the specified file
-        match ProjectManager::find_file(&target).await? {
+        match project.find_file(&target).await? {
             Some(file) => file,
             None => {
-                println!("{} {}", "❌ File not found:".red(), target);
-                println!(
+                outln!("{} {}", "❌ File not found:".red(), target);
+                outln!(
                     "{}",
                     "💡 Use \"writers list\" to see available files".yellow()
                 );
@@ -31,18 +34,18 @@ pub async fn run(target: Option<String>) -> Result<()> {
         }
     } else {
         // Show file selection menu
-        select_file_to_edit().await?
+        select_file_to_edit(project).await?
     };
 
     // Launch the built-in editor
-    launch_editor(target_file).await
+    launch_editor(project, target_file, LaunchOptions::default()).await
 }
 
-async fn select_file_to_edit() -> Result<PathBuf> {
-    println!("{}", "📝 Select a file to edit:".cyan().bold());
-    println!();
+pub async fn select_file_to_edit(project: &ProjectManager) -> Result<PathBuf> {
+    outln!("{}", "📝 Select a file to edit:".cyan().bold());
+    outln!();
 
-    let config = ProjectManager::get_config().await?;
+    let config = project.get_config().await?;
     let mut all_files = Vec::new();
     let mut file_descriptions = Vec::new();
 
@@ -50,7 +53,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
     match config.r#type.as_str() {
         "novel" => {
             // Add chapters
-            let chapters = ProjectManager::get_chapters().await?;
+            let chapters = project.get_chapters().await?;
             for chapter in chapters {
                 if let Some(file_name) = chapter.file_name().and_then(|n| n.to_str()) {
                     all_files.push(chapter.clone());
@@ -59,7 +62,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
             }
 
             // Add scenes
-            let scenes = ProjectManager::get_scenes().await?;
+            let scenes = project.get_scenes().await?;
             for scene in scenes {
                 if let Some(file_name) = scene.file_name().and_then(|n| n.to_str()) {
                     all_files.push(scene.clone());
@@ -68,7 +71,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
             }
 
             // Add characters
-            let characters = ProjectManager::get_characters().await?;
+            let characters = project.get_characters().await?;
             for character in characters {
                 if let Some(file_name) = character.file_name().and_then(|n| n.to_str()) {
                     all_files.push(character.clone());
@@ -77,13 +80,14 @@ async fn select_file_to_edit() -> Result<PathBuf> {
             }
 
             // Add outline if it exists
-            if std::path::Path::new("outline.md").exists() {
-                all_files.push(PathBuf::from("outline.md"));
+            let outline = project.path("outline.md");
+            if outline.exists() {
+                all_files.push(outline);
                 file_descriptions.push("📋 Outline".to_string());
             }
         }
         "short-story" => {
-            let stories = ProjectManager::get_short_stories().await?;
+            let stories = project.get_short_stories().await?;
             for story in stories {
                 if let Some(file_name) = story.file_name().and_then(|n| n.to_str()) {
                     all_files.push(story.clone());
@@ -91,7 +95,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
                 }
             }
 
-            let characters = ProjectManager::get_characters().await?;
+            let characters = project.get_characters().await?;
             for character in characters {
                 if let Some(file_name) = character.file_name().and_then(|n| n.to_str()) {
                     all_files.push(character.clone());
@@ -100,7 +104,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
             }
         }
         "simple-short-story" => {
-            let stories = ProjectManager::get_short_stories().await?;
+            let stories = project.get_short_stories().await?;
             for story in stories {
                 if let Some(file_name) = story.file_name().and_then(|n| n.to_str()) {
                     all_files.push(story.clone());
@@ -109,7 +113,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
             }
         }
         "blog" => {
-            let posts = ProjectManager::get_blog_posts().await?;
+            let posts = project.get_blog_posts().await?;
             for post in posts {
                 if let Some(file_name) = post.file_name().and_then(|n| n.to_str()) {
                     all_files.push(post.clone());
@@ -119,7 +123,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
         }
         _ => {
             // Generic project - get all markdown files
-            let all_content = ProjectManager::get_all_content_files().await?;
+            let all_content = project.get_all_content_files().await?;
             for file in all_content {
                 if let Some(file_name) = file.file_name().and_then(|n| n.to_str()) {
                     all_files.push(file.clone());
@@ -130,7 +134,7 @@ async fn select_file_to_edit() -> Result<PathBuf> {
     }
 
     // Add notes
-    let notes = ProjectManager::get_notes().await?;
+    let notes = project.get_notes().await?;
     for note in notes {
         if let Some(file_name) = note.file_name().and_then(|n| n.to_str()) {
             all_files.push(note.clone());
@@ -139,14 +143,15 @@ async fn select_file_to_edit() -> Result<PathBuf> {
     }
 
     if all_files.is_empty() {
-        println!("{}", "No files found to edit.".yellow());
-        println!("{}", "💡 Create some content first".yellow());
+        outln!("{}", "No files found to edit.".yellow());
+        outln!("{}", "💡 Create some content first".yellow());
         return Err(anyhow::anyhow!("No files available"));
     }
 
     // Add README if it exists
-    if std::path::Path::new("README.md").exists() {
-        all_files.push(PathBuf::from("README.md"));
+    let readme = project.path("README.md");
+    if readme.exists() {
+        all_files.push(readme);
         file_descriptions.push("📖 README".to_string());
     }
 
@@ -159,72 +164,74 @@ async fn select_file_to_edit() -> Result<PathBuf> {
     Ok(all_files[selection].clone())
 }
 
-async fn launch_editor(file_path: PathBuf) -> Result<()> {
-    println!();
-    println!(
-        "{} {}",
-        "🚀 Launching editor for:".green(),
-        file_path.display().to_string().cyan()
-    );
-    println!();
-    println!("{}", "Editor Controls:".yellow().bold());
-    println!(
-        "  • {} - Switch between Navigation and Insert mode",
-        "ESC".cyan()
-    );
-    println!("  • {} - Save file", "Ctrl+S".cyan());
-    println!("  • {} - Quit editor", "Ctrl+Q".cyan());
-    println!("  • {} - Search text", "/".cyan());
-    println!("  • {} - Toggle typewriter mode", "Ctrl+T".cyan());
-    println!("  • {} - Toggle distraction-free mode", "F3".cyan());
-    println!("  • {} - Undo", "Ctrl+Z".cyan());
-    println!("  • {} - Redo", "Ctrl+Y".cyan());
-    println!();
-    println!("{}", "Navigation Mode:".yellow().bold());
-    println!(
-        "  • {} - Move cursor",
-        "hjkl, Shift+WASD, or arrow keys".cyan()
-    );
-    println!("  • {} - Enter insert mode", "i".cyan());
-    println!("  • {} - Enter insert mode at end of line", "a".cyan());
-    println!("  • {} - Create new line and enter insert mode", "o".cyan());
-    println!("  • {} - Delete current line", "dd".cyan());
-    println!("  • {} - Go to beginning/end of line", "0 / $".cyan());
-    println!("  • {} - Page up/down", "Page Up/Down".cyan());
-    println!();
-    println!("{}", "Insert Mode:".yellow().bold());
-    println!("  • {} - Type normally", "Any character".cyan());
-    println!("  • {} - Return to navigation mode", "ESC".cyan());
-    println!(
-        "  • {} - Move cursor while in insert mode",
-        "Arrow keys".cyan()
-    );
-    println!();
-    println!("{}", "Press any key to continue...".bright_black());
+/// How the built-in editor opens a file
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Replaces the project's word goal for the day
+    pub daily_goal: Option<usize>,
+    /// Start with the cursor at the end of the file
+    pub at_end: bool,
+    /// Run a timed sprint. The caller introduces it, so the controls screen
+    /// is skipped and the clock starts as the editor opens.
+    pub sprint: Option<Sprint>,
+}
 
-    // Wait for a key press (simple implementation)
-    use std::io::{self, Read};
-    let mut buffer = [0];
-    let _ = io::stdin().read(&mut buffer);
+pub async fn launch_editor(
+    project: &ProjectManager,
+    file_path: PathBuf,
+    options: LaunchOptions,
+) -> Result<()> {
+    let settings = project.get_config().await?.settings;
+
+    // A broken lint setting shouldn't keep the file from opening
+    let linter = match Linter::new(settings.as_ref()) {
+        Ok(linter) => Some(linter),
+        Err(e) => {
+            outln!("{} {}", "⚠️  Using the default lint rules:".yellow(), e);
+            None
+        }
+    };
+
+    if options.sprint.is_none() {
+        show_controls(&file_path);
+    }
 
     // Clear screen and launch editor
     print!("\x1B[2J\x1B[1;1H"); // Clear screen and move cursor to top-left
 
     let mut editor = WritersEditor::new();
+    editor.set_project(project.clone());
+    if let Some(linter) = linter {
+        editor.set_linter(linter);
+    }
+    editor.set_start_at_end(options.at_end);
+    editor.set_pomodoro_settings(
+        settings
+            .as_ref()
+            .and_then(|settings| settings.pomodoro.as_ref()),
+    );
+    if let Some(sprint) = options.sprint {
+        editor.set_sprint(sprint);
+    }
+    if let Ok(Some((goal, elsewhere))) =
+        goals::daily_goal_for(project, &file_path, options.daily_goal).await
+    {
+        editor.set_daily_goal(goal, elsewhere);
+    }
     let file_path_str = file_path.to_string_lossy().to_string();
 
     match editor.launch(Some(file_path_str)).await {
         Ok(_) => {
-            println!();
-            println!("{}", "✅ Editor session completed.".green());
+            outln!();
+            outln!("{}", "✅ Editor session completed.".green());
         }
         Err(e) => {
-            println!();
+            outln!();
             if e.to_string().contains("TTY") {
-                println!("{}", "❌ Editor requires a real terminal to run.".red());
-                println!("{}", "💡 Make sure you're running this command directly in a terminal, not through pipes or scripts.".yellow());
+                outln!("{}", "❌ Editor requires a real terminal to run.".red());
+                outln!("{}", "💡 Make sure you're running this command directly in a terminal, not through pipes or scripts.".yellow());
             } else {
-                println!("{} {}", "❌ Editor error:".red(), e);
+                outln!("{} {}", "❌ Editor error:".red(), e);
             }
             return Err(e);
         }
@@ -232,3 +239,63 @@ async fn launch_editor(file_path: PathBuf) -> Result<()> {
 
     Ok(())
 }
+
+fn show_controls(file_path: &Path) {
+    outln!();
+    outln!(
+        "{} {}",
+        "🚀 Launching editor for:".green(),
+        file_path.display().to_string().cyan()
+    );
+    outln!();
+    outln!("{}", "Editor Controls:".yellow().bold());
+    outln!(
+        "  • {} - Switch between Navigation and Insert mode",
+        "ESC".cyan()
+    );
+    outln!("  • {} - Save file", "Ctrl+S".cyan());
+    outln!("  • {} - Quit editor", "Ctrl+Q".cyan());
+    outln!("  • {} - Search text", "/".cyan());
+    outln!("  • {} - Toggle typewriter mode", "Ctrl+T".cyan());
+    outln!("  • {} - Toggle distraction-free mode", "F3".cyan());
+    outln!(
+        "  • {} - Check style and go to the next problem",
+        "Ctrl+L".cyan()
+    );
+    outln!("  • {} - Undo", "Ctrl+Z".cyan());
+    outln!("  • {} - Redo", "Ctrl+Y".cyan());
+    outln!("  • {} - Start/pause Pomodoro timer", "Ctrl+P".cyan());
+    outln!("  • {} - Reset Pomodoro timer", "F4".cyan());
+    outln!();
+    outln!("{}", "Navigation Mode:".yellow().bold());
+    outln!(
+        "  • {} - Move cursor",
+        "hjkl, Shift+WASD, or arrow keys".cyan()
+    );
+    outln!("  • {} - Enter insert mode", "i".cyan());
+    outln!("  • {} - Enter insert mode at end of line", "a".cyan());
+    outln!("  • {} - Create new line and enter insert mode", "o".cyan());
+    outln!("  • {} - Delete current line", "dd".cyan());
+    outln!("  • {} - Go to beginning/end of line", "0 / $".cyan());
+    outln!("  • {} - Page up/down", "Page Up/Down".cyan());
+    outln!();
+    outln!("{}", "Insert Mode:".yellow().bold());
+    outln!("  • {} - Type normally", "Any character".cyan());
+    outln!("  • {} - Return to navigation mode", "ESC".cyan());
+    outln!(
+        "  • {} - Move cursor while in insert mode",
+        "Arrow keys".cyan()
+    );
+    outln!();
+    outln!("{}", "Press any key to continue...".bright_black());
+
+    // Wait for a key press (simple implementation)
+    wait_for_key();
+}
+
+/// Block until a key is pressed
+pub fn wait_for_key() {
+    use std::io::{self, Read};
+    let mut buffer = [0];
+    let _ = io::stdin().read(&mut buffer);
+}
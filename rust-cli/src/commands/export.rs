@@ -1,19 +1,230 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use colored::*;
+use std::path::PathBuf;
+use tokio::fs as async_fs;
 
-pub async fn run(format: String, output: Option<String>, chapters: Option<String>) -> Result<()> {
-    println!("{}", "📤 Export Project".cyan().bold());
-    println!("Format: {}", format.cyan());
+use crate::export::docx::{self, DocxOptions};
+use crate::export::pdf::{self, PdfOptions};
+use crate::export::selection::{self, ChapterRanges};
+use crate::export::{epub, html, markdown, Manuscript};
+use crate::outln;
+use crate::utils::{output, ProjectManager};
+use serde_json::json;
 
-    if let Some(output) = output {
-        println!("Output: {}", output.cyan());
+pub async fn run(
+    project: &ProjectManager,
+    format: String,
+    output: Option<String>,
+    chapters: Option<String>,
+    status: Option<String>,
+    story: Option<String>,
+) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
     }
 
-    if let Some(chapters) = chapters {
-        println!("Chapters: {}", chapters.cyan());
+    let format = format.to_lowercase();
+    let extension = match format.as_str() {
+        "markdown" | "md" => "md",
+        "html" => "html",
+        "epub" => "epub",
+        "docx" => "docx",
+        "pdf" => "pdf",
+        _ => {
+            outln!("{} {}", "❌ Unsupported format:".red(), format);
+            outln!(
+                "{}",
+                "💡 Supported formats: markdown, html, epub, docx, pdf".yellow()
+            );
+            return Ok(());
+        }
+    };
+
+    outln!(
+        "{}",
+        format!("📤 Exporting to {}...", format.to_uppercase())
+            .cyan()
+            .bold()
+    );
+    outln!();
+
+    let ranges = match chapters.as_deref().map(ChapterRanges::parse) {
+        Some(Ok(ranges)) => Some(ranges),
+        Some(Err(e)) => {
+            outln!("{} {}", "❌".red(), e.to_string().red());
+            outln!("{}", "💡 Example: --chapters 1-5,8,12-".yellow());
+            return Ok(());
+        }
+        None => None,
+    };
+
+    if story.is_some() && (ranges.is_some() || status.is_some()) {
+        outln!(
+            "{}",
+            "⚠️  --chapters and --status only apply to chapter exports, ignoring them.".yellow()
+        );
+    }
+
+    let config = project.get_config().await?;
+
+    // Either a single story or the full set of chapters
+    let source_files = match &story {
+        Some(name) => match project.find_story(name).await? {
+            Some(file) => vec![file],
+            None => {
+                outln!("{} {}", "❌ Story not found:".red(), name);
+                outln!(
+                    "{}",
+                    "💡 Use \"writers list\" to see available stories".yellow()
+                );
+                return Ok(());
+            }
+        },
+        None => {
+            let all_chapters = project.get_chapters().await?;
+            let total = all_chapters.len();
+
+            if ranges.is_none() && status.is_none() {
+                all_chapters
+            } else {
+                let selected =
+                    selection::select(all_chapters, ranges.as_ref(), status.as_deref()).await?;
+                if total > 0 {
+                    if selected.is_empty() {
+                        outln!("{}", "⚠️  No chapters match the selection.".yellow());
+                        return Ok(());
+                    }
+                    outln!(
+                        "{} {} of {} chapters",
+                        "📑 Selected".cyan(),
+                        selected.len(),
+                        total
+                    );
+                }
+                selected
+            }
+        }
+    };
+
+    if source_files.is_empty() {
+        outln!("{}", "⚠️  No chapters found to export.".yellow());
+        outln!(
+            "{}",
+            "💡 Add markdown files to the chapters/ folder".yellow()
+        );
+        return Ok(());
+    }
+
+    let mut manuscript = match &story {
+        Some(_) => Manuscript::compile_story(&config, &source_files[0]).await?,
+        None => {
+            let parts = project.group_parts(&source_files).await;
+            Manuscript::compile(&config, &parts).await?
+        }
+    };
+    // The cover is configured relative to the project root
+    manuscript.cover = manuscript.cover.map(|cover| project.path(cover));
+
+    let mut total_words = 0;
+    for file in &source_files {
+        total_words += project.get_word_count(file).await.unwrap_or(0);
+    }
+
+    let output_path = match output {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let exports = project.path(config.dir("exports"));
+            async_fs::create_dir_all(&exports).await?;
+            exports.join(format!("{}.{}", manuscript.slug(), extension))
+        }
+    };
+
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            async_fs::create_dir_all(parent).await?;
+        }
+    }
+
+    match extension {
+        "html" => async_fs::write(&output_path, html::render(&manuscript)).await?,
+        "epub" => {
+            // Build next to the output and only move it into place once it
+            // validates, so a broken EPUB never sits at the output path
+            let staging = output_path.with_extension("epub.partial");
+            let problems =
+                match epub::write(&manuscript, &staging).and_then(|_| epub::validate(&staging)) {
+                    Ok(problems) => problems,
+                    Err(e) => {
+                        let _ = async_fs::remove_file(&staging).await;
+                        return Err(e);
+                    }
+                };
+            if !problems.is_empty() {
+                let _ = async_fs::remove_file(&staging).await;
+                outln!("{}", "❌ EPUB validation found problems:".red());
+                for problem in &problems {
+                    outln!("  • {}", problem);
+                }
+                return Err(anyhow!(
+                    "The EPUB failed validation, so {} was not written",
+                    project.display_path(&output_path)
+                ));
+            }
+            async_fs::rename(&staging, &output_path).await?;
+        }
+        "docx" => {
+            if config.contact.is_none() {
+                outln!(
+                    "{}",
+                    "⚠️  No contact details in writers.config.json, the first page will only show your name."
+                        .yellow()
+                );
+            }
+
+            let options = DocxOptions {
+                contact: config.contact.clone().unwrap_or_default(),
+                word_count: total_words,
+                font: config
+                    .settings
+                    .as_ref()
+                    .and_then(|s| s.manuscript_font.clone())
+                    .unwrap_or_else(|| "courier".to_string()),
+                chapter_headings: story.is_none(),
+            };
+            docx::write(&manuscript, &options, &output_path)?;
+        }
+        "pdf" => {
+            let options = PdfOptions::from_settings(config.settings.as_ref())?;
+            pdf::write(&manuscript, &options, &output_path)?;
+        }
+        _ => async_fs::write(&output_path, markdown::render(&manuscript)).await?,
+    }
+
+    if output::is_json() {
+        return output::json(&json!({
+            "format": extension,
+            "output": project.display_path(&output_path),
+            "chapters": manuscript.chapters.len(),
+            "words": total_words,
+        }));
     }
 
-    println!("{}", "📝 This feature is coming soon!".bright_black());
+    outln!(
+        "{} {} chapters ({} words)",
+        "✅ Compiled".green(),
+        manuscript.chapters.len().to_string().cyan(),
+        total_words.to_string().cyan()
+    );
+    outln!(
+        "{} {}",
+        "📁 Saved to:".green(),
+        project.display_path(&output_path).cyan()
+    );
 
     Ok(())
 }
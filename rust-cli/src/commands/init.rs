@@ -3,34 +3,81 @@ use colored::*;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use tokio::fs as async_fs;
 
+use crate::outln;
+use crate::utils::templates::{self, TemplateVars};
 use crate::utils::ProjectManager;
 
-pub async fn run(name: Option<String>, author: Option<String>) -> Result<()> {
-    println!(
+pub async fn run(
+    project: &ProjectManager,
+    name: Option<String>,
+    author: Option<String>,
+    template: Option<String>,
+) -> Result<()> {
+    outln!(
         "{}",
         "📝 Writers CLI - Project Initialization".cyan().bold()
     );
-    println!();
+    outln!();
 
     // Check if already in a project
-    if ProjectManager::is_writers_project() {
-        println!("{}", "⚠️  Already in a Writers project!".yellow());
+    if project.is_writers_project() {
+        outln!("{}", "⚠️  Already in a Writers project!".yellow());
         let overwrite = Confirm::with_theme(&ColorfulTheme::default())
             .with_prompt("Do you want to reinitialize this project?")
             .default(false)
             .interact()?;
 
         if !overwrite {
-            println!("{}", "Project initialization cancelled.".bright_black());
+            outln!("{}", "Project initialization cancelled.".bright_black());
             return Ok(());
         }
     }
 
+    // Resolve the project template up front so a typo doesn't leave a half-made project
+    let project_template = match template {
+        Some(template) => {
+            let available = templates::project_templates().await?;
+            match available
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&template))
+            {
+                Some((_, dir)) => Some(dir.clone()),
+                None => {
+                    outln!("{} {}", "❌ Project template not found:".red(), template);
+                    if available.is_empty() {
+                        if let Some(dir) = templates::user_dir() {
+                            outln!(
+                                "{}",
+                                format!(
+                                    "💡 Project templates are folders in {}",
+                                    dir.join("projects").display()
+                                )
+                                .yellow()
+                            );
+                        }
+                    } else {
+                        let names: Vec<&str> =
+                            available.iter().map(|(name, _)| name.as_str()).collect();
+                        outln!(
+                            "{}",
+                            format!("💡 Available templates: {}", names.join(", ")).yellow()
+                        );
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        None => None,
+    };
+
     // Get project name
     let project_name = match name {
         Some(n) => n,
         None => {
-            let current_dir = std::env::current_dir()?
+            let current_dir = project
+                .root()
+                .canonicalize()
+                .unwrap_or_else(|_| project.root().to_path_buf())
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("my-novel")
@@ -72,19 +119,21 @@ pub async fn run(name: Option<String>, author: Option<String>) -> Result<()> {
         _ => "novel",
     };
 
-    println!();
-    println!("{}", "🚀 Creating project structure...".green());
+    outln!();
+    outln!("{}", "🚀 Creating project structure...".green());
 
     // Create project structure
-    ProjectManager::create_project_structure(project_type).await?;
+    async_fs::create_dir_all(project.root()).await?;
+    project.create_project_structure(project_type).await?;
 
     // Create configuration
-    let _config = ProjectManager::create_project(
-        project_name.clone(),
-        author_name.clone(),
-        project_type.to_string(),
-    )
-    .await?;
+    let _config = project
+        .create_project(
+            project_name.clone(),
+            author_name.clone(),
+            project_type.to_string(),
+        )
+        .await?;
 
     // Create basic README
     let readme_content = format!(
@@ -106,36 +155,54 @@ Use `writers --help` to see available commands.
         chrono::Utc::now().format("%Y-%m-%d")
     );
 
-    async_fs::write("README.md", readme_content).await?;
+    async_fs::write(project.path("README.md"), readme_content).await?;
+
+    if let Some(template_dir) = project_template {
+        let vars = TemplateVars {
+            title: project_name.clone(),
+            author: author_name.clone(),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            number: None,
+        };
+        let files = templates::apply_project_template(project, &template_dir, &vars).await?;
+        outln!(
+            "{} {} files from the template",
+            "📋 Added".green(),
+            files.len().to_string().cyan()
+        );
+    }
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "{} {}",
         "✅ Project".green().bold(),
         format!("'{}'", project_name).cyan().bold()
     );
-    println!("{}", "   successfully initialized!".green().bold());
-    println!();
+    outln!("{}", "   successfully initialized!".green().bold());
+    outln!();
 
-    println!("{}", "📖 Next Steps:".blue().bold());
+    outln!("{}", "📖 Next Steps:".blue().bold());
     match project_type {
         "novel" => {
-            println!("   1. Create chapters: {}", "mkdir chapters".green());
-            println!(
-                "   2. Start writing: {}",
-                "touch chapters/chapter1.md".green()
+            outln!(
+                "   1. Create a chapter: {}",
+                "writers new chapter --open".green()
             );
+            outln!("   2. Add characters: {}", "writers new character".green());
         }
         "short-story" => {
-            println!("   1. Create drafts folder: {}", "mkdir drafts".green());
-            println!("   2. Start writing: {}", "touch drafts/story.md".green());
+            outln!(
+                "   1. Create a story: {}",
+                "writers new story --open".green()
+            );
+            outln!("   2. Add characters: {}", "writers new character".green());
         }
         "blog" => {
-            println!("   1. Create posts folder: {}", "mkdir posts".green());
-            println!(
-                "   2. Start writing: {}",
-                "touch posts/first-post.md".green()
+            outln!(
+                "   1. Create a post: {}",
+                "writers new blogpost --open".green()
             );
+            outln!("   2. Keep writing: {}", "writers edit".green());
         }
         _ => {}
     }
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde_json::json;
+use std::path::PathBuf;
+use tokio::fs as async_fs;
+
+use crate::outln;
+use crate::utils::lint::{Linter, Severity};
+use crate::utils::output::plural;
+use crate::utils::{output, ProjectManager};
+
+/// Check content files against the project's style rules. Fails when any
+/// rule set to "error" finds something, so it can gate a handoff.
+pub async fn run(project: &ProjectManager, files: Vec<String>, list_rules: bool) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let config = project.get_config().await?;
+    let linter = Linter::new(config.settings.as_ref())?;
+
+    if list_rules {
+        return print_rules(&linter);
+    }
+
+    let paths = if files.is_empty() {
+        project.get_all_content_files().await?
+    } else {
+        let mut paths = Vec::new();
+        for name in &files {
+            match resolve(project, name).await? {
+                Some(path) => paths.push(path),
+                None => {
+                    // An error, so a typo in CI doesn't pass as a clean lint
+                    outln!(
+                        "{}",
+                        "💡 Use \"writers list\" to see available files".yellow()
+                    );
+                    return Err(anyhow!("File not found: {}", name));
+                }
+            }
+        }
+        paths
+    };
+
+    let mut results = Vec::new();
+    for path in &paths {
+        let content = async_fs::read_to_string(path).await?;
+        results.push((project.display_path(path), linter.check(&content)));
+    }
+
+    let count = |severity: Severity| {
+        results
+            .iter()
+            .flat_map(|(_, diagnostics)| diagnostics)
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    let (errors, warnings, infos) = (
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Info),
+    );
+
+    if output::is_json() {
+        let diagnostics: Vec<_> = results
+            .iter()
+            .flat_map(|(path, diagnostics)| {
+                diagnostics.iter().map(move |diagnostic| {
+                    json!({
+                        "path": path,
+                        "line": diagnostic.line,
+                        "column": diagnostic.column,
+                        "rule": diagnostic.rule,
+                        "severity": diagnostic.severity,
+                        "message": diagnostic.message,
+                    })
+                })
+            })
+            .collect();
+        output::json(&json!({
+            "files": paths.len(),
+            "errors": errors,
+            "warnings": warnings,
+            "infos": infos,
+            "diagnostics": diagnostics,
+        }))?;
+    } else {
+        for (path, diagnostics) in &results {
+            for diagnostic in diagnostics {
+                let rule = match diagnostic.severity {
+                    Severity::Error => diagnostic.rule.red().bold(),
+                    Severity::Warning => diagnostic.rule.yellow(),
+                    _ => diagnostic.rule.blue(),
+                };
+                outln!(
+                    "{}:{}:{}: {}: {}",
+                    path,
+                    diagnostic.line,
+                    diagnostic.column,
+                    rule,
+                    diagnostic.message
+                );
+            }
+        }
+
+        if errors + warnings + infos == 0 {
+            outln!(
+                "{}",
+                format!("✅ No style problems in {}", plural(paths.len(), "file")).green()
+            );
+        } else {
+            outln!();
+            outln!(
+                "{} {}, {}, {} in {}",
+                "📋".cyan(),
+                plural(errors, "error").red(),
+                plural(warnings, "warning").yellow(),
+                plural(infos, "note").blue(),
+                plural(paths.len(), "file")
+            );
+        }
+    }
+
+    if errors > 0 {
+        return Err(anyhow!("{} found", plural(errors, "lint error")));
+    }
+    Ok(())
+}
+
+/// A file named on the command line: a path from the current directory or
+/// the project root, or else a content file name as `writers edit` takes
+async fn resolve(project: &ProjectManager, name: &str) -> Result<Option<PathBuf>> {
+    for candidate in [std::env::current_dir()?.join(name), project.path(name)] {
+        if candidate.is_file() {
+            return Ok(Some(candidate));
+        }
+    }
+    project.find_file(name).await
+}
+
+fn print_rules(linter: &Linter) -> Result<()> {
+    if output::is_json() {
+        let rules: Vec<_> = linter
+            .rules()
+            .map(|(rule, severity)| {
+                json!({
+                    "name": rule.name(),
+                    "severity": severity,
+                    "description": rule.description(),
+                })
+            })
+            .collect();
+        return output::json(&rules);
+    }
+
+    outln!("{}", "📏 Lint Rules".cyan().bold());
+    outln!();
+    for (rule, severity) in linter.rules() {
+        let label = format!("{:<8}", severity.to_string());
+        let label = match severity {
+            Severity::Error => label.red(),
+            Severity::Warning => label.yellow(),
+            Severity::Info => label.blue(),
+            Severity::Off => label.bright_black(),
+        };
+        outln!(
+            "  {} {}  {}",
+            label,
+            format!("{:<20}", rule.name()).cyan(),
+            rule.description().bright_black()
+        );
+    }
+    outln!();
+    outln!(
+        "{}",
+        "💡 Change severities with \"lint_rules\" under settings in writers.config.json, e.g. \"passive-voice\": \"off\""
+            .bright_black()
+    );
+
+    Ok(())
+}
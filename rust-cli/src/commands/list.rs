@@ -1,51 +1,155 @@
 use anyhow::Result;
 use colored::*;
 
-use crate::utils::ProjectManager;
+use crate::outln;
+use crate::utils::{output, ContentFile, ProjectConfig, ProjectManager};
+use serde_json::json;
 
-pub async fn run() -> Result<()> {
+pub async fn run(project: &ProjectManager) -> Result<()> {
     // Check if in a writers project
-    if !ProjectManager::is_writers_project() {
-        println!(
+    if !project.is_writers_project() {
+        outln!(
             "{}",
             "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
         );
         return Ok(());
     }
 
-    let config = ProjectManager::get_config().await?;
+    let config = project.get_config().await?;
 
-    println!("{}", "📋 Project Contents".cyan().bold());
-    println!();
-    println!("Project: {}", config.name.cyan());
-    println!("Type: {}", config.r#type.cyan());
-    println!();
+    if output::is_json() {
+        return print_json(project, &config).await;
+    }
+
+    outln!("{}", "📋 Project Contents".cyan().bold());
+    outln!();
+    outln!("Project: {}", config.name.cyan());
+    outln!("Type: {}", config.r#type.cyan());
+    outln!();
 
     // Get all content files
-    let all_files = ProjectManager::get_all_content_files().await?;
+    let all_files = project.get_all_content_files().await?;
 
     if all_files.is_empty() {
-        println!("{}", "No content files found.".yellow());
-        println!("{}", "💡 Create some files to get started!".bright_black());
+        outln!("{}", "No content files found.".yellow());
+        outln!("{}", "💡 Create some files to get started!".bright_black());
         return Ok(());
     }
 
-    println!("{}", "📄 Files:".green().bold());
-    for file in all_files {
-        if let Some(file_name) = file.file_name().and_then(|n| n.to_str()) {
-            if let Ok(content) = tokio::fs::read_to_string(&file).await {
-                let word_count = content.split_whitespace().count();
-                println!(
-                    "  • {} ({} words)",
-                    file_name.cyan(),
-                    word_count.to_string().yellow()
-                );
-            } else {
-                println!("  • {}", file_name.cyan());
+    let parts = project.get_parts().await?;
+    let has_parts = parts.iter().any(|part| part.title.is_some());
+
+    // Chapters in parts are shown as a tree, everything else as a flat list
+    if has_parts {
+        outln!("{}", "📖 Chapters:".green().bold());
+        for part in &parts {
+            let chapters = project.read_contents(&part.chapters).await;
+            match &part.title {
+                Some(title) => {
+                    let words: usize = chapters.iter().map(ContentFile::word_count).sum();
+                    outln!(
+                        "  📂 {} ({}, {})",
+                        title.bold(),
+                        format!("{} chapters", chapters.len()).bright_black(),
+                        format!("{} words", words).yellow()
+                    );
+                    for (index, file) in chapters.iter().enumerate() {
+                        let branch = if index + 1 == chapters.len() {
+                            "└─"
+                        } else {
+                            "├─"
+                        };
+                        print_file(file, &format!("    {} ", branch), "       ");
+                    }
+                }
+                None => {
+                    for file in &chapters {
+                        print_file(file, "  • ", "      ");
+                    }
+                }
             }
         }
+        outln!();
+    }
+
+    let files: Vec<_> = if has_parts {
+        let chapters: Vec<_> = parts.iter().flat_map(|part| &part.chapters).collect();
+        all_files
+            .iter()
+            .filter(|file| !chapters.contains(file))
+            .cloned()
+            .collect()
+    } else {
+        all_files
+    };
+
+    if !files.is_empty() {
+        outln!("{}", "📄 Files:".green().bold());
+        for file in project.read_contents(&files).await {
+            print_file(&file, "  • ", "      ");
+        }
+        outln!();
     }
-    println!();
 
     Ok(())
 }
+
+/// The project's files and parts as one JSON document
+async fn print_json(project: &ProjectManager, config: &ProjectConfig) -> Result<()> {
+    let files = project
+        .read_contents(&project.get_all_content_files().await?)
+        .await;
+    let files: Vec<_> = files.iter().map(|file| project.summarize(file)).collect();
+
+    let parts: Vec<_> = project
+        .get_parts()
+        .await?
+        .into_iter()
+        .filter(|part| part.title.is_some())
+        .map(|part| {
+            let chapters: Vec<_> = part
+                .chapters
+                .iter()
+                .map(|chapter| project.display_path(chapter))
+                .collect();
+            json!({ "title": part.title, "chapters": chapters })
+        })
+        .collect();
+
+    output::json(&json!({
+        "project": config.name,
+        "type": config.r#type,
+        "parts": parts,
+        "files": files,
+    }))
+}
+
+/// Print a file's name, word count and metadata, with its synopsis below
+fn print_file(file: &ContentFile, prefix: &str, indent: &str) {
+    let words = match file.meta.target_words {
+        Some(target) => format!("{}/{} words", file.word_count(), target),
+        None => format!("{} words", file.word_count()),
+    };
+
+    let mut line = format!("{}{} ({})", prefix, file.file_name().cyan(), words.yellow());
+    if let Some(title) = &file.meta.title {
+        line.push_str(&format!(" {}", title.bold()));
+    }
+    if let Some(status) = &file.meta.status {
+        line.push_str(&format!(" [{}]", status.magenta()));
+    }
+    if let Some(pov) = &file.meta.pov {
+        line.push_str(&format!(" {}", format!("POV: {}", pov).bright_black()));
+    }
+    if !file.meta.tags.is_empty() {
+        line.push_str(&format!(
+            " {}",
+            format!("#{}", file.meta.tags.join(" #")).blue()
+        ));
+    }
+    outln!("{}", line);
+
+    if let Some(synopsis) = &file.meta.synopsis {
+        outln!("{}{}", indent, synopsis.bright_black());
+    }
+}
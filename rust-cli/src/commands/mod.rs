@@ -1,4 +1,15 @@
+pub mod analyze;
+pub mod config;
 pub mod edit;
+pub mod export;
 pub mod init;
+pub mod lint;
 pub mod list;
+pub mod new;
+pub mod progress;
+pub mod reorder;
+pub mod sprint;
 pub mod stats;
+pub mod story;
+pub mod submissions;
+pub mod workflow;
@@ -1,23 +1,211 @@
 use anyhow::Result;
 use colored::*;
+use dialoguer::{theme::ColorfulTheme, Input};
+use std::path::Path;
+use tokio::fs as async_fs;
+
+use crate::outln;
+use crate::utils::templates::{self, TemplateVars};
+use crate::utils::{slugify, ContentType, ProjectManager};
 
 pub async fn run(
+    project: &ProjectManager,
     content_type: String,
     name: Option<String>,
     template: Option<String>,
+    open: bool,
 ) -> Result<()> {
-    println!("{}", "📝 Create New Content".cyan().bold());
-    println!("Type: {}", content_type.cyan());
-
-    if let Some(name) = name {
-        println!("Name: {}", name.cyan());
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
     }
 
-    if let Some(template) = template {
-        println!("Template: {}", template.cyan());
+    let content_type = match ContentType::parse(&content_type) {
+        Some(content_type) => content_type,
+        None => {
+            let types: Vec<&str> = ContentType::ALL.iter().map(|t| t.name()).collect();
+            outln!("{} {}", "❌ Unknown content type:".red(), content_type);
+            outln!(
+                "{}",
+                format!("💡 Available types: {}", types.join(", ")).yellow()
+            );
+            return Ok(());
+        }
+    };
+
+    let template_name = template.unwrap_or_else(|| "basic".to_string());
+    let template = match templates::find(project, content_type, &template_name).await? {
+        Some(template) => template,
+        None => {
+            outln!(
+                "{} {}",
+                format!("❌ No {} template named", content_type.name()).red(),
+                template_name
+            );
+            outln!("{}", "💡 Available templates:".yellow());
+            for template in templates::available(project, content_type).await? {
+                outln!(
+                    "   {} - {} {}",
+                    template.name.cyan(),
+                    template.description,
+                    format!("({})", template.source.label()).bright_black()
+                );
+            }
+            outln!(
+                "{}",
+                format!(
+                    "💡 Add your own as {}/{}/<name>.md",
+                    templates::PROJECT_DIR,
+                    content_type.name()
+                )
+                .yellow()
+            );
+            return Ok(());
+        }
+    };
+
+    let config = project.get_config().await?;
+    let directory = project.path(config.dir(content_type.directory(&config.r#type)));
+    async_fs::create_dir_all(&directory).await?;
+
+    let (path, title, number) = if content_type.is_numbered() {
+        let number = next_number(project, &directory, content_type.name()).await?;
+        let path = directory.join(format!("{}-{:02}.md", content_type.name(), number));
+        let title =
+            name.unwrap_or_else(|| format!("{} {}", capitalize(content_type.name()), number));
+        (path, title, Some(number))
+    } else {
+        let name = match name {
+            Some(name) => name,
+            None => Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Name of the {}", content_type.name()))
+                .interact_text()?,
+        };
+
+        let slug = slugify(&name);
+        if slug.is_empty() {
+            outln!("{}", "❌ Please use a name with letters or numbers.".red());
+            return Ok(());
+        }
+
+        (directory.join(format!("{}.md", slug)), name, None)
+    };
+
+    if path.exists() {
+        outln!(
+            "{} {}",
+            "❌ File already exists:".red(),
+            project.display_path(&path).cyan()
+        );
+        outln!(
+            "{}",
+            "💡 Choose another name, or use \"writers edit\" to open it".yellow()
+        );
+        return Ok(());
     }
 
-    println!("{}", "📝 This feature is coming soon!".bright_black());
+    let vars = TemplateVars {
+        title: title.clone(),
+        author: config.author.clone(),
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        number,
+    };
+    async_fs::write(&path, template.render(&vars)).await?;
+
+    outln!(
+        "{} {}",
+        format!("✅ Created {}:", content_type.name()).green(),
+        title.cyan().bold()
+    );
+    outln!("   {}", project.display_path(&path).bright_black());
+
+    if open {
+        super::edit::launch_editor(project, path, Default::default()).await?;
+    } else {
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        outln!();
+        outln!(
+            "{} {}",
+            "💡 Start writing:".yellow(),
+            format!("writers edit {}", stem).cyan()
+        );
+    }
 
     Ok(())
 }
+
+/// Next free number for files named like `chapter-07.md` in the directory,
+/// counting files in part folders too
+async fn next_number(project: &ProjectManager, directory: &Path, prefix: &str) -> Result<usize> {
+    let highest = project
+        .markdown_files(directory)
+        .await?
+        .iter()
+        .filter_map(|path| file_number(path.file_stem()?.to_str()?, prefix))
+        .max()
+        .unwrap_or(0);
+
+    Ok(highest + 1)
+}
+
+/// The number in a file stem like `chapter-07`, if it has the prefix
+fn file_number(stem: &str, prefix: &str) -> Option<usize> {
+    let stem = stem.to_lowercase();
+    let digits: String = stem
+        .strip_prefix(prefix)?
+        .trim_start_matches(['-', '_', ' '])
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::project_with;
+
+    #[test]
+    fn numbers_come_from_prefixed_stems() {
+        assert_eq!(file_number("chapter-07", "chapter"), Some(7));
+        assert_eq!(file_number("Chapter_12-the-storm", "chapter"), Some(12));
+        assert_eq!(file_number("chapter", "chapter"), None);
+        assert_eq!(file_number("scene-3", "chapter"), None);
+    }
+
+    #[test]
+    fn capitalizes_the_first_letter() {
+        assert_eq!(capitalize("chapter"), "Chapter");
+        assert_eq!(capitalize("édition"), "Édition");
+        assert_eq!(capitalize(""), "");
+    }
+
+    #[tokio::test]
+    async fn numbering_counts_chapters_in_part_folders() {
+        let (dir, project) = project_with(&[
+            ("chapters/chapter-01.md", ""),
+            ("chapters/part-2/chapter-05.md", ""),
+            ("chapters/notes.txt", ""),
+        ]);
+        let chapters = dir.path().join("chapters");
+        assert_eq!(
+            next_number(&project, &chapters, "chapter").await.unwrap(),
+            6
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use chrono::{Duration, Local};
+use colored::*;
+
+use crate::outln;
+use crate::utils::output::plural;
+use crate::utils::{history, output, ProjectManager};
+use serde_json::json;
+
+pub async fn run(project: &ProjectManager, days: usize) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    history::record(project).await?;
+    let snapshots = history::load(project).await?;
+
+    if output::is_json() {
+        return print_json(&snapshots, days);
+    }
+
+    outln!("{}", "📈 Writing Progress".cyan().bold());
+    outln!();
+
+    if snapshots.len() < 2 {
+        outln!(
+            "{}",
+            "No history yet. Word counts are recorded every time you run a command or save in the editor."
+                .yellow()
+        );
+        outln!(
+            "{}",
+            "💡 Write something and check back later!".bright_black()
+        );
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+    let per_day = history::words_per_day(&snapshots);
+    let per_week = history::words_per_week(&per_day);
+    let this_week = history::week_start(today);
+
+    let written_today = per_day.get(&today).copied().unwrap_or(0);
+    let written_this_week = per_week.get(&this_week).copied().unwrap_or(0);
+    outln!("  Today: {} words", signed(written_today).cyan());
+    outln!("  This week: {} words", signed(written_this_week).cyan());
+
+    let (current, longest) = history::streaks(&per_day, today);
+    outln!(
+        "  Streak: {} (longest {})",
+        plural(current, "day").cyan(),
+        plural(longest, "day").cyan()
+    );
+    outln!();
+
+    // Bars are scaled to the best day shown
+    let shown: Vec<_> = (0..days)
+        .rev()
+        .map(|ago| today - Duration::days(ago as i64))
+        .collect();
+    let best = shown
+        .iter()
+        .filter_map(|day| per_day.get(day))
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    outln!("{}", format!("📅 Last {} days:", days).green().bold());
+    for day in &shown {
+        let words = per_day.get(day).copied().unwrap_or(0);
+        let bar = "█".repeat((words.max(0) * 30 / best) as usize);
+        let label = day.format("%a %Y-%m-%d").to_string();
+        if words == 0 {
+            outln!(
+                "  {}  {}",
+                label.bright_black(),
+                format!("{:>7}", "-").bright_black()
+            );
+        } else {
+            outln!("  {}  {:>7}  {}", label, signed(words), bar.green());
+        }
+    }
+    outln!();
+
+    outln!("{}", "📆 By week:".green().bold());
+    for (week, words) in per_week.iter().rev().take(8) {
+        outln!(
+            "  Week of {}  {}",
+            week.format("%Y-%m-%d"),
+            format!("{:>7}", signed(*words)).cyan()
+        );
+    }
+    outln!();
+
+    Ok(())
+}
+
+/// The same figures as one JSON document. Days with no snapshot count as zero.
+fn print_json(snapshots: &[history::Snapshot], days: usize) -> Result<()> {
+    let today = Local::now().date_naive();
+    let per_day = history::words_per_day(snapshots);
+    let per_week = history::words_per_week(&per_day);
+    let (current, longest) = history::streaks(&per_day, today);
+
+    let shown: Vec<_> = (0..days)
+        .rev()
+        .map(|ago| today - Duration::days(ago as i64))
+        .map(|day| json!({ "date": day, "words": per_day.get(&day).copied().unwrap_or(0) }))
+        .collect();
+    let weeks: Vec<_> = per_week
+        .iter()
+        .rev()
+        .take(8)
+        .map(|(week, words)| json!({ "week_of": week, "words": words }))
+        .collect();
+
+    output::json(&json!({
+        "today": per_day.get(&today).copied().unwrap_or(0),
+        "this_week": per_week.get(&history::week_start(today)).copied().unwrap_or(0),
+        "streak": { "current": current, "longest": longest },
+        "days": shown,
+        "weeks": weeks,
+    }))
+}
+
+fn signed(words: i64) -> String {
+    if words > 0 {
+        format!("+{}", words)
+    } else {
+        words.to_string()
+    }
+}
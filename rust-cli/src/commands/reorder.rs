@@ -0,0 +1,291 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+use tokio::fs as async_fs;
+
+use crate::outln;
+use crate::utils::{frontmatter, output, ContentFile, ProjectManager};
+use serde_json::json;
+
+pub async fn run(
+    project: &ProjectManager,
+    chapter: Option<String>,
+    up: bool,
+    down: bool,
+    to: Option<usize>,
+    renumber: bool,
+) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let mut chapters = project.get_chapters().await?;
+    if chapters.is_empty() {
+        outln!("{}", "⚠️  No chapters found.".yellow());
+        outln!(
+            "{}",
+            "💡 Use \"writers new chapter\" to create one".yellow()
+        );
+        return Ok(());
+    }
+
+    let mut changed = false;
+
+    if let Some(target) = chapter {
+        let index = match find_chapter(&chapters, &target) {
+            Some(index) => index,
+            None => {
+                outln!("{} {}", "❌ Chapter not found:".red(), target);
+                outln!(
+                    "{}",
+                    "💡 Use a file name or a position from \"writers reorder\"".yellow()
+                );
+                return Ok(());
+            }
+        };
+
+        let last = chapters.len() - 1;
+        let new_index = if up {
+            index.saturating_sub(1)
+        } else if down {
+            (index + 1).min(last)
+        } else if let Some(position) = to {
+            position.saturating_sub(1).min(last)
+        } else {
+            outln!(
+                "{}",
+                "❌ Say where to move it: --up, --down or --to <position>".red()
+            );
+            return Ok(());
+        };
+
+        let moved = chapters.remove(index);
+        chapters.insert(new_index, moved);
+        changed = new_index != index;
+    }
+
+    if renumber {
+        chapters = renumber_files(&chapters).await?;
+        changed = true;
+    }
+
+    if changed {
+        // Entries are relative to the chapters folder so nested parts stay unambiguous
+        let chapters_dir = project.content_dir("chapters").await;
+        let order: Vec<String> = chapters
+            .iter()
+            .filter_map(|path| path.strip_prefix(&chapters_dir).ok())
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect();
+        save_order(project, order).await?;
+        outln!("{}", "✅ Chapter order updated".green());
+        outln!();
+    }
+
+    let contents = project.read_contents(&chapters).await;
+    let title_of = |file: &ContentFile| {
+        file.meta
+            .title
+            .clone()
+            .or_else(|| heading(&file.body).map(String::from))
+            .unwrap_or_default()
+    };
+
+    if output::is_json() {
+        let order: Vec<_> = contents
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                json!({
+                    "position": index + 1,
+                    "path": project.display_path(&file.path),
+                    "title": title_of(file),
+                })
+            })
+            .collect();
+        return output::json(&json!({ "changed": changed, "chapters": order }));
+    }
+
+    outln!("{}", "📖 Reading order:".cyan().bold());
+    for (index, file) in contents.iter().enumerate() {
+        let title = title_of(file);
+        outln!(
+            "  {:>3}. {} {}",
+            index + 1,
+            file.file_name().cyan(),
+            title.bright_black()
+        );
+    }
+
+    Ok(())
+}
+
+/// Write the chapter order into writers.config.json, leaving every other key
+/// as it is so configs the Node CLI still reads keep working
+async fn save_order(project: &ProjectManager, order: Vec<String>) -> Result<()> {
+    let mut raw = project.get_raw_config().await?;
+    let root = raw
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("writers.config.json is not a JSON object"))?;
+    let key = if root.contains_key("chapterOrder") {
+        "chapterOrder"
+    } else {
+        "chapter_order"
+    };
+    root.insert(key.to_string(), json!(order));
+    project.save_raw_config(&raw).await
+}
+
+/// Find a chapter by position (1-based), exact file name, or partial name
+fn find_chapter(chapters: &[PathBuf], target: &str) -> Option<usize> {
+    if let Ok(position) = target.parse::<usize>() {
+        if (1..=chapters.len()).contains(&position) {
+            return Some(position - 1);
+        }
+    }
+
+    let target = target.trim_end_matches(".md").to_lowercase();
+    let stem_of = |path: &PathBuf| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .map(|stem| stem.to_lowercase())
+            .unwrap_or_default()
+    };
+
+    chapters
+        .iter()
+        .position(|path| stem_of(path) == target)
+        .or_else(|| {
+            chapters
+                .iter()
+                .position(|path| stem_of(path).contains(&target))
+        })
+}
+
+fn heading(body: &str) -> Option<&str> {
+    body.lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.strip_prefix("# "))
+        .map(str::trim)
+}
+
+/// Rename chapters to `chapter-01.md`, `chapter-02.md`, ... in the given order,
+/// updating headings like "# Chapter 7" to match. Returns the new paths.
+async fn renumber_files(chapters: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut renamed = Vec::with_capacity(chapters.len());
+    let mut staged = Vec::new();
+
+    // Move changing files aside first so new names never clash with old ones
+    for (index, path) in chapters.iter().enumerate() {
+        let number = index + 1;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let target = dir.join(format!("chapter-{:02}.md", number));
+
+        let content = async_fs::read_to_string(path).await?;
+        if let Some(updated) = renumber_heading(&content, number) {
+            async_fs::write(path, updated).await?;
+        }
+
+        if &target != path {
+            let temp = dir.join(format!(".reorder-{}.tmp", number));
+            async_fs::rename(path, &temp).await?;
+            staged.push((temp, target.clone()));
+        }
+        renamed.push(target);
+    }
+
+    for (temp, target) in staged {
+        async_fs::rename(temp, target).await?;
+    }
+
+    Ok(renamed)
+}
+
+/// Replace the number in a leading "# Chapter N" heading, keeping any subtitle
+fn renumber_heading(content: &str, number: usize) -> Option<String> {
+    let body = frontmatter::body(content);
+    let mut offset = content.len() - body.len();
+
+    let line = body
+        .split_inclusive('\n')
+        .inspect(|line| {
+            if line.trim().is_empty() {
+                offset += line.len();
+            }
+        })
+        .find(|line| !line.trim().is_empty())?;
+
+    let rest = line.strip_prefix("# Chapter ")?;
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || rest[..digits].parse::<usize>().ok() == Some(number) {
+        return None;
+    }
+
+    Some(format!(
+        "{}# Chapter {}{}{}",
+        &content[..offset],
+        number,
+        &rest[digits..],
+        &content[offset + line.len()..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::project_with;
+
+    #[test]
+    fn chapters_are_found_by_position_or_name() {
+        let chapters: Vec<PathBuf> = ["chapter-1.md", "chapter-2.md", "chapter-12.md"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(find_chapter(&chapters, "2"), Some(1));
+        assert_eq!(find_chapter(&chapters, "chapter-12.md"), Some(2));
+        assert_eq!(find_chapter(&chapters, "Chapter-1"), Some(0));
+        assert_eq!(find_chapter(&chapters, "-12"), Some(2));
+        assert_eq!(find_chapter(&chapters, "9"), None);
+    }
+
+    #[test]
+    fn headings_are_renumbered_after_frontmatter() {
+        let content = "---\ntitle: Storm\n---\n\n# Chapter 7: Storm\n\nText.\n";
+        assert_eq!(
+            renumber_heading(content, 2).unwrap(),
+            "---\ntitle: Storm\n---\n\n# Chapter 2: Storm\n\nText.\n"
+        );
+        assert_eq!(renumber_heading(content, 7), None);
+        assert_eq!(renumber_heading("# Prologue\n", 1), None);
+    }
+
+    #[tokio::test]
+    async fn saving_the_order_keeps_the_rest_of_a_node_config() {
+        let original = r#"{"name":"Book","author":"A","wordGoal":80000,"settings":{"backups":{"enabled":true}}}"#;
+        let (_dir, project) = project_with(&[("writers.config.json", original)]);
+        save_order(&project, vec!["chapter-2.md".to_string()])
+            .await
+            .unwrap();
+
+        let saved = project.get_raw_config().await.unwrap();
+        assert_eq!(saved["wordGoal"], 80000);
+        assert_eq!(saved["settings"]["backups"]["enabled"], true);
+        assert_eq!(saved["chapter_order"], json!(["chapter-2.md"]));
+        assert!(saved.get("schema_version").is_none());
+        assert_eq!(
+            project.get_config().await.unwrap().chapter_order,
+            Some(vec!["chapter-2.md".to_string()])
+        );
+    }
+}
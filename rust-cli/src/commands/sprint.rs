@@ -0,0 +1,137 @@
+use anyhow::Result;
+use colored::*;
+use serde_json::json;
+
+use crate::outln;
+use crate::utils::sprints::{self, SprintRecord};
+use crate::utils::{output, ProjectManager};
+
+/// Show personal bests and the most recent sprints
+pub async fn history(project: &ProjectManager, limit: usize) -> Result<()> {
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let records = sprints::load(project).await?;
+    let bests = sprints::personal_bests(&records);
+    let recent: Vec<&SprintRecord> = records.iter().rev().take(limit).collect();
+
+    if output::is_json() {
+        let recent: Vec<_> = recent
+            .iter()
+            .map(|record| {
+                let mut value = serde_json::to_value(record)?;
+                value["words_per_minute"] = json!(record.words_per_minute());
+                Ok(value)
+            })
+            .collect::<Result<_>>()?;
+        return output::json(&json!({
+            "personal_bests": bests,
+            "recent": recent,
+        }));
+    }
+
+    outln!("{}", "🏃 Sprint History".cyan().bold());
+    outln!();
+
+    if records.is_empty() {
+        outln!("{}", "No sprints yet.".yellow());
+        outln!(
+            "{}",
+            "💡 Start one with \"writers workflow sprint --time 20\"".bright_black()
+        );
+        return Ok(());
+    }
+
+    outln!(
+        "  {} sprints, {} words",
+        bests.sprints.to_string().cyan(),
+        bests.total_words.to_string().cyan()
+    );
+    outln!();
+
+    outln!("{}", "🏆 Personal Bests:".green().bold());
+    print_best(
+        "Most words:",
+        bests
+            .most_words
+            .as_ref()
+            .map(|r| (r, format!("{} words", r.words))),
+    );
+    print_best(
+        "Fastest:",
+        bests
+            .fastest
+            .as_ref()
+            .map(|r| (r, format!("{:.1} words/minute", r.words_per_minute()))),
+    );
+    print_best(
+        "Longest:",
+        bests.longest.as_ref().map(|r| (r, duration(r.seconds))),
+    );
+    outln!();
+
+    outln!("{}", format!("📅 Last {}:", recent.len()).green().bold());
+    outln!(
+        "  {}",
+        format!(
+            "{:<16}  {:>6}  {:>7}  {:>6}  {}",
+            "When", "Time", "Words", "WPM", "File"
+        )
+        .bold()
+    );
+    for record in recent {
+        let mut words = format!("{:>+7}", record.words).yellow().to_string();
+        if record
+            .target
+            .is_some_and(|target| record.words >= target as i64)
+        {
+            words = format!("{:>+7}", record.words).green().to_string();
+        }
+        outln!(
+            "  {}  {:>6}  {}  {:>6.1}  {}",
+            record.time.format("%Y-%m-%d %H:%M"),
+            duration(record.seconds),
+            words,
+            record.words_per_minute(),
+            record.file.bright_black()
+        );
+    }
+    outln!();
+
+    Ok(())
+}
+
+fn print_best(label: &str, best: Option<(&SprintRecord, String)>) {
+    match best {
+        Some((record, value)) => outln!(
+            "  {:<12} {}  {}",
+            label,
+            value.yellow(),
+            format!("{} in {}", record.time.format("%Y-%m-%d"), record.file).bright_black()
+        ),
+        None => outln!("  {:<12} {}", label, "-".bright_black()),
+    }
+}
+
+/// Seconds as "m:ss"
+fn duration(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_as_minutes_and_seconds() {
+        assert_eq!(duration(0), "0:00");
+        assert_eq!(duration(905), "15:05");
+        assert_eq!(duration(3600), "60:00");
+    }
+}
@@ -1,84 +1,575 @@
 use anyhow::Result;
 use colored::*;
+use serde_json::json;
+use std::collections::BTreeMap;
 
-use crate::utils::ProjectManager;
+use crate::outln;
+use crate::utils::goals::{manuscript_files, manuscript_pace, Pace};
+use crate::utils::output::truncate;
+use crate::utils::pomodoros::{self, FileFocus};
+use crate::utils::readability::{self, ProseMetrics};
+use crate::utils::{output, ContentFile, ProjectConfig, ProjectManager};
 
-pub async fn run() -> Result<()> {
+/// Width of the pomodoro column in `stats --by-file`
+const POMODORO_WIDTH: usize = 4;
+
+pub async fn run(project: &ProjectManager, by_file: bool) -> Result<()> {
     // Check if in a writers project
-    if !ProjectManager::is_writers_project() {
-        println!(
+    if !project.is_writers_project() {
+        outln!(
             "{}",
             "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
         );
         return Ok(());
     }
 
-    let config = ProjectManager::get_config().await?;
+    let config = project.get_config().await?;
+
+    if output::is_json() {
+        return print_json(project, &config, by_file).await;
+    }
 
-    println!("{}", "📊 Project Statistics".cyan().bold());
-    println!();
-    println!("Project: {}", config.name.cyan());
-    println!("Author: {}", config.author.cyan());
-    println!("Type: {}", config.r#type.cyan());
-    println!();
+    outln!("{}", "📊 Project Statistics".cyan().bold());
+    outln!();
+    outln!("Project: {}", config.name.cyan());
+    outln!("Author: {}", config.author.cyan());
+    outln!("Type: {}", config.r#type.cyan());
+    outln!();
 
     // Get all content files
-    let all_files = ProjectManager::get_all_content_files().await?;
+    let all_files = project.get_all_content_files().await?;
     let mut total_words = 0;
     let mut total_chars = 0;
     let total_files = all_files.len();
+    // Files and words per frontmatter status
+    let mut by_status: BTreeMap<String, (usize, usize)> = BTreeMap::new();
 
-    for file in &all_files {
-        if let Ok(content) = tokio::fs::read_to_string(&file).await {
-            let word_count = content.split_whitespace().count();
-            let char_count = content.chars().count();
-            total_words += word_count;
-            total_chars += char_count;
+    let contents = project.read_contents(&all_files).await;
+    for file in &contents {
+        let word_count = file.word_count();
+        total_words += word_count;
+        total_chars += file.body.chars().count();
+
+        if let Some(status) = file.meta.status_key() {
+            let entry = by_status.entry(status).or_default();
+            entry.0 += 1;
+            entry.1 += word_count;
         }
     }
 
     // Overall statistics
-    println!("{}", "📈 Overall Statistics:".green().bold());
-    println!("  Files: {}", total_files.to_string().cyan());
-    println!("  Words: {}", total_words.to_string().cyan());
-    println!("  Characters: {}", total_chars.to_string().cyan());
+    outln!("{}", "📈 Overall Statistics:".green().bold());
+    outln!("  Files: {}", total_files.to_string().cyan());
+    outln!("  Words: {}", total_words.to_string().cyan());
+    outln!("  Characters: {}", total_chars.to_string().cyan());
 
     if total_words > 0 {
         let reading_time = (total_words as f64 / 200.0).ceil() as u32; // 200 words per minute
-        println!(
+        outln!(
             "  Reading Time: {} minutes",
             reading_time.to_string().cyan()
         );
     }
-    println!();
+
+    outln!();
+
+    if let Some(pace) = manuscript_pace(project, &config).await? {
+        print_pace(&pace);
+    }
+
+    // Files with a frontmatter target_words
+    let targeted: Vec<_> = contents
+        .iter()
+        .filter_map(|file| Some((file, file.meta.target_words.filter(|t| *t > 0)?)))
+        .collect();
+    if !targeted.is_empty() {
+        outln!("{}", "🎯 File Targets:".green().bold());
+        for (file, target) in targeted {
+            let words = file.word_count();
+            let percent = (words as f64 / target as f64 * 100.0).min(100.0);
+            let marker = if words >= target {
+                " ✓".green()
+            } else {
+                "".normal()
+            };
+            outln!(
+                "  {}: {} / {} words ({}){}",
+                file.file_name(),
+                words.to_string().cyan(),
+                target.to_string().cyan(),
+                format!("{:.0}%", percent).cyan(),
+                marker
+            );
+        }
+        outln!();
+    }
+
+    if !by_status.is_empty() {
+        outln!("{}", "📋 By Status:".green().bold());
+        for (status, (files, words)) in &by_status {
+            outln!(
+                "  {}: {} files, {} words",
+                status,
+                files.to_string().cyan(),
+                words.to_string().cyan()
+            );
+        }
+        outln!();
+    }
+
+    let focus = pomodoros::per_file(&pomodoros::load(project).await?);
+    if !focus.is_empty() {
+        print_pomodoros(&focus);
+    }
 
     // Project type specific stats
     match config.r#type.as_str() {
         "novel" => {
-            let chapters = ProjectManager::get_chapters().await?;
-            let scenes = ProjectManager::get_scenes().await?;
-            let characters = ProjectManager::get_characters().await?;
-
-            println!("{}", "📖 Novel Statistics:".green().bold());
-            println!("  Chapters: {}", chapters.len().to_string().cyan());
-            println!("  Scenes: {}", scenes.len().to_string().cyan());
-            println!("  Characters: {}", characters.len().to_string().cyan());
-            println!();
+            let chapters = project.get_chapters().await?;
+            let scenes = project.get_scenes().await?;
+            let characters = project.get_characters().await?;
+
+            outln!("{}", "📖 Novel Statistics:".green().bold());
+            outln!("  Chapters: {}", chapters.len().to_string().cyan());
+            outln!("  Scenes: {}", scenes.len().to_string().cyan());
+            outln!("  Characters: {}", characters.len().to_string().cyan());
+            outln!();
+
+            let parts = project.group_parts(&chapters).await;
+            if parts.iter().any(|part| part.title.is_some()) {
+                outln!("{}", "📚 By Part:".green().bold());
+                for part in &parts {
+                    let words: usize = project
+                        .read_contents(&part.chapters)
+                        .await
+                        .iter()
+                        .map(ContentFile::word_count)
+                        .sum();
+                    outln!(
+                        "  {}: {} chapters, {} words",
+                        part.title.as_deref().unwrap_or("(no part)"),
+                        part.chapters.len().to_string().cyan(),
+                        words.to_string().cyan()
+                    );
+                }
+                outln!();
+            }
         }
         "short-story" => {
-            let stories = ProjectManager::get_short_stories().await?;
-            println!("{}", "📚 Short Story Statistics:".green().bold());
-            println!("  Stories: {}", stories.len().to_string().cyan());
-            println!();
+            let stories = project.get_short_stories().await?;
+            outln!("{}", "📚 Short Story Statistics:".green().bold());
+            outln!("  Stories: {}", stories.len().to_string().cyan());
+            outln!();
         }
         "blog" => {
-            let posts = ProjectManager::get_blog_posts().await?;
-            println!("{}", "📝 Blog Statistics:".green().bold());
-            println!("  Posts: {}", posts.len().to_string().cyan());
-            println!();
+            let posts = project.get_blog_posts().await?;
+            outln!("{}", "📝 Blog Statistics:".green().bold());
+            outln!("  Posts: {}", posts.len().to_string().cyan());
+            outln!();
         }
         _ => {}
     }
 
+    // Readability of the manuscript itself, not notes or character sheets
+    let prose_files = manuscript_files(project, &config).await?;
+    print_readability(&project.read_contents(&prose_files).await);
+
+    if by_file {
+        print_by_file(project, &config).await?;
+    }
+
     Ok(())
 }
+
+/// Everything `stats` reports, as one JSON document for scripts
+async fn print_json(project: &ProjectManager, config: &ProjectConfig, by_file: bool) -> Result<()> {
+    let contents = project
+        .read_contents(&project.get_all_content_files().await?)
+        .await;
+    let total_words: usize = contents.iter().map(ContentFile::word_count).sum();
+    let total_chars: usize = contents.iter().map(|file| file.body.chars().count()).sum();
+
+    let mut by_status: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for file in &contents {
+        if let Some(status) = file.meta.status_key() {
+            let entry = by_status.entry(status).or_default();
+            entry.0 += 1;
+            entry.1 += file.word_count();
+        }
+    }
+    let by_status: serde_json::Map<_, _> = by_status
+        .into_iter()
+        .map(|(status, (files, words))| (status, json!({ "files": files, "words": words })))
+        .collect();
+
+    let goal = manuscript_pace(project, config).await?.map(|pace| {
+        json!({
+            "goal": pace.goal,
+            "written": pace.written,
+            "remaining": pace.remaining(),
+            "percent": pace.percent(),
+            "deadline": pace.deadline,
+            "days_left": pace.days_left,
+            "daily_target": pace.daily_target,
+            "today": pace.today,
+            "ahead_by": pace.ahead_by,
+        })
+    });
+
+    let manuscript_paths = manuscript_files(project, config).await?;
+    let manuscript = project.read_contents(&manuscript_paths).await;
+    let focus = pomodoros::per_file(&pomodoros::load(project).await?);
+    let mut prose = ProseMetrics::default();
+    for file in &manuscript {
+        prose += ProseMetrics::analyze(&file.body);
+    }
+
+    let mut parts = Vec::new();
+    if config.r#type == "novel" {
+        for part in project.group_parts(&manuscript_paths).await {
+            let words: usize = project
+                .read_contents(&part.chapters)
+                .await
+                .iter()
+                .map(ContentFile::word_count)
+                .sum();
+            parts.push(json!({
+                "title": part.title,
+                "chapters": part.chapters.len(),
+                "words": words,
+            }));
+        }
+    }
+
+    let mut report = json!({
+        "project": config.name,
+        "author": config.author,
+        "type": config.r#type,
+        "files": contents.len(),
+        "words": total_words,
+        "characters": total_chars,
+        "reading_minutes": (total_words as f64 / 200.0).ceil() as u64,
+        "by_status": by_status,
+        "goal": goal,
+        "parts": parts,
+        "readability": prose.has_prose().then(|| readability_json(&prose)),
+        "pomodoros": pomodoros_json(&focus),
+    });
+
+    if by_file {
+        let manuscript_words: usize = manuscript.iter().map(ContentFile::word_count).sum();
+        let files: Vec<_> = manuscript
+            .iter()
+            .map(|file| {
+                let words = file.word_count();
+                let prose = ProseMetrics::analyze(&file.body);
+                let path = project.display_path(&file.path);
+                let pomodoros = focus.get(&path).copied().unwrap_or_default();
+                json!({
+                    "path": path,
+                    "words": words,
+                    "characters": file.body.chars().count(),
+                    "reading_minutes": (words as f64 / 200.0).ceil() as u64,
+                    "share": words as f64 / manuscript_words.max(1) as f64 * 100.0,
+                    "pomodoros": pomodoros.pomodoros,
+                    "readability": prose.has_prose().then(|| readability_json(&prose)),
+                })
+            })
+            .collect();
+        report["by_file"] = json!(files);
+    }
+
+    output::json(&report)
+}
+
+fn pomodoros_json(focus: &BTreeMap<String, FileFocus>) -> serde_json::Value {
+    json!({
+        "completed": focus.values().map(|f| f.pomodoros).sum::<usize>(),
+        "minutes": focus.values().map(|f| f.minutes).sum::<u32>(),
+        "by_file": focus,
+    })
+}
+
+/// Completed Pomodoros and focused time, busiest file first
+fn print_pomodoros(focus: &BTreeMap<String, FileFocus>) {
+    let completed: usize = focus.values().map(|f| f.pomodoros).sum();
+    let minutes: u32 = focus.values().map(|f| f.minutes).sum();
+
+    outln!("{}", "🍅 Pomodoros:".green().bold());
+    outln!(
+        "  Completed: {} ({} focused)",
+        completed.to_string().cyan(),
+        format!("{}h {:02}m", minutes / 60, minutes % 60).cyan()
+    );
+    let mut files: Vec<_> = focus.iter().collect();
+    files.sort_by_key(|(_, f)| std::cmp::Reverse(f.pomodoros));
+    for (file, file_focus) in files {
+        outln!(
+            "  {}: {} ({} min)",
+            file,
+            file_focus.pomodoros.to_string().cyan(),
+            file_focus.minutes
+        );
+    }
+    outln!();
+}
+
+fn readability_json(metrics: &ProseMetrics) -> serde_json::Value {
+    json!({
+        "flesch_reading_ease": metrics.flesch_reading_ease(),
+        "flesch_kincaid_grade": metrics.flesch_kincaid_grade(),
+        "gunning_fog": metrics.gunning_fog(),
+        "words_per_sentence": metrics.words_per_sentence(),
+        "sentences_per_paragraph": metrics.sentences_per_paragraph(),
+        "words_per_paragraph": metrics.words_per_paragraph(),
+        "dialogue_percent": metrics.dialogue_percent(),
+    })
+}
+
+/// Table and bar chart of each manuscript file's length, flagging outliers
+async fn print_by_file(project: &ProjectManager, config: &ProjectConfig) -> Result<()> {
+    let files = project
+        .read_contents(&manuscript_files(project, config).await?)
+        .await;
+    if files.is_empty() {
+        outln!("{}", "⚠️  No chapters found.".yellow());
+        return Ok(());
+    }
+
+    let words: Vec<usize> = files.iter().map(ContentFile::word_count).collect();
+    let total: usize = words.iter().sum();
+    let focus = pomodoros::per_file(&pomodoros::load(project).await?);
+    let name_width = files
+        .iter()
+        .map(|file| file.file_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(32);
+
+    // The Pomodoro column only shows once some have been logged
+    let pomodoro_column = |value: &dyn std::fmt::Display| -> String {
+        if focus.is_empty() {
+            String::new()
+        } else {
+            format!("  {:>POMODORO_WIDTH$}", value.to_string())
+        }
+    };
+
+    outln!("{}", "📋 By File:".green().bold());
+    outln!(
+        "  {:>3}  {:<name_width$}  {:>7}  {:>8}  {:>6}  {:>6}{}",
+        "#",
+        "File",
+        "Words",
+        "Chars",
+        "Read",
+        "Share",
+        pomodoro_column(&"Pomo")
+    );
+    for (index, (file, &count)) in files.iter().zip(&words).enumerate() {
+        let share = count as f64 / total.max(1) as f64 * 100.0;
+        let reading_time = (count as f64 / 200.0).ceil() as u32;
+        outln!(
+            "  {:>3}  {:<name_width$}  {:>7}  {:>8}  {:>6}  {:>6}{}",
+            index + 1,
+            truncate(file.file_name(), name_width),
+            count,
+            file.body.chars().count(),
+            format!("{}m", reading_time),
+            format!("{:.1}%", share),
+            pomodoro_column(
+                &focus
+                    .get(&project.display_path(&file.path))
+                    .map_or(0, |f| f.pomodoros)
+            )
+        );
+    }
+    outln!();
+
+    let median = median(&words);
+    let longest = words.iter().copied().max().unwrap_or(0).max(1);
+
+    outln!(
+        "{}",
+        format!("📈 Lengths (median {:.0} words):", median)
+            .green()
+            .bold()
+    );
+    for (index, (file, &count)) in files.iter().zip(&words).enumerate() {
+        let bar = "█".repeat(count * 40 / longest);
+        let (bar, flag) = if is_long(count, median) {
+            (bar.red(), "  ⚠️  long".red())
+        } else if is_short(count, median) {
+            (bar.yellow(), "  ⚠️  short".yellow())
+        } else {
+            (bar.green(), "".normal())
+        };
+        outln!(
+            "  {:>3}  {:<name_width$}  {} {}{}",
+            index + 1,
+            truncate(file.file_name(), name_width),
+            bar,
+            count.to_string().bright_black(),
+            flag
+        );
+    }
+    outln!();
+
+    Ok(())
+}
+
+/// The middle length, or the mean of the middle two; 0 for no files
+fn median(words: &[usize]) -> f64 {
+    let mut sorted = words.to_vec();
+    sorted.sort_unstable();
+    match sorted.len() {
+        0 => 0.0,
+        len if len % 2 == 1 => sorted[len / 2] as f64,
+        len => (sorted[len / 2 - 1] + sorted[len / 2]) as f64 / 2.0,
+    }
+}
+
+/// More than one and a half times the median length stands out as long
+fn is_long(words: usize, median: f64) -> bool {
+    median > 0.0 && words as f64 > median * 1.5
+}
+
+/// Less than half the median length stands out as short
+fn is_short(words: usize, median: f64) -> bool {
+    median > 0.0 && (words as f64) < median * 0.5
+}
+
+fn print_readability(files: &[ContentFile]) {
+    let per_file: Vec<_> = files
+        .iter()
+        .map(|file| (file, ProseMetrics::analyze(&file.body)))
+        .filter(|(_, metrics)| metrics.has_prose())
+        .collect();
+
+    let mut total = ProseMetrics::default();
+    for (_, metrics) in &per_file {
+        total += *metrics;
+    }
+    if !total.has_prose() {
+        return;
+    }
+
+    let ease = total.flesch_reading_ease();
+    outln!("{}", "📖 Readability:".green().bold());
+    outln!(
+        "  Flesch Reading Ease: {} ({})",
+        format!("{:.1}", ease).cyan(),
+        readability::ease_label(ease)
+    );
+    outln!(
+        "  Flesch-Kincaid Grade: {}",
+        format!("{:.1}", total.flesch_kincaid_grade()).cyan()
+    );
+    outln!(
+        "  Gunning Fog: {}",
+        format!("{:.1}", total.gunning_fog()).cyan()
+    );
+    outln!(
+        "  Sentence Length: {} words",
+        format!("{:.1}", total.words_per_sentence()).cyan()
+    );
+    outln!(
+        "  Paragraph Length: {} sentences, {} words",
+        format!("{:.1}", total.sentences_per_paragraph()).cyan(),
+        format!("{:.1}", total.words_per_paragraph()).cyan()
+    );
+    outln!(
+        "  Dialogue: {} / Narration: {}",
+        format!("{:.0}%", total.dialogue_percent()).cyan(),
+        format!("{:.0}%", 100.0 - total.dialogue_percent()).cyan()
+    );
+    outln!();
+
+    if per_file.len() > 1 {
+        outln!("{}", "📄 Readability by File:".green().bold());
+        for (file, metrics) in &per_file {
+            outln!(
+                "  {}: ease {}, grade {}, fog {}, {} words/sentence, {} dialogue",
+                file.file_name(),
+                format!("{:.0}", metrics.flesch_reading_ease()).cyan(),
+                format!("{:.1}", metrics.flesch_kincaid_grade()).cyan(),
+                format!("{:.1}", metrics.gunning_fog()).cyan(),
+                format!("{:.1}", metrics.words_per_sentence()).cyan(),
+                format!("{:.0}%", metrics.dialogue_percent()).cyan()
+            );
+        }
+        outln!();
+    }
+}
+
+fn print_pace(pace: &Pace) {
+    outln!("{}", "🎯 Word Goal:".green().bold());
+    outln!(
+        "  Progress: {} / {} words ({})",
+        pace.written.to_string().cyan(),
+        pace.goal.to_string().cyan(),
+        format!("{:.1}%", pace.percent()).cyan()
+    );
+    outln!("  Remaining: {} words", pace.remaining().to_string().cyan());
+
+    if let (Some(deadline), Some(days_left)) = (pace.deadline, pace.days_left) {
+        if pace.remaining() == 0 {
+            outln!("  Deadline: {} ({})", deadline, "goal reached".green());
+        } else if days_left > 0 {
+            outln!(
+                "  Deadline: {} ({} days left)",
+                deadline.to_string().cyan(),
+                days_left.to_string().cyan()
+            );
+        } else {
+            outln!(
+                "  Deadline: {} ({})",
+                deadline.to_string().cyan(),
+                "passed".red()
+            );
+        }
+    }
+
+    if let Some(target) = pace.daily_target.filter(|_| pace.remaining() > 0) {
+        outln!("  Daily target: {} words/day", target.to_string().cyan());
+        if let Some(today) = pace.today {
+            outln!(
+                "  Today: {} / {} words",
+                today.max(0).to_string().cyan(),
+                target.to_string().cyan()
+            );
+        }
+    }
+
+    match pace.ahead_by.filter(|_| pace.remaining() > 0) {
+        Some(ahead) if ahead >= 0 => outln!(
+            "  Status: {}",
+            format!("{} words ahead of schedule", ahead).green()
+        ),
+        Some(behind) => outln!(
+            "  Status: {}",
+            format!("{} words behind schedule", -behind).red()
+        ),
+        None => {}
+    }
+    outln!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlying_lengths() {
+        let words = [3000, 3000, 9000];
+        let median = median(&words);
+        assert_eq!(median, 3000.0);
+        assert!(is_long(9000, median));
+        assert!(!is_long(3000, median) && !is_short(3000, median));
+        assert!(is_short(1400, median));
+
+        assert_eq!(super::median(&[1000, 2000, 4000, 5000]), 3000.0);
+        assert_eq!(super::median(&[]), 0.0);
+        assert!(!is_long(10, 0.0) && !is_short(0, 0.0));
+    }
+}
@@ -1,103 +1,742 @@
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use colored::*;
+use serde_json::json;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use tokio::fs as async_fs;
+
+use crate::commands::submissions;
+use crate::outln;
+use crate::utils::output::truncate;
+use crate::utils::project::{slugify, title_from_name, STORY_DIRS};
+use crate::utils::{frontmatter, order, output, ContentFile, ProjectManager};
+
+/// Filters and values for the story actions, straight from the command line
+#[derive(Debug, Clone, Default)]
+pub struct StoryOptions {
+    pub status: Option<String>,
+    pub genre: Option<String>,
+    pub tag: Option<String>,
+    pub sort: Option<String>,
+    pub detailed: bool,
+    pub to: Option<String>,
+    pub r#as: Option<String>,
+    pub add: Option<String>,
+    pub remove: Option<String>,
+}
 
 pub async fn run(
+    project: &ProjectManager,
     action: String,
     story_name: Option<String>,
-    status: Option<String>,
-    genre: Option<String>,
-    tag: Option<String>,
-    sort: Option<String>,
-    detailed: bool,
-    to: Option<String>,
-    r#as: Option<String>,
-    add: Option<String>,
-    remove: Option<String>,
+    options: StoryOptions,
 ) -> Result<()> {
-    println!("{}", "📚 Advanced Short Story Management".cyan().bold());
-    println!("Action: {}", action.cyan());
-
-    if let Some(story) = story_name {
-        println!("Story: {}", story.cyan());
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
     }
 
-    if let Some(status) = status {
-        println!("Status Filter: {}", status.cyan());
+    if action == "list" {
+        return list(project, &options).await;
     }
 
-    if let Some(genre) = genre {
-        println!("Genre Filter: {}", genre.cyan());
+    let valid = [
+        "list", "status", "move", "copy", "archive", "tags", "notes", "submit",
+    ];
+    if !valid.contains(&action.as_str()) {
+        outln!("{} {}", "❌ Unknown action:".red(), action);
+        outln!(
+            "{}",
+            format!("💡 Valid actions: {}", valid.join(", ")).yellow()
+        );
+        return Ok(());
     }
 
-    if let Some(tag) = tag {
-        println!("Tag Filter: {}", tag.cyan());
+    let Some(name) = story_name else {
+        outln!("{}", "❌ Please specify a story name".red());
+        outln!(
+            "{}",
+            format!("💡 Example: writers story {} my-story", action).yellow()
+        );
+        return Ok(());
+    };
+    let Some(path) = project.find_story(&name).await? else {
+        outln!("{} {}", "❌ Story not found:".red(), name);
+        outln!(
+            "{}",
+            "💡 Use \"writers story list\" to see your stories".yellow()
+        );
+        return Ok(());
+    };
+
+    match action.as_str() {
+        "status" => status(project, &path, &options).await,
+        "move" => move_story(project, &path, &options).await,
+        "copy" => copy(project, &path, &options).await,
+        "archive" => archive(project, &path).await,
+        "tags" => tags(project, &path, &options).await,
+        "submit" => submissions::submit(project, &path, options.to, None).await,
+        _ => notes(project, &path, &options).await,
     }
+}
+
+/// List stories, filtered by status, genre and tag. Archived stories are
+/// only listed when filtering by the "archived" status.
+async fn list(project: &ProjectManager, options: &StoryOptions) -> Result<()> {
+    let status_filter = options.status.as_ref().map(|s| s.trim().to_lowercase());
+    let archived = status_filter.as_deref() == Some("archived");
+    let paths = if archived {
+        project.get_archived_stories().await?
+    } else {
+        project.get_short_stories().await?
+    };
 
-    if let Some(sort) = sort {
-        println!("Sort By: {}", sort.cyan());
+    let mut stories: Vec<ContentFile> = project
+        .read_contents(&paths)
+        .await
+        .into_iter()
+        .filter(|story| {
+            archived || status_filter.is_none() || story.meta.status_key() == status_filter
+        })
+        .filter(|story| match &options.genre {
+            Some(genre) => story
+                .meta
+                .genre
+                .as_ref()
+                .is_some_and(|g| g.eq_ignore_ascii_case(genre.trim())),
+            None => true,
+        })
+        .filter(|story| match &options.tag {
+            Some(tag) => {
+                let tag = tag.trim().trim_start_matches('#');
+                story.meta.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+            }
+            None => true,
+        })
+        .collect();
+
+    let sort = options.sort.as_deref().unwrap_or("name");
+    let compare: fn(&ContentFile, &ContentFile) -> Ordering = match sort {
+        "name" => |a, b| order::natural_cmp(&title(a).to_lowercase(), &title(b).to_lowercase()),
+        "length" => |a, b| b.word_count().cmp(&a.word_count()),
+        "status" => |a, b| a.meta.status_key().cmp(&b.meta.status_key()),
+        "modified" => |a, b| modified(&b.path).cmp(&modified(&a.path)),
+        _ => {
+            outln!("{} {}", "❌ Unknown sort field:".red(), sort);
+            outln!("{}", "💡 Sort by name, length, status or modified".yellow());
+            return Ok(());
+        }
+    };
+    stories.sort_by(compare);
+
+    if output::is_json() {
+        let stories: Vec<_> = stories
+            .iter()
+            .map(|story| {
+                let mut summary = serde_json::to_value(project.summarize(story))?;
+                summary["modified"] = json!(modified(&story.path));
+                Ok(summary)
+            })
+            .collect::<Result<_>>()?;
+        return output::json(&stories);
     }
 
-    if detailed {
-        println!("Mode: {}", "Detailed".cyan());
+    outln!(
+        "{}",
+        format!("📚 Short Stories ({})", stories.len())
+            .cyan()
+            .bold()
+    );
+    outln!();
+
+    if stories.is_empty() {
+        outln!("{}", "No stories match.".yellow());
+        outln!(
+            "{}",
+            "💡 Create one with \"writers new story <name>\"".bright_black()
+        );
+        return Ok(());
     }
 
-    if let Some(to) = to {
-        println!("Destination: {}", to.cyan());
+    if options.detailed {
+        print_table(&stories);
+    } else {
+        for story in &stories {
+            let status = story.meta.status_key().unwrap_or_default();
+            let mut line = format!(
+                "  {} {} {}",
+                status_color("●", &status),
+                title(story).bold(),
+                format!("({} words)", story.word_count()).yellow()
+            );
+            if !status.is_empty() {
+                line.push_str(&format!(" [{}]", status_color(&status, &status)));
+            }
+            if let Some(genre) = &story.meta.genre {
+                line.push_str(&format!(" {}", genre.magenta()));
+            }
+            if !story.meta.tags.is_empty() {
+                line.push_str(&format!(
+                    " {}",
+                    format!("#{}", story.meta.tags.join(" #")).blue()
+                ));
+            }
+            outln!("{}", line);
+            outln!("    {}", project.display_path(&story.path).bright_black());
+        }
     }
+    outln!();
 
-    if let Some(r#as) = r#as {
-        println!("New Name: {}", r#as.cyan());
+    Ok(())
+}
+
+fn print_table(stories: &[ContentFile]) {
+    outln!(
+        "  {}",
+        format!(
+            "{:<32} {:<10} {:<14} {:>7}  {}",
+            "Title", "Status", "Genre", "Words", "Modified"
+        )
+        .bold()
+    );
+    for story in stories {
+        let status = story.meta.status_key().unwrap_or_else(|| "-".to_string());
+        let modified = modified(&story.path)
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        outln!(
+            "  {} {} {} {:>7}  {}",
+            format!("{:<32}", truncate(&title(story), 32)).cyan(),
+            status_color(&format!("{:<10}", truncate(&status, 10)), &status),
+            format!(
+                "{:<14}",
+                truncate(story.meta.genre.as_deref().unwrap_or("-"), 14)
+            )
+            .magenta(),
+            story.word_count(),
+            modified.bright_black()
+        );
+        if let Some(synopsis) = &story.meta.synopsis {
+            outln!("    {}", synopsis.bright_black());
+        }
     }
+}
 
-    if let Some(add) = add {
-        println!("Add: {}", add.cyan());
+/// Show a story's details, first setting its status or genre when given
+async fn status(project: &ProjectManager, path: &Path, options: &StoryOptions) -> Result<()> {
+    if options.status.is_some() || options.genre.is_some() {
+        update(path, |meta| {
+            if let Some(status) = &options.status {
+                meta.status = Some(status.trim().to_lowercase());
+            }
+            if let Some(genre) = &options.genre {
+                meta.genre = Some(genre.trim().to_string());
+            }
+        })
+        .await?;
+        outln!("{}", "✅ Story updated".green());
+        outln!();
     }
 
-    if let Some(remove) = remove {
-        println!("Remove: {}", remove.cyan());
+    let story = project.read_content(path).await?;
+    let words = story.word_count();
+
+    if output::is_json() {
+        let mut summary = serde_json::to_value(project.summarize(&story))?;
+        summary["modified"] = json!(modified(path));
+        return output::json(&summary);
     }
 
-    match action.as_str() {
-        "list" => {
-            println!("{}", "📋 Listing stories with filters...".yellow());
-        }
-        "status" => {
-            println!("{}", "📊 Showing story status...".yellow());
-        }
-        "move" => {
-            println!("{}", "📦 Moving story...".yellow());
-        }
-        "copy" => {
-            println!("{}", "📋 Copying story...".yellow());
-        }
-        "archive" => {
-            println!("{}", "📚 Archiving story...".yellow());
-        }
-        "submit" => {
-            println!("{}", "📧 Managing submissions...".yellow());
-        }
-        "stats" => {
-            println!("{}", "📈 Showing story statistics...".yellow());
-        }
-        "search" => {
-            println!("{}", "🔍 Searching stories...".yellow());
-        }
-        "tags" => {
-            println!("{}", "🏷️  Managing tags...".yellow());
+    let status = story.meta.status_key().unwrap_or_default();
+    outln!(
+        "{}",
+        format!("📖 Story Status: {}", title(&story)).cyan().bold()
+    );
+    outln!();
+    outln!("  File: {}", project.display_path(path).cyan());
+    outln!(
+        "  Status: {}",
+        if status.is_empty() {
+            "not set".bright_black()
+        } else {
+            status_color(&status, &status)
         }
-        "notes" => {
-            println!("{}", "📝 Managing story notes...".yellow());
-        }
-        _ => {
-            println!("{} {}", "❌ Unknown action:".red(), action);
-            println!(
-                "{}",
-                "💡 Valid actions: list, status, move, copy, archive, submit, stats, search, tags, notes".yellow()
+    );
+    outln!(
+        "  Genre: {}",
+        story.meta.genre.as_deref().unwrap_or("not set").magenta()
+    );
+    match story.meta.target_words {
+        Some(target) if target > 0 => outln!(
+            "  Words: {} / {} ({}%)",
+            words.to_string().yellow(),
+            target,
+            (words * 100 / target).min(100)
+        ),
+        _ => outln!("  Words: {}", words.to_string().yellow()),
+    }
+    outln!(
+        "  Reading Time: {} minutes",
+        (words as f64 / 200.0).ceil() as u64
+    );
+    if let Some(time) = modified(path) {
+        outln!("  Modified: {}", time.format("%Y-%m-%d %H:%M"));
+    }
+    if !story.meta.tags.is_empty() {
+        outln!(
+            "  Tags: {}",
+            format!("#{}", story.meta.tags.join(" #")).blue()
+        );
+    }
+    if let Some(synopsis) = &story.meta.synopsis {
+        outln!();
+        outln!("  {}", synopsis.bright_black());
+    }
+    if !story.meta.notes.is_empty() {
+        outln!();
+        outln!(
+            "  {} {}, see \"writers story notes {}\"",
+            story.meta.notes.len(),
+            if story.meta.notes.len() == 1 {
+                "note"
+            } else {
+                "notes"
+            },
+            file_stem(path)
+        );
+    }
+    outln!();
+
+    Ok(())
+}
+
+/// Move a story to another story folder, or to the archive
+async fn move_story(project: &ProjectManager, path: &Path, options: &StoryOptions) -> Result<()> {
+    let Some(to) = options.to.as_deref().map(str::trim) else {
+        outln!("{}", "❌ Please give a destination with --to".red());
+        outln!(
+            "{}",
+            "💡 Example: writers story move my-story --to drafts".yellow()
+        );
+        return Ok(());
+    };
+    if to == "archive" {
+        return archive(project, path).await;
+    }
+    if !STORY_DIRS.contains(&to) {
+        outln!("{} {}", "❌ Not a story folder:".red(), to);
+        outln!(
+            "{}",
+            format!("💡 Move to one of: {}, archive", STORY_DIRS.join(", ")).yellow()
+        );
+        return Ok(());
+    }
+
+    let destination = project.content_dir(to).await;
+    if let Some(moved) = relocate(project, path, &destination).await? {
+        outln!(
+            "{} {}",
+            "✅ Moved to".green(),
+            project.display_path(&moved).cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Copy a story under a new title, next to the original
+async fn copy(project: &ProjectManager, path: &Path, options: &StoryOptions) -> Result<()> {
+    let Some(new_title) = options.r#as.as_deref().map(str::trim) else {
+        outln!("{}", "❌ Please give a name for the copy with --as".red());
+        outln!(
+            "{}",
+            "💡 Example: writers story copy my-story --as \"My Story (Revised)\"".yellow()
+        );
+        return Ok(());
+    };
+
+    let slug = slugify(new_title);
+    if slug.is_empty() {
+        outln!("{}", "❌ Please use a name with letters or numbers.".red());
+        return Ok(());
+    }
+
+    let target = path.with_file_name(format!("{}.md", slug));
+    if target.exists() {
+        outln!(
+            "{} {}",
+            "❌ File already exists:".red(),
+            project.display_path(&target)
+        );
+        return Ok(());
+    }
+
+    let content = async_fs::read_to_string(path).await?;
+    let body = frontmatter::body(&content);
+    let content = format!(
+        "{}{}",
+        &content[..content.len() - body.len()],
+        retitle(body, new_title)
+    );
+    let content = frontmatter::update(&content, |meta| {
+        meta.title = Some(new_title.to_string());
+        meta.notes.clear();
+    })?;
+    async_fs::write(&target, content).await?;
+
+    outln!(
+        "{} {}",
+        "✅ Copied to".green(),
+        project.display_path(&target).cyan()
+    );
+    Ok(())
+}
+
+/// Retitle a body that opens with a `# ` heading; any other body is kept
+fn retitle(body: &str, title: &str) -> String {
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        if !line.trim().is_empty() {
+            if !line.starts_with("# ") {
+                break;
+            }
+            let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+            return format!(
+                "{}# {}{}{}",
+                &body[..offset],
+                title,
+                ending,
+                &body[offset + line.len()..]
             );
         }
+        offset += line.len();
+    }
+    body.to_string()
+}
+
+/// Move a story to the archive and mark it archived
+async fn archive(project: &ProjectManager, path: &Path) -> Result<()> {
+    let archive_dir = project.content_dir("archive").await;
+    if path.starts_with(&archive_dir) {
+        outln!("{}", "⚠️  That story is already archived.".yellow());
+        return Ok(());
     }
 
-    println!("{}", "📝 This feature is coming soon!".bright_black());
+    if let Some(archived) = relocate(project, path, &archive_dir).await? {
+        update(&archived, |meta| meta.status = Some("archived".to_string())).await?;
+        outln!(
+            "{} {}",
+            "📦 Archived to".green(),
+            project.display_path(&archived).cyan()
+        );
+        outln!(
+            "{}",
+            "💡 Bring it back with \"writers story move <story> --to shortstories\"".bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// Add or remove comma-separated tags, then show the story's tags
+async fn tags(project: &ProjectManager, path: &Path, options: &StoryOptions) -> Result<()> {
+    let split = |list: &Option<String>| -> Vec<String> {
+        list.as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches('#').to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    };
+    let (added, removed) = (split(&options.add), split(&options.remove));
+
+    if !added.is_empty() || !removed.is_empty() {
+        update(path, |meta| {
+            for tag in added {
+                if !meta.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                    meta.tags.push(tag);
+                }
+            }
+            meta.tags
+                .retain(|t| !removed.iter().any(|r| r.eq_ignore_ascii_case(t)));
+        })
+        .await?;
+    }
+
+    let story = project.read_content(path).await?;
+    if output::is_json() {
+        return output::json(&json!({ "tags": story.meta.tags }));
+    }
+
+    if story.meta.tags.is_empty() {
+        outln!("{}", format!("No tags for \"{}\"", title(&story)).yellow());
+        outln!("{}", "💡 Add some with --add \"tag1,tag2\"".bright_black());
+    } else {
+        outln!(
+            "{}",
+            format!("🏷️  Tags for \"{}\":", title(&story)).cyan().bold()
+        );
+        outln!("  {}", format!("#{}", story.meta.tags.join(" #")).blue());
+    }
+    Ok(())
+}
+
+/// Add a dated note with --add, then show the story's notes
+async fn notes(project: &ProjectManager, path: &Path, options: &StoryOptions) -> Result<()> {
+    if let Some(note) = options
+        .add
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+    {
+        let entry = format!("{}: {}", Local::now().format("%Y-%m-%d"), note);
+        update(path, |meta| meta.notes.push(entry)).await?;
+    }
+
+    let story = project.read_content(path).await?;
+    if output::is_json() {
+        return output::json(&json!({ "notes": story.meta.notes }));
+    }
+
+    if story.meta.notes.is_empty() {
+        outln!("{}", format!("No notes for \"{}\"", title(&story)).yellow());
+        outln!(
+            "{}",
+            format!(
+                "💡 Add one with: writers story notes {} --add \"Your note\"",
+                file_stem(path)
+            )
+            .bright_black()
+        );
+    } else {
+        outln!(
+            "{}",
+            format!("📝 Notes for \"{}\":", title(&story)).cyan().bold()
+        );
+        for note in &story.meta.notes {
+            outln!("  • {}", note);
+        }
+    }
+    Ok(())
+}
 
+/// Rewrite a story's frontmatter in place
+async fn update(path: &Path, change: impl FnOnce(&mut frontmatter::ContentMeta)) -> Result<()> {
+    let content = async_fs::read_to_string(path).await?;
+    let updated = frontmatter::update(&content, change)
+        .map_err(|e| anyhow::anyhow!("Can't update {}: {}", path.display(), e))?;
+    async_fs::write(path, updated).await?;
     Ok(())
 }
+
+/// Move a file into `dir`, refusing to overwrite. Returns the new path.
+async fn relocate(project: &ProjectManager, path: &Path, dir: &Path) -> Result<Option<PathBuf>> {
+    let target = dir.join(path.file_name().unwrap_or_default());
+    if target == path {
+        outln!("{}", "⚠️  The story is already there.".yellow());
+        return Ok(None);
+    }
+    if target.exists() {
+        outln!(
+            "{} {}",
+            "❌ File already exists:".red(),
+            project.display_path(&target)
+        );
+        return Ok(None);
+    }
+
+    async_fs::create_dir_all(dir).await?;
+    async_fs::rename(path, &target).await?;
+    Ok(Some(target))
+}
+
+/// The story's frontmatter title, or one made from its file name
+fn title(story: &ContentFile) -> String {
+    story
+        .meta
+        .title
+        .clone()
+        .unwrap_or_else(|| title_from_name(&file_stem(&story.path)))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn modified(path: &Path) -> Option<DateTime<Local>> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(DateTime::from)
+}
+
+fn status_color(text: &str, status: &str) -> ColoredString {
+    match status {
+        "planning" | "idea" => text.yellow(),
+        "drafting" | "draft" => text.blue(),
+        "revising" | "revised" => text.magenta(),
+        "complete" | "final" => text.green(),
+        "submitted" => text.cyan(),
+        "published" => text.bright_green().bold(),
+        _ => text.bright_black(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{self, config_json};
+
+    /// A short-story project holding `files`
+    fn project_with(files: &[(&str, &str)]) -> (tempfile::TempDir, ProjectManager) {
+        let config = config_json("short-story");
+        let mut files = files.to_vec();
+        files.push(("writers.config.json", &config));
+        test_support::project_with(&files)
+    }
+
+    #[tokio::test]
+    async fn copy_retitles_and_drops_notes() {
+        let (dir, project) = project_with(&[(
+            "shortstories/rain.md",
+            "---\ntitle: Rain\nnotes:\n- old note\n---\n# Rain\n\nIt fell.\n",
+        )]);
+        let options = StoryOptions {
+            r#as: Some("Rain (Revised)".to_string()),
+            ..Default::default()
+        };
+
+        run(
+            &project,
+            "copy".to_string(),
+            Some("rain".to_string()),
+            options,
+        )
+        .await
+        .unwrap();
+
+        let copy = project
+            .read_content(&dir.path().join("shortstories/rain-revised.md"))
+            .await
+            .unwrap();
+        assert_eq!(copy.meta.title.as_deref(), Some("Rain (Revised)"));
+        assert!(copy.meta.notes.is_empty());
+        assert!(copy.body.contains("# Rain (Revised)\n\nIt fell.\n"));
+    }
+
+    #[tokio::test]
+    async fn copy_needs_a_name_with_letters_or_numbers() {
+        let (dir, project) = project_with(&[("shortstories/rain.md", "It fell.\n")]);
+        let options = StoryOptions {
+            r#as: Some("!!!".to_string()),
+            ..Default::default()
+        };
+
+        run(
+            &project,
+            "copy".to_string(),
+            Some("rain".to_string()),
+            options,
+        )
+        .await
+        .unwrap();
+
+        assert!(!dir.path().join("shortstories/.md").exists());
+    }
+
+    #[test]
+    fn only_an_opening_heading_is_retitled() {
+        assert_eq!(
+            retitle("\n# Rain\r\nIt fell.\n", "Snow"),
+            "\n# Snow\r\nIt fell.\n"
+        );
+        assert_eq!(
+            retitle("It fell.\n\n# Part Two\n", "Snow"),
+            "It fell.\n\n# Part Two\n"
+        );
+        assert_eq!(retitle("## Rain\n", "Snow"), "## Rain\n");
+        assert_eq!(retitle("", "Snow"), "");
+    }
+
+    #[tokio::test]
+    async fn tags_are_added_once_and_removed() {
+        let (dir, project) = project_with(&[(
+            "shortstories/rain.md",
+            "---\ntags:\n- dark\n---\nIt fell.\n",
+        )]);
+        let options = StoryOptions {
+            add: Some("#Dark, storm,".to_string()),
+            remove: Some("DARK".to_string()),
+            ..Default::default()
+        };
+
+        run(
+            &project,
+            "tags".to_string(),
+            Some("rain".to_string()),
+            options,
+        )
+        .await
+        .unwrap();
+
+        let story = project
+            .read_content(&dir.path().join("shortstories/rain.md"))
+            .await
+            .unwrap();
+        assert_eq!(story.meta.tags, vec!["storm"]);
+    }
+
+    #[tokio::test]
+    async fn archive_moves_and_marks_the_story() {
+        let (dir, project) = project_with(&[
+            ("shortstories/rain.md", "It fell.\n"),
+            ("archive/snow.md", "Old.\n"),
+        ]);
+
+        run(
+            &project,
+            "archive".to_string(),
+            Some("rain".to_string()),
+            StoryOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!dir.path().join("shortstories/rain.md").exists());
+        let archived = project
+            .read_content(&dir.path().join("archive/rain.md"))
+            .await
+            .unwrap();
+        assert_eq!(archived.meta.status.as_deref(), Some("archived"));
+
+        // Moving onto an existing file is refused
+        std::fs::write(dir.path().join("shortstories/snow.md"), "New.\n").unwrap();
+        let refused = relocate(
+            &project,
+            &dir.path().join("shortstories/snow.md"),
+            &dir.path().join("archive"),
+        )
+        .await
+        .unwrap();
+        assert!(refused.is_none());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("archive/snow.md")).unwrap(),
+            "Old.\n"
+        );
+    }
+
+    #[test]
+    fn titles_come_from_the_file_name() {
+        let story = ContentFile {
+            path: PathBuf::from("shortstories/the-long-night.md"),
+            meta: Default::default(),
+            body: String::new(),
+        };
+        assert_eq!(title(&story), "The Long Night");
+    }
+}
@@ -0,0 +1,535 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use serde_json::json;
+use std::path::Path;
+
+use crate::outln;
+use crate::utils::output::{plural, truncate};
+use crate::utils::project::title_from_name;
+use crate::utils::submissions::{self, Market, Status, Submission, MARKETS_FILE, SUBMISSIONS_FILE};
+use crate::utils::{output, ProjectManager};
+
+/// Log a story as sent to a market
+pub async fn log(
+    project: &ProjectManager,
+    story_name: String,
+    market: Option<String>,
+    date: Option<NaiveDate>,
+) -> Result<()> {
+    if !in_project(project) {
+        return Ok(());
+    }
+
+    let Some(path) = project.find_story(&story_name).await? else {
+        outln!("{} {}", "❌ Story not found:".red(), story_name);
+        outln!(
+            "{}",
+            "💡 Use \"writers story list\" to see your stories".yellow()
+        );
+        return Ok(());
+    };
+    submit(project, &path, market, date).await
+}
+
+/// Log the story at `path` as sent to a market, warning when that breaks a
+/// market's rule against simultaneous submissions
+pub async fn submit(
+    project: &ProjectManager,
+    path: &Path,
+    market: Option<String>,
+    date: Option<NaiveDate>,
+) -> Result<()> {
+    let Some(market) = market
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+    else {
+        outln!("{}", "❌ Please name the market with --to".red());
+        outln!(
+            "{}",
+            "💡 Example: writers story submit my-story --to \"Clarkesworld\"".yellow()
+        );
+        return Ok(());
+    };
+
+    let story = stem(path);
+    let mut ledger = submissions::load(project).await?;
+    let markets = submissions::load_markets(project).await?;
+
+    if let Some(pending) = ledger.iter().find(|s| {
+        s.story == story && s.status == Status::Pending && s.market.eq_ignore_ascii_case(&market)
+    }) {
+        outln!(
+            "{}",
+            format!(
+                "❌ \"{}\" is already pending at {} (#{}, sent {})",
+                story, pending.market, pending.id, pending.sent
+            )
+            .red()
+        );
+        outln!(
+            "{}",
+            format!(
+                "💡 Record the response with: writers submissions update {} --status rejected",
+                pending.id
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    // Use the market's name as the markets file spells it
+    let known = submissions::find_market(&markets, &market);
+    let market = known.map(|m| m.name.clone()).unwrap_or(market);
+    let conflicts: Vec<u32> = submissions::conflicts(&ledger, &markets, &story, &market)
+        .iter()
+        .map(|s| s.id)
+        .collect();
+
+    let submission = Submission {
+        id: submissions::next_id(&ledger),
+        story: story.clone(),
+        market: market.clone(),
+        sent: date.unwrap_or_else(|| Local::now().date_naive()),
+        status: Status::Pending,
+        responded: None,
+        payment: None,
+    };
+    ledger.push(submission.clone());
+    submissions::save(project, &ledger).await?;
+
+    if output::is_json() {
+        return output::json(&json!({
+            "submission": submission,
+            "conflicts": conflicts,
+        }));
+    }
+
+    outln!(
+        "{} #{}: {} → {} ({})",
+        "✅ Logged submission".green(),
+        submission.id,
+        story.cyan(),
+        market.cyan(),
+        submission.sent
+    );
+
+    for conflict in ledger.iter().filter(|s| conflicts.contains(&s.id)) {
+        let forbidding = if policy(&markets, &market) == Some(false) {
+            &market
+        } else {
+            &conflict.market
+        };
+        outln!(
+            "{}",
+            format!(
+                "⚠️  {} doesn't accept simultaneous submissions, and \"{}\" is still pending at {} (#{}, sent {})",
+                forbidding, story, conflict.market, conflict.id, conflict.sent
+            )
+            .yellow()
+        );
+    }
+    if known.is_none() {
+        outln!(
+            "{}",
+            format!(
+                "💡 Add \"{}\" to {} to record its simultaneous submission policy",
+                market, MARKETS_FILE
+            )
+            .bright_black()
+        );
+    }
+
+    Ok(())
+}
+
+/// Record a response, a withdrawal or a payment
+pub async fn update(
+    project: &ProjectManager,
+    id: u32,
+    status: Option<Status>,
+    date: Option<NaiveDate>,
+    payment: Option<f64>,
+) -> Result<()> {
+    if !in_project(project) {
+        return Ok(());
+    }
+
+    let mut ledger = submissions::load(project).await?;
+    let Some(submission) = ledger.iter_mut().find(|s| s.id == id) else {
+        outln!("{} #{}", "❌ No submission".red(), id);
+        outln!(
+            "{}",
+            "💡 Use \"writers submissions list --all\" to see the ledger".yellow()
+        );
+        return Ok(());
+    };
+    if status.is_none() && date.is_none() && payment.is_none() {
+        outln!("{}", "❌ Nothing to update".red());
+        outln!(
+            "{}",
+            format!(
+                "💡 Example: writers submissions update {} --status accepted --payment 120",
+                id
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    if let Some(status) = status {
+        submission.status = status;
+    }
+    if submission.status == Status::Pending {
+        submission.responded = None;
+    } else if status.is_some() || date.is_some() {
+        submission.responded = Some(date.unwrap_or_else(|| Local::now().date_naive()));
+    }
+    if let Some(payment) = payment {
+        submission.payment = Some(payment);
+    }
+    if submission
+        .responded
+        .is_some_and(|responded| responded < submission.sent)
+    {
+        outln!(
+            "{}",
+            format!(
+                "❌ The response date is before the story was sent ({})",
+                submission.sent
+            )
+            .red()
+        );
+        return Ok(());
+    }
+
+    let submission = submission.clone();
+    submissions::save(project, &ledger).await?;
+
+    if output::is_json() {
+        return output::json(&submission);
+    }
+
+    let icon = match submission.status {
+        Status::Accepted => "🎉",
+        _ => "✅",
+    };
+    let mut line = format!(
+        "{} #{} {} at {}: {}",
+        icon,
+        submission.id,
+        submission.story.cyan(),
+        submission.market.cyan(),
+        status_color(submission.status)
+    );
+    if let Some(days) = submission.response_days() {
+        line.push_str(&format!(" after {}", plural(days, "day")));
+    }
+    if let Some(payment) = submission.payment {
+        line.push_str(&format!(", paid {}", format!("{:.2}", payment).green()));
+    }
+    outln!("{}", line);
+
+    Ok(())
+}
+
+/// Show outstanding submissions, or the whole ledger with `all`
+pub async fn list(project: &ProjectManager, all: bool) -> Result<()> {
+    if !in_project(project) {
+        return Ok(());
+    }
+
+    let ledger = submissions::load(project).await?;
+    let markets = submissions::load_markets(project).await?;
+    let today = Local::now().date_naive();
+    let mut shown: Vec<&Submission> = ledger
+        .iter()
+        .filter(|s| all || s.status == Status::Pending)
+        .collect();
+    shown.sort_by_key(|s| (s.sent, s.id));
+
+    // A pending submission is overdue once it's been out longer than the
+    // market says it takes to respond
+    let overdue = |submission: &Submission| {
+        submission.status == Status::Pending
+            && submissions::find_market(&markets, &submission.market)
+                .and_then(|m| m.response_days)
+                .is_some_and(|days| (today - submission.sent).num_days() > days as i64)
+    };
+
+    if output::is_json() {
+        let shown: Vec<_> = shown
+            .iter()
+            .map(|submission| {
+                let mut value = serde_json::to_value(submission)?;
+                if submission.status == Status::Pending {
+                    value["days_out"] = json!((today - submission.sent).num_days());
+                    value["overdue"] = json!(overdue(submission));
+                }
+                Ok(value)
+            })
+            .collect::<Result<_>>()?;
+        return output::json(&shown);
+    }
+
+    let heading = if all {
+        format!("📬 Submissions ({})", shown.len())
+    } else {
+        format!("📬 Outstanding Submissions ({})", shown.len())
+    };
+    outln!("{}", heading.cyan().bold());
+    outln!();
+
+    if shown.is_empty() {
+        outln!(
+            "{}",
+            if all {
+                "No submissions logged yet."
+            } else {
+                "Nothing out right now."
+            }
+            .yellow()
+        );
+        outln!(
+            "{}",
+            "💡 Log one with \"writers story submit <story> --to <market>\"".bright_black()
+        );
+        return Ok(());
+    }
+
+    outln!(
+        "  {}",
+        format!(
+            "{:>4}  {:<24} {:<24} {:<10}  {}",
+            "#", "Story", "Market", "Sent", "Status"
+        )
+        .bold()
+    );
+    for submission in &shown {
+        let status = match submission.status {
+            Status::Pending => {
+                let days = (today - submission.sent).num_days();
+                let out = format!("out {}", plural(days, "day"));
+                if overdue(submission) {
+                    format!("{} {}", out.yellow(), "⏰ overdue".red())
+                } else {
+                    out.yellow().to_string()
+                }
+            }
+            _ => {
+                let mut status = status_color(submission.status).to_string();
+                if let Some(responded) = submission.responded {
+                    status.push_str(&format!(" {}", responded.to_string().bright_black()));
+                }
+                if let Some(payment) = submission.payment {
+                    status.push_str(&format!(" {}", format!("{:.2}", payment).green()));
+                }
+                status
+            }
+        };
+        outln!(
+            "  {:>4}  {} {} {}  {}",
+            submission.id,
+            format!("{:<24}", truncate(&submission.story, 24)).cyan(),
+            format!("{:<24}", truncate(&submission.market, 24)).magenta(),
+            submission.sent,
+            status
+        );
+    }
+    outln!();
+
+    Ok(())
+}
+
+/// Response times, acceptances and payments for each market
+pub async fn markets(project: &ProjectManager) -> Result<()> {
+    if !in_project(project) {
+        return Ok(());
+    }
+
+    let ledger = submissions::load(project).await?;
+    let markets = submissions::load_markets(project).await?;
+    let stats = submissions::market_stats(&ledger);
+
+    if output::is_json() {
+        let stats: Vec<_> = stats
+            .iter()
+            .map(|stats| {
+                let mut value = serde_json::to_value(stats)?;
+                value["simultaneous"] = json!(policy(&markets, &stats.market));
+                Ok(value)
+            })
+            .collect::<Result<_>>()?;
+        return output::json(&stats);
+    }
+
+    outln!("{}", "🏛️  Markets".cyan().bold());
+    outln!();
+
+    if stats.is_empty() {
+        outln!("{}", "No submissions logged yet.".yellow());
+        outln!(
+            "{}",
+            "💡 Log one with \"writers story submit <story> --to <market>\"".bright_black()
+        );
+        return Ok(());
+    }
+
+    outln!(
+        "  {}",
+        format!(
+            "{:<24} {:>5} {:>8} {:>9} {:>9} {:>14} {:>9}",
+            "Market", "Sent", "Pending", "Accepted", "Rejected", "Avg Response", "Paid"
+        )
+        .bold()
+    );
+    for stats in &stats {
+        let average = stats
+            .average_response_days
+            .map(|days| format!("{:.0} days", days))
+            .unwrap_or_else(|| "-".to_string());
+        let mut line = format!(
+            "  {} {:>5} {:>8} {} {:>9} {:>14} {}",
+            format!("{:<24}", truncate(&stats.market, 24)).magenta(),
+            stats.submissions,
+            stats.pending,
+            format!("{:>9}", stats.accepted).green(),
+            stats.rejected,
+            average,
+            format!("{:>9.2}", stats.payments).green()
+        );
+        if policy(&markets, &stats.market) == Some(false) {
+            line.push_str(&format!("  {}", "no simultaneous".yellow()));
+        }
+        outln!("{}", line);
+    }
+    outln!();
+
+    let (accepted, decided): (usize, usize) = stats.iter().fold((0, 0), |(a, d), stats| {
+        (a + stats.accepted, d + stats.accepted + stats.rejected)
+    });
+    if let Some(rate) = (accepted * 100).checked_div(decided) {
+        outln!("  Acceptance rate: {}", format!("{}%", rate).cyan());
+        outln!(
+            "  Total paid: {}",
+            format!(
+                "{:.2}",
+                stats.iter().map(|s| s.payments).fold(0.0, |a, b| a + b)
+            )
+            .green()
+        );
+        outln!();
+    }
+    if markets.is_empty() {
+        outln!(
+            "{}",
+            format!(
+                "💡 Record market policies in {}, e.g. [{{\"name\": \"Clarkesworld\", \"simultaneous\": false, \"response_days\": 30}}]",
+                MARKETS_FILE
+            )
+            .bright_black()
+        );
+    }
+
+    Ok(())
+}
+
+/// Stories that have never been sent anywhere
+pub async fn unsubmitted(project: &ProjectManager) -> Result<()> {
+    if !in_project(project) {
+        return Ok(());
+    }
+
+    let ledger = submissions::load(project).await?;
+    let stories: Vec<_> = project
+        .read_contents(&project.get_short_stories().await?)
+        .await
+        .into_iter()
+        .filter(|story| !ledger.iter().any(|s| s.story == stem(&story.path)))
+        .collect();
+
+    if output::is_json() {
+        let stories: Vec<_> = stories
+            .iter()
+            .map(|story| project.summarize(story))
+            .collect();
+        return output::json(&stories);
+    }
+
+    outln!(
+        "{}",
+        format!("📭 Never Submitted ({})", stories.len())
+            .cyan()
+            .bold()
+    );
+    outln!();
+
+    if stories.is_empty() {
+        outln!(
+            "{}",
+            "✓ Every story has been sent out at least once.".green()
+        );
+        return Ok(());
+    }
+
+    for story in &stories {
+        let title = story
+            .meta
+            .title
+            .clone()
+            .unwrap_or_else(|| title_from_name(&stem(&story.path)));
+        let mut line = format!(
+            "  • {} {}",
+            title.bold(),
+            format!("({} words)", story.word_count()).yellow()
+        );
+        if let Some(status) = story.meta.status_key() {
+            line.push_str(&format!(" [{}]", status.bright_black()));
+        }
+        outln!("{}", line);
+        outln!("    {}", project.display_path(&story.path).bright_black());
+    }
+    outln!();
+    outln!(
+        "{}",
+        format!(
+            "💡 The ledger is kept in {}; log a submission with \"writers story submit <story> --to <market>\"",
+            SUBMISSIONS_FILE
+        )
+        .bright_black()
+    );
+
+    Ok(())
+}
+
+fn in_project(project: &ProjectManager) -> bool {
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return false;
+    }
+    true
+}
+
+/// Whether a market takes simultaneous submissions, if its policy is known
+fn policy(markets: &[Market], name: &str) -> Option<bool> {
+    submissions::find_market(markets, name).and_then(|m| m.simultaneous)
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn status_color(status: Status) -> ColoredString {
+    let text = status.to_string();
+    match status {
+        Status::Pending => text.yellow(),
+        Status::Accepted => text.bright_green().bold(),
+        Status::Rejected => text.red(),
+        Status::Withdrawn => text.bright_black(),
+    }
+}
@@ -1,94 +1,440 @@
 use anyhow::Result;
+use chrono::Local;
 use colored::*;
+use serde_json::json;
+use std::time::Instant;
+use tokio::fs as async_fs;
+
+use crate::commands::edit::{self, LaunchOptions};
+use crate::commands::submissions;
+use crate::editor::sprint::Sprint;
+use crate::outln;
+use crate::utils::frontmatter::{self, ContentMeta};
+use crate::utils::output::plural;
+use crate::utils::sprints::{self, SprintRecord};
+use crate::utils::{daily, goals, history, output, ProjectManager};
+
+/// Sprint length when no --time is given
+const DEFAULT_SPRINT_MINUTES: u32 = 15;
 
 pub async fn run(
+    project: &ProjectManager,
     workflow_type: String,
     goal: Option<String>,
     time: Option<u32>,
     words: Option<u32>,
+    file: Option<String>,
 ) -> Result<()> {
-    println!("{}", "🔄 Automated Writing Workflows".cyan().bold());
-    println!("Workflow Type: {}", workflow_type.cyan());
+    // Check if in a writers project
+    if !project.is_writers_project() {
+        outln!(
+            "{}",
+            "❌ Not a Writers project. Run \"writers init\" to initialize.".red()
+        );
+        return Ok(());
+    }
+
+    let valid = [
+        "daily",
+        "submission",
+        "revision",
+        "collection",
+        "prompt",
+        "sprint",
+        "publish",
+        "backup",
+    ];
+    match workflow_type.as_str() {
+        "daily" => return daily_session(project, goal, time, words).await,
+        "submission" => return submission(project).await,
+        "sprint" => return sprint(project, file, time, words).await,
+        name if !valid.contains(&name) => {
+            outln!("{} {}", "❌ Unknown workflow type:".red(), workflow_type);
+            outln!(
+                "{}",
+                format!("💡 Valid workflows: {}", valid.join(", ")).yellow()
+            );
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    outln!("{}", "🔄 Automated Writing Workflows".cyan().bold());
+    outln!("Workflow Type: {}", workflow_type.cyan());
 
     if let Some(goal) = goal {
-        println!("Goal: {}", goal.cyan());
+        outln!("Goal: {}", goal.cyan());
     }
 
     if let Some(time) = time {
-        println!("Time Limit: {} minutes", time.to_string().cyan());
+        outln!("Time Limit: {} minutes", time.to_string().cyan());
     }
 
     if let Some(words) = words {
-        println!("Word Target: {} words", words.to_string().cyan());
+        outln!("Word Target: {} words", words.to_string().cyan());
     }
 
     match workflow_type.as_str() {
-        "daily" => {
-            println!("{}", "📅 Setting up daily writing routine...".yellow());
-            println!("  • Opens today's writing file");
-            println!("  • Sets daily word count goal");
-            println!("  • Tracks writing streak");
-        }
-        "submission" => {
-            println!("{}", "📧 Managing story submissions...".yellow());
-            println!("  • Tracks submission deadlines");
-            println!("  • Formats stories for submission");
-            println!("  • Manages rejection/acceptance tracking");
-        }
         "revision" => {
-            println!("{}", "✏️  Starting revision workflow...".yellow());
-            println!("  • Creates revision checklist");
-            println!("  • Tracks changes and versions");
-            println!("  • Provides editing guidelines");
+            outln!("{}", "✏️  Starting revision workflow...".yellow());
+            outln!("  • Creates revision checklist");
+            outln!("  • Tracks changes and versions");
+            outln!("  • Provides editing guidelines");
         }
         "collection" => {
-            println!("{}", "📚 Organizing story collection...".yellow());
-            println!("  • Groups related stories");
-            println!("  • Checks for thematic consistency");
-            println!("  • Prepares collection manuscript");
+            outln!("{}", "📚 Organizing story collection...".yellow());
+            outln!("  • Groups related stories");
+            outln!("  • Checks for thematic consistency");
+            outln!("  • Prepares collection manuscript");
         }
         "prompt" => {
-            println!("{}", "💡 Writing prompt session...".yellow());
-            println!("  • Generates random writing prompts");
-            println!("  • Sets up timed writing sessions");
-            println!("  • Saves prompt responses");
-        }
-        "sprint" => {
-            println!("{}", "🏃 Writing sprint mode...".yellow());
-            println!("  • Sets timer for focused writing");
-            println!("  • Disables distractions");
-            println!("  • Tracks words per minute");
+            outln!("{}", "💡 Writing prompt session...".yellow());
+            outln!("  • Generates random writing prompts");
+            outln!("  • Sets up timed writing sessions");
+            outln!("  • Saves prompt responses");
         }
         "publish" => {
-            println!("{}", "🚀 Publication workflow...".yellow());
-            println!("  • Formats for different platforms");
-            println!("  • Generates metadata");
-            println!("  • Creates publication checklist");
-        }
-        "backup" => {
-            println!("{}", "💾 Backup and archive workflow...".yellow());
-            println!("  • Creates timestamped backups");
-            println!("  • Archives completed projects");
-            println!("  • Syncs to cloud storage");
+            outln!("{}", "🚀 Publication workflow...".yellow());
+            outln!("  • Formats for different platforms");
+            outln!("  • Generates metadata");
+            outln!("  • Creates publication checklist");
         }
         _ => {
-            println!("{} {}", "❌ Unknown workflow type:".red(), workflow_type);
-            println!(
-                "{}",
-                "💡 Valid workflows: daily, submission, revision, collection, prompt, sprint, publish, backup".yellow()
+            outln!("{}", "💾 Backup and archive workflow...".yellow());
+            outln!("  • Creates timestamped backups");
+            outln!("  • Archives completed projects");
+            outln!("  • Syncs to cloud storage");
+        }
+    }
+
+    outln!();
+    outln!("{}", "📝 This workflow is coming soon!".bright_black());
+    outln!(
+        "{}",
+        "💡 For now, try \"writers workflow daily\" or \"writers edit\"".bright_black()
+    );
+
+    Ok(())
+}
+
+/// Open today's page in the editor, then report the session against the
+/// day's goal and keep the streak
+async fn daily_session(
+    project: &ProjectManager,
+    goal: Option<String>,
+    time: Option<u32>,
+    words: Option<u32>,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let page = daily::page_path(project, today).await;
+    let created = !page.exists();
+    if created {
+        if let Some(parent) = page.parent() {
+            async_fs::create_dir_all(parent).await?;
+        }
+        // The date goes in the frontmatter, so an untouched page counts as no words
+        let meta = ContentMeta {
+            title: Some(today.format("%A, %B %-d, %Y").to_string()),
+            ..Default::default()
+        };
+        async_fs::write(&page, frontmatter::render(&meta, "\n")?).await?;
+    }
+
+    let mut state = daily::load(project).await?;
+    state.sessions += 1;
+    daily::save(project, &state).await?;
+
+    let before = project.read_content(&page).await?.word_count();
+    let streak = state.current(today);
+
+    outln!("{}", "📅 Daily Writing Session".cyan().bold());
+    outln!();
+    outln!(
+        "  {} {}",
+        if created { "Created" } else { "Opening" },
+        project.display_path(&page).cyan()
+    );
+    if let Some(goal) = &goal {
+        outln!("  Focus: {}", goal.cyan());
+    }
+    if let Some(time) = time {
+        outln!("  Time: {} minutes", time.to_string().cyan());
+    }
+    outln!(
+        "  Streak: {}",
+        if streak == 0 {
+            "none yet, today starts one".bright_black()
+        } else {
+            plural(streak, "day").yellow()
+        }
+    );
+
+    let started = Instant::now();
+    let options = LaunchOptions {
+        daily_goal: words.map(|w| w as usize),
+        at_end: true,
+        ..Default::default()
+    };
+    edit::launch_editor(project, page.clone(), options).await?;
+    let minutes = started.elapsed().as_secs() / 60;
+
+    let after = project.read_content(&page).await?.word_count();
+    let session = after as i64 - before as i64;
+
+    // Today's words across the whole project, by the word-count history
+    history::record(project).await?;
+    let snapshots = history::load(project).await?;
+    let today_words = history::words_per_day(&snapshots)
+        .get(&today)
+        .copied()
+        .unwrap_or(0);
+    let config = project.get_config().await?;
+    // The target paces the manuscript alone; the daily page isn't part of it
+    let manuscript = goals::manuscript_history(project, &config).await?;
+    let day_goal = words.map(|w| w as usize).or_else(|| {
+        let written = manuscript.last().map(|s| s.total).unwrap_or(0);
+        goals::daily_target(&config, written, &manuscript, today)
+    });
+
+    // Without a goal, any words written today keep the streak going
+    let met = match day_goal {
+        Some(target) => today_words >= target as i64,
+        None => today_words > 0,
+    };
+    let extended = met && state.mark_met(today);
+    daily::save(project, &state).await?;
+    let streak = state.current(today);
+
+    if output::is_json() {
+        return output::json(&json!({
+            "date": today,
+            "page": project.display_path(&page),
+            "session_words": session,
+            "page_words": after,
+            "today_words": today_words,
+            "goal": day_goal,
+            "goal_met": met,
+            "minutes": minutes,
+            "streak": { "current": streak, "longest": state.longest },
+        }));
+    }
+
+    outln!();
+    outln!("{}", "📊 Session Summary".cyan().bold());
+    outln!();
+    outln!(
+        "  Words this session: {}",
+        format!("{:+}", session).yellow()
+    );
+    match time {
+        Some(time) => outln!("  Time: {} of {} minutes", minutes, time),
+        None => outln!("  Time: {}", plural(minutes, "minute")),
+    }
+    match day_goal {
+        Some(target) => {
+            let percent = (today_words.max(0) as f64 / target.max(1) as f64 * 100.0).min(100.0);
+            outln!(
+                "  Today: {} / {} words ({:.0}%)",
+                today_words.to_string().yellow(),
+                target,
+                percent
             );
+            if !met {
+                outln!(
+                    "  {}",
+                    format!("{} words to go today", target as i64 - today_words).bright_black()
+                );
+            }
         }
+        None => outln!("  Today: {} words", today_words.to_string().yellow()),
+    }
+    outln!(
+        "  Streak: {} (longest {})",
+        plural(streak, "day").yellow(),
+        plural(state.longest, "day")
+    );
+    outln!();
+
+    if extended {
+        outln!(
+            "{}",
+            format!("🔥 Goal met! That's {} in a row.", plural(streak, "day"))
+                .green()
+                .bold()
+        );
+    } else if met {
+        outln!("{}", "✅ Today's goal is already met.".green());
+    } else if day_goal.is_none() {
+        outln!(
+            "{}",
+            "💡 Write something to keep your streak, or set \"daily_goal\" in writers.config.json"
+                .bright_black()
+        );
+    } else {
+        outln!(
+            "{}",
+            "💡 Run \"writers workflow daily\" again to keep going today".bright_black()
+        );
     }
 
-    println!();
-    println!(
+    Ok(())
+}
+
+/// Write against the clock in distraction-free mode, then log the sprint
+async fn sprint(
+    project: &ProjectManager,
+    file: Option<String>,
+    time: Option<u32>,
+    words: Option<u32>,
+) -> Result<()> {
+    let minutes = time.unwrap_or(DEFAULT_SPRINT_MINUTES);
+    if minutes == 0 {
+        outln!("{}", "❌ A sprint needs at least a minute".red());
+        outln!(
+            "{}",
+            "💡 Example: writers workflow sprint --time 20 --words 500".yellow()
+        );
+        return Ok(());
+    }
+
+    let path = match file {
+        Some(name) => match project.find_file(&name).await? {
+            Some(path) => path,
+            None => {
+                outln!("{} {}", "❌ File not found:".red(), name);
+                outln!(
+                    "{}",
+                    "💡 Use \"writers list\" to see available files".yellow()
+                );
+                return Ok(());
+            }
+        },
+        None => edit::select_file_to_edit(project).await?,
+    };
+
+    let target = words.map(|w| w as usize);
+    let before = project.read_content(&path).await?.word_count();
+    let previous = sprints::personal_bests(&sprints::load(project).await?);
+
+    outln!();
+    outln!("{}", "🏃 Writing Sprint".cyan().bold());
+    outln!();
+    outln!("  File: {}", project.display_path(&path).cyan());
+    outln!("  Time: {}", plural(minutes, "minute").cyan());
+    if let Some(target) = target {
+        outln!("  Target: {}", plural(target, "word").cyan());
+    }
+    outln!();
+    outln!(
         "{}",
-        "📝 Advanced workflows are coming soon!".bright_black()
+        "  Distraction-free until the time is up. Ctrl+S saves, Ctrl+Q ends early.".bright_black()
     );
-    println!(
+    outln!("{}", "Press any key to start...".bright_black());
+    edit::wait_for_key();
+
+    let started = Instant::now();
+    let options = LaunchOptions {
+        at_end: true,
+        sprint: Some(Sprint::new(minutes, target)),
+        ..Default::default()
+    };
+    edit::launch_editor(project, path.clone(), options).await?;
+    let seconds = started.elapsed().as_secs().min(minutes as u64 * 60);
+
+    let after = project.read_content(&path).await?.word_count();
+    let record = SprintRecord {
+        time: Local::now(),
+        file: project.display_path(&path),
+        planned_minutes: minutes,
+        seconds,
+        words: after as i64 - before as i64,
+        target,
+    };
+    sprints::append(project, &record).await?;
+
+    let wpm = record.words_per_minute();
+    let most_words = record.words > 0
+        && previous
+            .most_words
+            .as_ref()
+            .is_none_or(|best| record.words > best.words);
+    let fastest = record.words > 0
+        && record.seconds >= sprints::MIN_FASTEST_SECONDS
+        && previous
+            .fastest
+            .as_ref()
+            .is_none_or(|best| wpm > best.words_per_minute());
+
+    if output::is_json() {
+        let mut value = serde_json::to_value(&record)?;
+        value["words_per_minute"] = json!(wpm);
+        value["target_met"] = json!(target.map(|t| record.words >= t as i64));
+        value["personal_best"] = json!({ "most_words": most_words, "fastest": fastest });
+        return output::json(&value);
+    }
+
+    outln!();
+    outln!("{}", "📊 Sprint Results".cyan().bold());
+    outln!();
+    outln!("  Words: {}", format!("{:+}", record.words).yellow());
+    outln!(
+        "  Time: {}:{:02}{}",
+        seconds / 60,
+        seconds % 60,
+        if seconds < minutes as u64 * 60 {
+            " (ended early)".bright_black().to_string()
+        } else {
+            String::new()
+        }
+    );
+    outln!("  Speed: {}", format!("{:.1} words/minute", wpm).yellow());
+    if let Some(target) = target {
+        if record.words >= target as i64 {
+            outln!(
+                "  {}",
+                format!("🎯 Target of {} words reached!", target).green()
+            );
+        } else {
+            outln!(
+                "  {}",
+                format!("{} words short of {}", target as i64 - record.words, target)
+                    .bright_black()
+            );
+        }
+    }
+    outln!();
+
+    if most_words && previous.sprints > 0 {
+        outln!(
+            "{}",
+            "🏆 New personal best: most words in a sprint!"
+                .green()
+                .bold()
+        );
+    }
+    if fastest && previous.sprints > 0 {
+        outln!("{}", "🏆 New personal best: fastest sprint!".green().bold());
+    }
+    outln!(
         "{}",
-        "💡 For now, use basic commands like 'writers write' and 'writers edit'".bright_black()
+        "💡 See your sprint history with \"writers sprint history\"".bright_black()
     );
 
     Ok(())
 }
+
+/// Outstanding submissions from the ledger, with the commands that manage it
+async fn submission(project: &ProjectManager) -> Result<()> {
+    submissions::list(project, false).await?;
+    if output::is_json() {
+        return Ok(());
+    }
+
+    outln!("{}", "📧 Submission workflow:".yellow());
+    outln!("  • Log a submission: writers story submit <story> --to <market>");
+    outln!("  • Record a response: writers submissions update <id> --status accepted");
+    outln!("  • Compare markets: writers submissions markets");
+    outln!("  • Find stories to send out: writers submissions unsubmitted");
+
+    Ok(())
+}
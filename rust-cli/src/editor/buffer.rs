@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::utils::wordcount;
+
 #[derive(Debug, Clone)]
 pub struct BufferState {
     lines: Vec<String>,
@@ -185,11 +187,9 @@ impl TextBuffer {
         self.lines.len() == 1 && self.lines[0].is_empty()
     }
 
+    /// Words in the buffer, counted the same way as `writers stats`
     pub fn word_count(&self) -> usize {
-        self.lines
-            .iter()
-            .map(|line| line.split_whitespace().count())
-            .sum()
+        wordcount::count(&self.to_string())
     }
 
     pub fn char_count(&self) -> usize {
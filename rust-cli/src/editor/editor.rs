@@ -14,7 +14,12 @@ use tokio::fs;
 use super::buffer::TextBuffer;
 use super::cursor::Cursor;
 use super::input::InputHandler;
+use super::pomodoro::{self, PhaseEnd, Pomodoro};
 use super::screen::Screen;
+use super::sprint::Sprint;
+use crate::utils::lint::{Diagnostic, Linter, Severity};
+use crate::utils::pomodoros::{self, PomodoroRecord};
+use crate::utils::{frontmatter, history, PomodoroSettings, ProjectManager};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum EditorMode {
@@ -37,6 +42,19 @@ pub struct WritersEditor {
     show_line_numbers: bool,
     last_key: Option<KeyEvent>,
     last_key_time: std::time::Instant,
+    /// Project whose word-count history is updated on save
+    project: Option<ProjectManager>,
+    /// The day's word goal and words written today in other files
+    daily_goal: Option<(usize, i64)>,
+    linter: Linter,
+    /// Style problems from the last lint, cleared when the text changes
+    diagnostics: Vec<Diagnostic>,
+    /// Put the cursor after the last line when the file opens
+    start_at_end: bool,
+    /// A running sprint, which keeps distraction-free mode on until time is up
+    sprint: Option<Sprint>,
+    /// Work/break timer, idle until started with Ctrl+P
+    pomodoro: Pomodoro,
 }
 
 impl WritersEditor {
@@ -56,14 +74,61 @@ impl WritersEditor {
             show_line_numbers: true,
             last_key: None,
             last_key_time: std::time::Instant::now(),
+            project: None,
+            daily_goal: None,
+            linter: Linter::default(),
+            diagnostics: Vec::new(),
+            start_at_end: false,
+            sprint: None,
+            pomodoro: Pomodoro::new(None),
         }
     }
 
+    /// Record the project's word counts every time the file is saved
+    pub fn set_project(&mut self, project: ProjectManager) {
+        self.project = Some(project);
+    }
+
+    /// Show progress towards the day's goal; `written_elsewhere` is what was
+    /// written today in other files
+    pub fn set_daily_goal(&mut self, goal: usize, written_elsewhere: i64) {
+        self.daily_goal = Some((goal, written_elsewhere));
+    }
+
+    /// Lint with the project's rules instead of the defaults
+    pub fn set_linter(&mut self, linter: Linter) {
+        self.linter = linter;
+    }
+
+    /// Open files with the cursor at the end, ready to carry on writing
+    pub fn set_start_at_end(&mut self, start_at_end: bool) {
+        self.start_at_end = start_at_end;
+    }
+
+    /// Use the project's Pomodoro lengths instead of 25/5/15
+    pub fn set_pomodoro_settings(&mut self, settings: Option<&PomodoroSettings>) {
+        self.pomodoro = Pomodoro::new(settings);
+    }
+
+    /// Run a sprint: straight into insert mode, distraction-free with a
+    /// countdown, saving and closing when the time is up
+    pub fn set_sprint(&mut self, sprint: Sprint) {
+        self.sprint = Some(sprint);
+        self.distraction_free = true;
+        self.mode = EditorMode::Insert;
+    }
+
     pub async fn launch(&mut self, file_path: Option<String>) -> Result<()> {
         self.setup_terminal()?;
 
         if let Some(path) = file_path {
             self.open_file(&path).await?;
+            if self.start_at_end {
+                self.cursor.move_to_end_of_document(&self.buffer);
+            }
+        }
+        if let Some(sprint) = &mut self.sprint {
+            sprint.begin(self.buffer.word_count());
         }
 
         self.run().await?;
@@ -119,11 +184,80 @@ impl WritersEditor {
                     _ => {}
                 }
             }
+
+            if !self.should_quit && self.sprint.as_ref().is_some_and(Sprint::is_over) {
+                self.finish_sprint().await?;
+            }
+            if let Some(ended) = self.pomodoro.tick() {
+                self.pomodoro_phase_ended(ended).await;
+            }
         }
         Ok(())
     }
 
+    /// Announce the next phase, logging a finished work session against the file
+    async fn pomodoro_phase_ended(&mut self, ended: PhaseEnd) {
+        let PhaseEnd::Work { completed, minutes } = ended else {
+            self.status_message = "Break over, back to writing!".to_string();
+            return;
+        };
+        self.status_message = format!(
+            "Pomodoro #{} done! Time for a {}",
+            completed,
+            self.pomodoro.phase().label()
+        );
+
+        if let (Some(project), Some(path)) = (&self.project, self.buffer.get_file_path()) {
+            let record = PomodoroRecord {
+                time: chrono::Local::now(),
+                file: project.display_path(Path::new(&path)),
+                minutes,
+            };
+            if let Err(e) = pomodoros::append(project, &record).await {
+                self.status_message
+                    .push_str(&format!(" (not logged: {})", e));
+            }
+        }
+    }
+
+    /// Whether a Pomodoro break is holding off edits, saying so if it is
+    fn typing_blocked(&mut self) -> bool {
+        if !self.pomodoro.blocks_typing() {
+            return false;
+        }
+        self.status_message = format!(
+            "On a {}: writing resumes in {}",
+            self.pomodoro.phase().label(),
+            pomodoro::clock(self.pomodoro.remaining())
+        );
+        true
+    }
+
+    /// Time's up: keep the words and close the editor
+    async fn finish_sprint(&mut self) -> Result<()> {
+        if self.is_dirty {
+            self.save_file().await?;
+        }
+        self.should_quit = true;
+        Ok(())
+    }
+
     async fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
+        // The Pomodoro keys work in every mode
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Char('p') if ctrl => {
+                self.status_message = self.pomodoro.toggle();
+                return Ok(());
+            }
+            KeyCode::F(4) => {
+                self.pomodoro.reset();
+                self.status_message = "Pomodoro reset".to_string();
+                return Ok(());
+            }
+            _ => {}
+        }
+
         // Handle double-key sequences like 'dd' for delete line
         let now = std::time::Instant::now();
         let is_double_key = if let Some(last_key) = self.last_key {
@@ -175,6 +309,9 @@ impl WritersEditor {
             KeyCode::Char('t') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.toggle_typewriter_mode();
             }
+            KeyCode::Char('l') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.next_diagnostic();
+            }
             KeyCode::F(3) => {
                 self.toggle_distraction_free();
             }
@@ -310,6 +447,14 @@ impl WritersEditor {
             fs::write(&path, content).await?;
             self.is_dirty = false;
             self.status_message = format!("Saved: {}", path);
+
+            self.diagnostics = self.linter.check(&self.buffer.to_string());
+
+            if let Some(project) = &self.project {
+                if let Err(e) = history::record(project).await {
+                    self.status_message = format!("Saved: {} (history not updated: {})", path, e);
+                }
+            }
         } else {
             self.status_message = "No file path set".to_string();
         }
@@ -317,12 +462,18 @@ impl WritersEditor {
     }
 
     fn insert_char(&mut self, c: char) {
+        if self.typing_blocked() {
+            return;
+        }
         self.buffer.insert_char(self.cursor.row, self.cursor.col, c);
         self.cursor.move_right(&self.buffer);
         self.mark_dirty();
     }
 
     fn insert_newline(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         self.buffer.insert_newline(self.cursor.row, self.cursor.col);
         self.cursor.move_down(&self.buffer);
         self.cursor.col = 0;
@@ -330,6 +481,9 @@ impl WritersEditor {
     }
 
     fn backspace(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         if self.cursor.col > 0 {
             self.cursor.move_left();
             self.buffer.delete_char(self.cursor.row, self.cursor.col);
@@ -343,11 +497,17 @@ impl WritersEditor {
     }
 
     fn delete_char(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         self.buffer.delete_char(self.cursor.row, self.cursor.col);
         self.mark_dirty();
     }
 
     fn delete_line(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         self.buffer.delete_line(self.cursor.row);
         if self.cursor.row >= self.buffer.line_count() && self.buffer.line_count() > 0 {
             self.cursor.row = self.buffer.line_count() - 1;
@@ -363,6 +523,9 @@ impl WritersEditor {
     }
 
     fn undo(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         if self.buffer.undo() {
             self.status_message = "Undo".to_string();
             self.is_dirty = true;
@@ -370,6 +533,9 @@ impl WritersEditor {
     }
 
     fn redo(&mut self) {
+        if self.typing_blocked() {
+            return;
+        }
         if self.buffer.redo() {
             self.status_message = "Redo".to_string();
             self.is_dirty = true;
@@ -400,6 +566,11 @@ impl WritersEditor {
     }
 
     fn toggle_distraction_free(&mut self) {
+        if self.sprint.is_some() {
+            self.status_message =
+                "Distraction-free mode stays on until the sprint ends".to_string();
+            return;
+        }
         self.distraction_free = !self.distraction_free;
         self.status_message = if self.distraction_free {
             "Distraction-free mode: ON".to_string()
@@ -416,6 +587,54 @@ impl WritersEditor {
 
     fn mark_dirty(&mut self) {
         self.is_dirty = true;
+        self.diagnostics.clear();
+    }
+
+    /// Lint the buffer and move to the next problem after the cursor,
+    /// wrapping around to the first
+    fn next_diagnostic(&mut self) {
+        self.diagnostics = self.linter.check(&self.buffer.to_string());
+        if self.diagnostics.is_empty() {
+            self.status_message = "No style problems found".to_string();
+            return;
+        }
+
+        let cursor = (self.cursor.row + 1, self.cursor.col + 1);
+        let index = self
+            .diagnostics
+            .iter()
+            .position(|d| (d.line, d.column) > cursor)
+            .unwrap_or(0);
+        let diagnostic = &self.diagnostics[index];
+
+        // Diagnostic columns count characters; the cursor counts bytes
+        self.cursor.row = diagnostic.line - 1;
+        self.cursor.col = self
+            .buffer
+            .get_line(self.cursor.row)
+            .char_indices()
+            .nth(diagnostic.column - 1)
+            .map(|(offset, _)| offset)
+            .unwrap_or(0);
+
+        self.status_message = format!(
+            "{}:{} {}: {} ({}/{})",
+            diagnostic.line,
+            diagnostic.column,
+            diagnostic.rule,
+            diagnostic.message,
+            index + 1,
+            self.diagnostics.len()
+        );
+    }
+
+    /// The most severe problem on a line, if any
+    fn line_severity(&self, line: usize) -> Option<Severity> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.line == line)
+            .map(|d| d.severity)
+            .max()
     }
 
     fn render(&mut self) -> Result<()> {
@@ -454,11 +673,17 @@ impl WritersEditor {
                     0
                 };
 
-                // Render line number
+                // Render line number, colored when the line has style problems
                 if self.show_line_numbers && !self.distraction_free {
+                    let color = match self.line_severity(buffer_row + 1) {
+                        Some(Severity::Error) => Color::Red,
+                        Some(Severity::Warning) => Color::Yellow,
+                        Some(Severity::Info) => Color::Blue,
+                        _ => Color::DarkGrey,
+                    };
                     execute!(
                         io::stdout(),
-                        SetForegroundColor(Color::DarkGrey),
+                        SetForegroundColor(color),
                         Print(format!("{:4} ", buffer_row + 1)),
                         ResetColor
                     )?;
@@ -491,9 +716,11 @@ impl WritersEditor {
             }
         }
 
-        // Render status bar
+        // Render status bar; a sprint keeps its countdown even when distraction-free
         if !self.distraction_free {
             self.render_status_bar()?;
+        } else if let Some(sprint) = &self.sprint {
+            self.render_sprint_bar(sprint)?;
         }
 
         // Position cursor
@@ -551,10 +778,31 @@ impl WritersEditor {
 
         let dirty_indicator = if self.is_dirty { " [+]" } else { "" };
         let line_info = format!("{}:{}", self.cursor.row + 1, self.cursor.col + 1);
+        let words = self.buffer.word_count();
+        let target = frontmatter::parse(&self.buffer.to_string()).0.target_words;
+        let mut word_info = match target {
+            Some(target) if target > 0 => format!(
+                "{}/{} words ({}%)",
+                words,
+                target,
+                (words * 100 / target).min(100)
+            ),
+            _ => format!("{} words", words),
+        };
+        if let Some((goal, elsewhere)) = self.daily_goal {
+            let today = (elsewhere + words as i64).max(0);
+            word_info.push_str(&format!(" | today {}/{}", today, goal));
+        }
+        if !self.diagnostics.is_empty() {
+            word_info.push_str(&format!(" | lint {}", self.diagnostics.len()));
+        }
+        if self.pomodoro.is_started() {
+            word_info.push_str(&format!(" | {}", self.pomodoro.status()));
+        }
 
         let status = format!(
-            " {} | {}{} | {} ",
-            mode_str, file_name, dirty_indicator, line_info
+            " {} | {}{} | {} | {} ",
+            mode_str, file_name, dirty_indicator, line_info, word_info
         );
 
         execute!(io::stdout(), Print(&status))?;
@@ -574,6 +822,30 @@ impl WritersEditor {
 
         Ok(())
     }
+
+    fn render_sprint_bar(&self, sprint: &Sprint) -> Result<()> {
+        let height = self.screen.get_height();
+
+        execute!(
+            io::stdout(),
+            MoveTo(0, (height - 2) as u16),
+            SetForegroundColor(if sprint.remaining().as_secs() < 60 {
+                Color::Yellow
+            } else {
+                Color::DarkGrey
+            }),
+            Print(format!(" {}", sprint.status(self.buffer.word_count()))),
+            ResetColor,
+            Clear(ClearType::UntilNewLine),
+            MoveTo(0, (height - 1) as u16),
+            SetForegroundColor(Color::DarkGrey),
+            Print(&self.status_message),
+            ResetColor,
+            Clear(ClearType::UntilNewLine)
+        )?;
+
+        Ok(())
+    }
 }
 
 impl Default for WritersEditor {
@@ -2,6 +2,8 @@ pub mod buffer;
 pub mod cursor;
 pub mod editor;
 pub mod input;
+pub mod pomodoro;
 pub mod screen;
+pub mod sprint;
 
 pub use editor::WritersEditor;
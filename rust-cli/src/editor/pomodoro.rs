@@ -0,0 +1,281 @@
+use std::time::{Duration, Instant};
+
+use crate::utils::PomodoroSettings;
+
+/// What the timer is counting down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::Work => "pomodoro",
+            Phase::ShortBreak => "short break",
+            Phase::LongBreak => "long break",
+        }
+    }
+
+    pub fn is_break(self) -> bool {
+        self != Phase::Work
+    }
+}
+
+/// A phase that just ran out. The timer moves straight on to the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseEnd {
+    /// A work session finished; holds how many have finished so far
+    Work {
+        completed: usize,
+        minutes: u32,
+    },
+    Break,
+}
+
+/// Work sessions and breaks, advanced by the editor's event loop calling
+/// `tick`. Nothing runs until it's started.
+#[derive(Debug, Clone)]
+pub struct Pomodoro {
+    work: Duration,
+    short_break: Duration,
+    long_break: Duration,
+    long_break_every: usize,
+    block_typing: bool,
+    phase: Phase,
+    /// When the running phase would end; `None` while stopped or paused
+    ends_at: Option<Instant>,
+    /// Time left in the phase while paused or not yet started
+    remaining: Duration,
+    started: bool,
+    completed: usize,
+}
+
+impl Pomodoro {
+    pub fn new(settings: Option<&PomodoroSettings>) -> Self {
+        let minutes = |value: Option<u32>, default: u32| {
+            Duration::from_secs(value.filter(|m| *m > 0).unwrap_or(default) as u64 * 60)
+        };
+        let settings = settings.cloned().unwrap_or_default();
+        let work = minutes(settings.work_minutes, 25);
+
+        Self {
+            work,
+            short_break: minutes(settings.short_break_minutes, 5),
+            long_break: minutes(settings.long_break_minutes, 15),
+            long_break_every: settings.long_break_every.filter(|n| *n > 0).unwrap_or(4) as usize,
+            block_typing: settings.block_typing.unwrap_or(false),
+            phase: Phase::Work,
+            ends_at: None,
+            remaining: work,
+            started: false,
+            completed: 0,
+        }
+    }
+
+    /// Start, pause or resume. Returns a message for the status line.
+    pub fn toggle(&mut self) -> String {
+        match self.ends_at.take() {
+            Some(ends_at) => {
+                self.remaining = ends_at.saturating_duration_since(Instant::now());
+                format!("Pomodoro paused: {} left", clock(self.remaining))
+            }
+            None => {
+                self.ends_at = Some(Instant::now() + self.remaining);
+                let verb = if self.started { "resumed" } else { "started" };
+                self.started = true;
+                format!(
+                    "Pomodoro {}: {} {}",
+                    verb,
+                    clock(self.remaining),
+                    self.phase.label()
+                )
+            }
+        }
+    }
+
+    /// Stop and go back to the start of a work session, keeping the count
+    pub fn reset(&mut self) {
+        self.phase = Phase::Work;
+        self.ends_at = None;
+        self.remaining = self.work;
+        self.started = false;
+    }
+
+    /// Move on when the running phase is over, starting the next one
+    pub fn tick(&mut self) -> Option<PhaseEnd> {
+        let ends_at = self.ends_at?;
+        let now = Instant::now();
+        if now < ends_at {
+            return None;
+        }
+
+        let ended = match self.phase {
+            Phase::Work => {
+                self.completed += 1;
+                self.phase = if self.completed.is_multiple_of(self.long_break_every) {
+                    Phase::LongBreak
+                } else {
+                    Phase::ShortBreak
+                };
+                PhaseEnd::Work {
+                    completed: self.completed,
+                    minutes: (self.work.as_secs() / 60) as u32,
+                }
+            }
+            _ => {
+                self.phase = Phase::Work;
+                PhaseEnd::Break
+            }
+        };
+        self.remaining = self.duration(self.phase);
+        self.ends_at = Some(now + self.remaining);
+        Some(ended)
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn remaining(&self) -> Duration {
+        match self.ends_at {
+            Some(ends_at) => ends_at.saturating_duration_since(Instant::now()),
+            None => self.remaining,
+        }
+    }
+
+    /// Whether edits should be refused: a break is running and the settings
+    /// ask for typing to be blocked
+    pub fn blocks_typing(&self) -> bool {
+        self.block_typing && self.ends_at.is_some() && self.phase.is_break()
+    }
+
+    /// Status bar text, e.g. "pomodoro 24:13 (2 done)"
+    pub fn status(&self) -> String {
+        let mut status = format!("{} {}", self.phase.label(), clock(self.remaining()));
+        if self.ends_at.is_none() {
+            status.push_str(" paused");
+        }
+        if self.completed > 0 {
+            status.push_str(&format!(" ({} done)", self.completed));
+        }
+        status
+    }
+
+    fn duration(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Work => self.work,
+            Phase::ShortBreak => self.short_break,
+            Phase::LongBreak => self.long_break,
+        }
+    }
+}
+
+/// A duration as "mm:ss"
+pub fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the current phase out and move to the next
+    fn finish(pomodoro: &mut Pomodoro) -> Option<PhaseEnd> {
+        pomodoro.ends_at = Some(Instant::now());
+        pomodoro.tick()
+    }
+
+    #[test]
+    fn defaults_and_settings() {
+        let pomodoro = Pomodoro::new(None);
+        assert_eq!(pomodoro.remaining(), Duration::from_secs(25 * 60));
+        assert_eq!(pomodoro.status(), "pomodoro 25:00 paused");
+
+        let settings = PomodoroSettings {
+            work_minutes: Some(50),
+            short_break_minutes: Some(0),
+            ..Default::default()
+        };
+        let pomodoro = Pomodoro::new(Some(&settings));
+        assert_eq!(pomodoro.work, Duration::from_secs(50 * 60));
+        assert_eq!(pomodoro.short_break, Duration::from_secs(5 * 60));
+    }
+
+    #[test]
+    fn toggle_starts_pauses_and_resumes() {
+        let mut pomodoro = Pomodoro::new(None);
+        assert_eq!(pomodoro.tick(), None);
+
+        assert!(pomodoro.toggle().starts_with("Pomodoro started"));
+        assert!(pomodoro.is_started());
+        assert_eq!(pomodoro.tick(), None);
+        assert!(pomodoro.toggle().starts_with("Pomodoro paused"));
+        assert!(pomodoro.toggle().starts_with("Pomodoro resumed"));
+    }
+
+    #[test]
+    fn work_alternates_with_breaks() {
+        let settings = PomodoroSettings {
+            long_break_every: Some(2),
+            ..Default::default()
+        };
+        let mut pomodoro = Pomodoro::new(Some(&settings));
+        pomodoro.toggle();
+
+        assert_eq!(
+            finish(&mut pomodoro),
+            Some(PhaseEnd::Work {
+                completed: 1,
+                minutes: 25
+            })
+        );
+        assert_eq!(pomodoro.phase(), Phase::ShortBreak);
+        assert_eq!(finish(&mut pomodoro), Some(PhaseEnd::Break));
+        assert_eq!(pomodoro.phase(), Phase::Work);
+        finish(&mut pomodoro);
+        assert_eq!(pomodoro.phase(), Phase::LongBreak);
+        assert!(pomodoro.status().ends_with("(2 done)"));
+    }
+
+    #[test]
+    fn typing_is_blocked_only_during_running_breaks() {
+        let settings = PomodoroSettings {
+            block_typing: Some(true),
+            ..Default::default()
+        };
+        let mut pomodoro = Pomodoro::new(Some(&settings));
+        pomodoro.toggle();
+        assert!(!pomodoro.blocks_typing());
+
+        finish(&mut pomodoro);
+        assert!(pomodoro.blocks_typing());
+        pomodoro.toggle();
+        assert!(!pomodoro.blocks_typing());
+    }
+
+    #[test]
+    fn reset_keeps_the_count() {
+        let mut pomodoro = Pomodoro::new(None);
+        pomodoro.toggle();
+        finish(&mut pomodoro);
+        pomodoro.reset();
+
+        assert_eq!(pomodoro.phase(), Phase::Work);
+        assert!(!pomodoro.is_started());
+        assert_eq!(pomodoro.completed, 1);
+    }
+
+    #[test]
+    fn clock_format() {
+        assert_eq!(clock(Duration::from_secs(0)), "00:00");
+        assert_eq!(clock(Duration::from_secs(25 * 60 + 7)), "25:07");
+    }
+}
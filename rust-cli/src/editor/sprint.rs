@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+/// A timed writing sprint: a countdown and the words written since it began
+#[derive(Debug, Clone)]
+pub struct Sprint {
+    duration: Duration,
+    started: Instant,
+    start_words: usize,
+    /// Words to reach before the time is up
+    target: Option<usize>,
+}
+
+impl Sprint {
+    pub fn new(minutes: u32, target: Option<usize>) -> Self {
+        Self {
+            duration: Duration::from_secs(minutes as u64 * 60),
+            started: Instant::now(),
+            start_words: 0,
+            target,
+        }
+    }
+
+    /// Start the clock, counting words from `words`
+    pub fn begin(&mut self, words: usize) {
+        self.started = Instant::now();
+        self.start_words = words;
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.started.elapsed())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Words added since the sprint began; deleting counts against it
+    pub fn words(&self, current: usize) -> i64 {
+        current as i64 - self.start_words as i64
+    }
+
+    /// The status bar text, e.g. "SPRINT 12:04 left | +215/500 words"
+    pub fn status(&self, current: usize) -> String {
+        let remaining = self.remaining().as_secs();
+        let words = self.words(current);
+        let progress = match self.target {
+            Some(target) => format!("{:+}/{} words", words, target),
+            None => format!("{:+} words", words),
+        };
+        format!(
+            "SPRINT {:02}:{:02} left | {}",
+            remaining / 60,
+            remaining % 60,
+            progress
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_count_from_the_start() {
+        let mut sprint = Sprint::new(15, Some(500));
+        sprint.begin(1000);
+        assert_eq!(sprint.words(1215), 215);
+        assert_eq!(sprint.words(990), -10);
+        assert!(!sprint.is_over());
+        assert!(sprint.status(1215).ends_with("left | +215/500 words"));
+    }
+
+    #[test]
+    fn status_without_a_target() {
+        let sprint = Sprint::new(0, None);
+        assert!(sprint.is_over());
+        assert_eq!(sprint.status(3), "SPRINT 00:00 left | +3 words");
+    }
+}
@@ -0,0 +1,490 @@
+use anyhow::Result;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::html::escape;
+use super::Manuscript;
+use crate::utils::ContactInfo;
+
+/// Options for a Standard Manuscript Format document
+pub struct DocxOptions {
+    pub contact: ContactInfo,
+    /// Exact word count; printed rounded on the first page
+    pub word_count: usize,
+    /// "courier" (default) or "times"
+    pub font: String,
+    /// Start each chapter on a new page with a heading (novel format)
+    pub chapter_headings: bool,
+}
+
+// All measurements are in twentieths of a point unless noted otherwise
+const PAGE_WIDTH: u32 = 12240;
+const PAGE_HEIGHT: u32 = 15840;
+const MARGIN: u32 = 1440;
+const HALF_INCH: u32 = 720;
+const DOUBLE_SPACED: u32 = 480;
+
+/// Write the manuscript as a .docx in Shunn-style Standard Manuscript Format
+pub fn write(manuscript: &Manuscript, options: &DocxOptions, path: &Path) -> Result<()> {
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", PACKAGE_RELS.to_string()),
+        ("docProps/core.xml", core_properties(manuscript)),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELS.to_string()),
+        ("word/styles.xml", styles(options)),
+        ("word/settings.xml", SETTINGS.to_string()),
+        ("word/header1.xml", running_header(manuscript, options)),
+        ("word/header2.xml", empty_header()),
+        ("word/document.xml", document(manuscript, options)),
+    ];
+
+    for (name, content) in parts {
+        zip.start_file(name, deflated)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Round a word count the way submission guidelines expect: to the nearest
+/// hundred for short fiction and the nearest thousand for novel-length work.
+pub fn rounded_word_count(words: usize) -> usize {
+    let unit = if words >= 20_000 { 1000 } else { 100 };
+    ((words + unit / 2) / unit * unit).max(100)
+}
+
+/// Format a number with thousands separators
+fn with_separators(number: usize) -> String {
+    let digits = number.to_string();
+    let mut output = String::new();
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            output.push(',');
+        }
+        output.push(digit);
+    }
+
+    output
+}
+
+fn font_name(options: &DocxOptions) -> &'static str {
+    match options.font.to_lowercase().as_str() {
+        "times" | "times new roman" => "Times New Roman",
+        _ => "Courier New",
+    }
+}
+
+/// Surname for the running header, taken from the legal name or byline
+fn surname(manuscript: &Manuscript, options: &DocxOptions) -> String {
+    let name = options
+        .contact
+        .legal_name
+        .as_deref()
+        .unwrap_or(&manuscript.author);
+
+    name.split_whitespace()
+        .next_back()
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Short title for the running header
+fn title_keyword(manuscript: &Manuscript) -> String {
+    let skip = ["a", "an", "the"];
+    manuscript
+        .title
+        .split_whitespace()
+        .find(|word| !skip.contains(&word.to_lowercase().as_str()))
+        .unwrap_or(&manuscript.title)
+        .to_string()
+}
+
+fn run(text: &str, italic: bool, bold: bool) -> String {
+    let mut properties = String::new();
+    if bold {
+        properties.push_str("<w:b/>");
+    }
+    if italic {
+        properties.push_str("<w:i/>");
+    }
+
+    let properties = if properties.is_empty() {
+        String::new()
+    } else {
+        format!("<w:rPr>{}</w:rPr>", properties)
+    };
+
+    format!(
+        "<w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>",
+        properties,
+        escape(text)
+    )
+}
+
+fn paragraph(properties: &str, runs: &str) -> String {
+    if properties.is_empty() {
+        format!("<w:p>{}</w:p>", runs)
+    } else {
+        format!("<w:p><w:pPr>{}</w:pPr>{}</w:p>", properties, runs)
+    }
+}
+
+fn centered(text: &str, extra_properties: &str) -> String {
+    paragraph(
+        &format!(
+            "{}<w:ind w:firstLine=\"0\"/><w:jc w:val=\"center\"/>",
+            extra_properties
+        ),
+        &run(text, false, false),
+    )
+}
+
+fn scene_break() -> String {
+    centered("#", "")
+}
+
+/// Contact block (top left, single spaced) and word count (top right)
+fn first_page_header(manuscript: &Manuscript, options: &DocxOptions) -> String {
+    let single_spaced = format!(
+        "<w:tabs><w:tab w:val=\"right\" w:pos=\"{}\"/></w:tabs><w:spacing w:line=\"240\" w:lineRule=\"auto\"/><w:ind w:firstLine=\"0\"/>",
+        PAGE_WIDTH - 2 * MARGIN
+    );
+
+    let name = options
+        .contact
+        .legal_name
+        .clone()
+        .unwrap_or_else(|| manuscript.author.clone());
+    let word_count = format!(
+        "about {} words",
+        with_separators(rounded_word_count(options.word_count))
+    );
+
+    let mut lines = Vec::new();
+    lines.push(paragraph(
+        &single_spaced,
+        &format!(
+            "{}<w:r><w:tab/></w:r>{}",
+            run(&name, false, false),
+            run(&word_count, false, false)
+        ),
+    ));
+
+    let contact = &options.contact;
+    let details = contact
+        .address
+        .iter()
+        .cloned()
+        .chain(contact.phone.clone())
+        .chain(contact.email.clone())
+        .chain(contact.website.clone());
+
+    for line in details {
+        lines.push(paragraph(&single_spaced, &run(&line, false, false)));
+    }
+
+    lines.join("")
+}
+
+fn document(manuscript: &Manuscript, options: &DocxOptions) -> String {
+    let mut body = first_page_header(manuscript, options);
+
+    // Title about halfway down the first page, then the byline
+    body.push_str(&centered(
+        &manuscript.title,
+        &format!(
+            "<w:spacing w:before=\"{}\"/>",
+            PAGE_HEIGHT / 2 - 2 * MARGIN - 1440
+        ),
+    ));
+    body.push_str(&centered(&format!("by {}", manuscript.author), ""));
+
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        // Parts get a page of their own before their first chapter
+        let part = manuscript
+            .part_opening(index)
+            .filter(|_| options.chapter_headings);
+        if let Some(part) = part {
+            let page_break = if index == 0 {
+                ""
+            } else {
+                "<w:pageBreakBefore/>"
+            };
+            body.push_str(&centered(
+                part,
+                &format!("{}<w:spacing w:before=\"2880\"/>", page_break),
+            ));
+        }
+
+        if options.chapter_headings && !chapter.title.is_empty() {
+            // Chapters start on a new page about a third of the way down
+            let page_break = if index == 0 && part.is_none() {
+                ""
+            } else {
+                "<w:pageBreakBefore/>"
+            };
+            body.push_str(&centered(
+                &chapter.title,
+                &format!("{}<w:spacing w:before=\"2880\"/>", page_break),
+            ));
+        } else if index > 0 {
+            body.push_str(&scene_break());
+        }
+
+        body.push_str(&chapter_paragraphs(&chapter.body));
+    }
+
+    body.push_str(&centered("END", ""));
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<w:body>{body}<w:sectPr><w:headerReference w:type="default" r:id="rIdHeaderDefault"/><w:headerReference w:type="first" r:id="rIdHeaderFirst"/><w:pgSz w:w="{width}" w:h="{height}"/><w:pgMar w:top="{margin}" w:right="{margin}" w:bottom="{margin}" w:left="{margin}" w:header="{half}" w:footer="{half}" w:gutter="0"/><w:titlePg/></w:sectPr></w:body>
+</w:document>
+"#,
+        body = body,
+        width = PAGE_WIDTH,
+        height = PAGE_HEIGHT,
+        margin = MARGIN,
+        half = HALF_INCH
+    )
+}
+
+/// Convert a chapter's markdown into manuscript paragraphs
+fn chapter_paragraphs(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+
+    let mut output = String::new();
+    let mut runs = String::new();
+    let mut italic = 0;
+    let mut bold = 0;
+    let mut quote_depth = 0;
+    let mut list_item = false;
+
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Emphasis) => italic += 1,
+            Event::End(Tag::Emphasis) => italic -= 1,
+            Event::Start(Tag::Strong) => bold += 1,
+            Event::End(Tag::Strong) => bold -= 1,
+            Event::Start(Tag::BlockQuote) => quote_depth += 1,
+            Event::End(Tag::BlockQuote) => quote_depth -= 1,
+            Event::Start(Tag::Item) => {
+                list_item = true;
+                runs.push_str(&run("• ", false, false));
+            }
+            Event::End(Tag::Paragraph) | Event::End(Tag::Heading(..)) | Event::End(Tag::Item) => {
+                if runs.is_empty() {
+                    // An empty heading (a lone `#`) is a scene break
+                    if matches!(event, Event::End(Tag::Heading(..))) {
+                        output.push_str(&scene_break());
+                    }
+                    continue;
+                }
+
+                let properties = if quote_depth > 0 || list_item {
+                    format!("<w:ind w:left=\"{}\" w:firstLine=\"0\"/>", HALF_INCH)
+                } else if matches!(event, Event::End(Tag::Heading(..))) {
+                    "<w:ind w:firstLine=\"0\"/><w:jc w:val=\"center\"/>".to_string()
+                } else {
+                    String::new()
+                };
+                output.push_str(&paragraph(&properties, &runs));
+                runs.clear();
+                if matches!(event, Event::End(Tag::Item)) {
+                    list_item = false;
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                runs.push_str(&run(&text, italic > 0, bold > 0));
+            }
+            Event::SoftBreak => runs.push_str(&run(" ", italic > 0, bold > 0)),
+            Event::HardBreak => runs.push_str("<w:r><w:br/></w:r>"),
+            Event::Rule => output.push_str(&scene_break()),
+            _ => {}
+        }
+    }
+
+    output
+}
+
+fn running_header(manuscript: &Manuscript, options: &DocxOptions) -> String {
+    let label = format!(
+        "{} / {} / ",
+        surname(manuscript, options),
+        title_keyword(manuscript)
+    );
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:p><w:pPr><w:spacing w:line="240" w:lineRule="auto"/><w:ind w:firstLine="0"/><w:jc w:val="right"/></w:pPr>{label}<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGE </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>2</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p></w:hdr>
+"#,
+        label = run(&label, false, false)
+    )
+}
+
+fn empty_header() -> String {
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:p/></w:hdr>
+"#
+    .to_string()
+}
+
+fn styles(options: &DocxOptions) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="{font}" w:hAnsi="{font}" w:cs="{font}" w:eastAsia="{font}"/><w:sz w:val="24"/><w:szCs w:val="24"/><w:lang w:val="en-US"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:before="0" w:after="0" w:line="{line}" w:lineRule="auto"/><w:ind w:firstLine="{indent}"/></w:pPr></w:pPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+</w:styles>
+"#,
+        font = font_name(options),
+        line = DOUBLE_SPACED,
+        indent = HALF_INCH
+    )
+}
+
+fn core_properties(manuscript: &Manuscript) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:creator>{}</dc:creator><dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created></cp:coreProperties>
+"#,
+        escape(&manuscript.title),
+        escape(&manuscript.author),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    )
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+<Override PartName="/word/settings.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml"/>
+<Override PartName="/word/header1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"/>
+<Override PartName="/word/header2.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"/>
+<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+</Types>
+"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+</Relationships>
+"#;
+
+const DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rIdSettings" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings" Target="settings.xml"/>
+<Relationship Id="rIdHeaderDefault" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Target="header1.xml"/>
+<Relationship Id="rIdHeaderFirst" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Target="header2.xml"/>
+</Relationships>
+"#;
+
+const SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:defaultTabStop w:val="720"/><w:compat><w:compatSetting w:name="compatibilityMode" w:uri="http://schemas.microsoft.com/office/word" w:val="15"/></w:compat></w:settings>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manuscript(title: &str, author: &str) -> Manuscript {
+        Manuscript {
+            title: title.to_string(),
+            author: author.to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: String::new(),
+            chapters: Vec::new(),
+        }
+    }
+
+    fn options(legal_name: Option<&str>, font: &str) -> DocxOptions {
+        DocxOptions {
+            contact: ContactInfo {
+                legal_name: legal_name.map(str::to_string),
+                ..Default::default()
+            },
+            word_count: 0,
+            font: font.to_string(),
+            chapter_headings: true,
+        }
+    }
+
+    #[test]
+    fn word_counts_round_by_length() {
+        assert_eq!(rounded_word_count(0), 100);
+        assert_eq!(rounded_word_count(4_349), 4_300);
+        assert_eq!(rounded_word_count(4_350), 4_400);
+        assert_eq!(rounded_word_count(85_499), 85_000);
+        assert_eq!(rounded_word_count(85_500), 86_000);
+    }
+
+    #[test]
+    fn thousands_separators() {
+        assert_eq!(with_separators(900), "900");
+        assert_eq!(with_separators(4_300), "4,300");
+        assert_eq!(with_separators(1_250_000), "1,250,000");
+    }
+
+    #[test]
+    fn running_header_names() {
+        let book = manuscript("The Long Night", "Jane Q. Doe");
+        assert_eq!(surname(&book, &options(None, "")), "Doe");
+        assert_eq!(surname(&book, &options(Some("Janet Smith"), "")), "Smith");
+        assert_eq!(title_keyword(&book), "Long");
+        assert_eq!(title_keyword(&manuscript("The", "A")), "The");
+    }
+
+    #[test]
+    fn fonts() {
+        assert_eq!(font_name(&options(None, "Times")), "Times New Roman");
+        assert_eq!(font_name(&options(None, "courier")), "Courier New");
+        assert_eq!(font_name(&options(None, "comic sans")), "Courier New");
+    }
+
+    #[test]
+    fn chapter_markdown_to_paragraphs() {
+        let output = chapter_paragraphs("She *ran* & hid.\n\n---\n\n> Quoted");
+        assert_eq!(
+            output,
+            [
+                paragraph(
+                    "",
+                    &[
+                        run("She ", false, false),
+                        run("ran", true, false),
+                        run(" & hid.", false, false)
+                    ]
+                    .concat()
+                ),
+                scene_break(),
+                paragraph(
+                    &format!("<w:ind w:left=\"{}\" w:firstLine=\"0\"/>", HALF_INCH),
+                    &run("Quoted", false, false)
+                ),
+            ]
+            .concat()
+        );
+        assert_eq!(
+            run("a & b", false, true),
+            "<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">a &amp; b</w:t></w:r>"
+        );
+    }
+}
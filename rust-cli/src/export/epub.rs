@@ -0,0 +1,616 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::html::{escape, markdown_to_xhtml};
+use super::Manuscript;
+
+const MIMETYPE: &str = "application/epub+zip";
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+const STYLESHEET: &str = r#"body { font-family: serif; line-height: 1.5; margin: 0 5%; }
+h1 { text-align: center; margin: 3em 0 2em; page-break-before: always; }
+p { text-indent: 1.5em; margin: 0; }
+h1 + p, hr + p { text-indent: 0; }
+hr { border: none; margin: 1.5em 0; text-align: center; }
+.part { text-align: center; margin-top: 30%; }
+.title-page { text-align: center; margin-top: 30%; }
+.title-page .author { font-style: italic; font-size: 1.2em; }
+.cover { text-align: center; margin: 0; padding: 0; }
+.cover img { max-width: 100%; max-height: 100%; }
+"#;
+
+/// Package the manuscript as an EPUB 3 file with an EPUB 2 NCX for older readers
+pub fn write(manuscript: &Manuscript, path: &Path) -> Result<()> {
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    // The mimetype must be the first entry and must not be compressed
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(MIMETYPE.as_bytes())?;
+
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(CONTAINER_XML.as_bytes())?;
+
+    zip.start_file("OEBPS/style.css", deflated)?;
+    zip.write_all(STYLESHEET.as_bytes())?;
+
+    let cover = match &manuscript.cover {
+        Some(cover_path) => {
+            let media_type = image_media_type(cover_path)?;
+            let extension = cover_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("jpg")
+                .to_lowercase();
+            let href = format!("images/cover.{}", extension);
+            let data = std::fs::read(cover_path)
+                .map_err(|e| anyhow!("Failed to read cover {}: {}", cover_path.display(), e))?;
+
+            zip.start_file(format!("OEBPS/{}", href), stored)?;
+            zip.write_all(&data)?;
+            zip.start_file("OEBPS/cover.xhtml", deflated)?;
+            zip.write_all(cover_page(manuscript, &href).as_bytes())?;
+            Some((href, media_type))
+        }
+        None => None,
+    };
+
+    zip.start_file("OEBPS/title.xhtml", deflated)?;
+    zip.write_all(title_page(manuscript).as_bytes())?;
+
+    let mut part_number = 0;
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        if let Some(part) = manuscript.part_opening(index) {
+            part_number += 1;
+            let body = format!(
+                "<section epub:type=\"part\" class=\"part\" id=\"part-{}\">\n<h1>{}</h1>\n</section>",
+                part_number,
+                escape(part)
+            );
+            zip.start_file(format!("OEBPS/{}", part_href(part_number)), deflated)?;
+            zip.write_all(xhtml_document(manuscript, part, &body).as_bytes())?;
+        }
+
+        let body = format!(
+            "<section epub:type=\"chapter\" id=\"chapter-{}\">\n<h1>{}</h1>\n{}</section>",
+            index + 1,
+            escape(manuscript.chapter_label(chapter)),
+            markdown_to_xhtml(&chapter.body)
+        );
+        let title = manuscript.chapter_label(chapter);
+        zip.start_file(format!("OEBPS/{}", chapter_href(index)), deflated)?;
+        zip.write_all(xhtml_document(manuscript, title, &body).as_bytes())?;
+    }
+
+    zip.start_file("OEBPS/nav.xhtml", deflated)?;
+    zip.write_all(nav_document(manuscript).as_bytes())?;
+
+    zip.start_file("OEBPS/toc.ncx", deflated)?;
+    zip.write_all(ncx_document(manuscript).as_bytes())?;
+
+    zip.start_file("OEBPS/content.opf", deflated)?;
+    zip.write_all(package_document(manuscript, cover.as_ref()).as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Check the structure of a generated EPUB without any external tools.
+/// Returns a list of problems; an empty list means the package looks valid.
+pub fn validate(path: &Path) -> Result<Vec<String>> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut problems = Vec::new();
+
+    {
+        let mut first = archive.by_index(0)?;
+        if first.name() != "mimetype" {
+            problems.push("mimetype is not the first entry in the archive".to_string());
+        } else {
+            if first.compression() != CompressionMethod::Stored {
+                problems.push("mimetype entry is compressed".to_string());
+            }
+            let mut content = String::new();
+            first.read_to_string(&mut content)?;
+            if content != MIMETYPE {
+                problems.push(format!("mimetype contains '{}'", content));
+            }
+        }
+    }
+
+    if archive.by_name("META-INF/container.xml").is_err() {
+        problems.push("META-INF/container.xml is missing".to_string());
+    }
+
+    let opf = match read_entry(&mut archive, "OEBPS/content.opf") {
+        Some(opf) => opf,
+        None => {
+            problems.push("OEBPS/content.opf is missing".to_string());
+            return Ok(problems);
+        }
+    };
+
+    for href in attribute_values(&opf, "href") {
+        if archive.by_name(&format!("OEBPS/{}", href)).is_err() {
+            problems.push(format!("manifest item {} is missing", href));
+        }
+    }
+
+    let markup_files: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            name.ends_with(".xhtml")
+                || name.ends_with(".opf")
+                || name.ends_with(".ncx")
+                || name.ends_with(".xml")
+        })
+        .map(|name| name.to_string())
+        .collect();
+
+    for name in markup_files {
+        if let Some(content) = read_entry(&mut archive, &name) {
+            if let Err(e) = check_well_formed(&content) {
+                problems.push(format!("{} is not well-formed: {}", name, e));
+            }
+        }
+    }
+
+    Ok(problems)
+}
+
+fn chapter_href(index: usize) -> String {
+    format!("chapter-{:03}.xhtml", index + 1)
+}
+
+fn part_href(number: usize) -> String {
+    format!("part-{:02}.xhtml", number)
+}
+
+fn image_media_type(path: &Path) -> Result<&'static str> {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("jpg") | Some("jpeg") => Ok("image/jpeg"),
+        Some("png") => Ok("image/png"),
+        Some("gif") => Ok("image/gif"),
+        _ => Err(anyhow!(
+            "Unsupported cover image format: {} (use JPEG, PNG or GIF)",
+            path.display()
+        )),
+    }
+}
+
+/// The `YYYY-MM-DD` date for `<dc:date>`, or None when the created date is
+/// missing or not a date
+fn publication_date(created: &str) -> Option<String> {
+    let date = chrono::NaiveDate::parse_from_str(created.get(..10)?, "%Y-%m-%d").ok()?;
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+fn identifier(manuscript: &Manuscript) -> String {
+    match &manuscript.isbn {
+        Some(isbn) => {
+            let digits: String = isbn
+                .chars()
+                .filter(|c| c.is_ascii_digit() || *c == 'X')
+                .collect();
+            format!("urn:isbn:{}", digits)
+        }
+        None => format!("urn:writers:{}:{}", manuscript.slug(), manuscript.created),
+    }
+}
+
+fn xhtml_document(manuscript: &Manuscript, title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" xml:lang="{lang}" lang="{lang}">
+<head>
+<title>{title}</title>
+<link rel="stylesheet" type="text/css" href="style.css"/>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        lang = escape(&manuscript.language),
+        title = escape(title),
+        body = body
+    )
+}
+
+fn cover_page(manuscript: &Manuscript, image_href: &str) -> String {
+    let body = format!(
+        "<section epub:type=\"cover\" class=\"cover\">\n<img src=\"{}\" alt=\"{}\"/>\n</section>",
+        image_href,
+        escape(&manuscript.title)
+    );
+    xhtml_document(manuscript, "Cover", &body)
+}
+
+fn title_page(manuscript: &Manuscript) -> String {
+    let body = format!(
+        "<section epub:type=\"titlepage\" class=\"title-page\">\n<h1>{}</h1>\n<p class=\"author\">{}</p>\n</section>",
+        escape(&manuscript.title),
+        escape(&manuscript.author)
+    );
+    xhtml_document(manuscript, &manuscript.title, &body)
+}
+
+fn nav_document(manuscript: &Manuscript) -> String {
+    let mut items = String::new();
+    let mut part_number = 0;
+    let mut in_part = false;
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        // Chapters nest under their part's entry
+        let opening = manuscript.part_opening(index);
+        if in_part && (opening.is_some() || chapter.part.is_none()) {
+            items.push_str("</ol>\n</li>\n");
+            in_part = false;
+        }
+        if let Some(part) = opening {
+            part_number += 1;
+            items.push_str(&format!(
+                "<li><a href=\"{}\">{}</a>\n<ol>\n",
+                part_href(part_number),
+                escape(part)
+            ));
+            in_part = true;
+        }
+
+        items.push_str(&format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            chapter_href(index),
+            escape(manuscript.chapter_label(chapter))
+        ));
+    }
+    if in_part {
+        items.push_str("</ol>\n</li>\n");
+    }
+
+    let mut body = format!(
+        "<nav epub:type=\"toc\" id=\"toc\">\n<h1>Contents</h1>\n<ol>\n{}</ol>\n</nav>\n",
+        items
+    );
+    body.push_str("<nav epub:type=\"landmarks\" hidden=\"hidden\">\n<ol>\n");
+    body.push_str("<li><a epub:type=\"titlepage\" href=\"title.xhtml\">Title Page</a></li>\n");
+    body.push_str(&format!(
+        "<li><a epub:type=\"bodymatter\" href=\"{}\">Start of Content</a></li>\n",
+        chapter_href(0)
+    ));
+    body.push_str("</ol>\n</nav>");
+    xhtml_document(manuscript, "Contents", &body)
+}
+
+fn ncx_document(manuscript: &Manuscript) -> String {
+    let mut nav_points = String::new();
+    let mut play_order = 0;
+    let mut part_number = 0;
+    let mut in_part = false;
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        let opening = manuscript.part_opening(index);
+        if in_part && (opening.is_some() || chapter.part.is_none()) {
+            nav_points.push_str("    </navPoint>\n");
+            in_part = false;
+        }
+        if let Some(part) = opening {
+            play_order += 1;
+            part_number += 1;
+            nav_points.push_str(&format!(
+                "    <navPoint id=\"navpoint-{n}\" playOrder=\"{n}\">\n      <navLabel><text>{title}</text></navLabel>\n      <content src=\"{href}\"/>\n",
+                n = play_order,
+                title = escape(part),
+                href = part_href(part_number)
+            ));
+            in_part = true;
+        }
+
+        play_order += 1;
+        nav_points.push_str(&format!(
+            "    <navPoint id=\"navpoint-{n}\" playOrder=\"{n}\">\n      <navLabel><text>{title}</text></navLabel>\n      <content src=\"{href}\"/>\n    </navPoint>\n",
+            n = play_order,
+            title = escape(manuscript.chapter_label(chapter)),
+            href = chapter_href(index)
+        ));
+    }
+    if in_part {
+        nav_points.push_str("    </navPoint>\n");
+    }
+    let depth = if part_number > 0 { 2 } else { 1 };
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <head>
+    <meta name="dtb:uid" content="{uid}"/>
+    <meta name="dtb:depth" content="{depth}"/>
+    <meta name="dtb:totalPageCount" content="0"/>
+    <meta name="dtb:maxPageNumber" content="0"/>
+  </head>
+  <docTitle><text>{title}</text></docTitle>
+  <docAuthor><text>{author}</text></docAuthor>
+  <navMap>
+{nav_points}  </navMap>
+</ncx>
+"#,
+        uid = escape(&identifier(manuscript)),
+        title = escape(&manuscript.title),
+        author = escape(&manuscript.author),
+        depth = depth,
+        nav_points = nav_points
+    )
+}
+
+fn package_document(manuscript: &Manuscript, cover: Option<&(String, &str)>) -> String {
+    let modified = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+
+    let mut metadata = String::new();
+    metadata.push_str(&format!(
+        "    <dc:identifier id=\"book-id\">{}</dc:identifier>\n",
+        escape(&identifier(manuscript))
+    ));
+    metadata.push_str(&format!(
+        "    <dc:title>{}</dc:title>\n",
+        escape(&manuscript.title)
+    ));
+    metadata.push_str(&format!(
+        "    <dc:creator id=\"author\">{}</dc:creator>\n",
+        escape(&manuscript.author)
+    ));
+    metadata.push_str(
+        "    <meta refines=\"#author\" property=\"role\" scheme=\"marc:relators\">aut</meta>\n",
+    );
+    metadata.push_str(&format!(
+        "    <dc:language>{}</dc:language>\n",
+        escape(&manuscript.language)
+    ));
+    if let Some(date) = publication_date(&manuscript.created) {
+        metadata.push_str(&format!("    <dc:date>{}</dc:date>\n", date));
+    }
+    metadata.push_str(&format!(
+        "    <meta property=\"dcterms:modified\">{}</meta>\n",
+        modified
+    ));
+
+    let mut manifest = String::new();
+    manifest.push_str("    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n");
+    manifest.push_str(
+        "    <item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>\n",
+    );
+    manifest.push_str("    <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\n");
+    manifest.push_str(
+        "    <item id=\"title\" href=\"title.xhtml\" media-type=\"application/xhtml+xml\"/>\n",
+    );
+    let mut spine = String::new();
+
+    if let Some((href, media_type)) = cover {
+        metadata.push_str("    <meta name=\"cover\" content=\"cover-image\"/>\n");
+        manifest.push_str(&format!(
+            "    <item id=\"cover-image\" href=\"{}\" media-type=\"{}\" properties=\"cover-image\"/>\n",
+            href, media_type
+        ));
+        manifest.push_str(
+            "    <item id=\"cover\" href=\"cover.xhtml\" media-type=\"application/xhtml+xml\"/>\n",
+        );
+        spine.push_str("    <itemref idref=\"cover\" linear=\"no\"/>\n");
+    }
+
+    spine.push_str("    <itemref idref=\"title\"/>\n");
+    spine.push_str("    <itemref idref=\"nav\"/>\n");
+
+    let mut part_number = 0;
+    for index in 0..manuscript.chapters.len() {
+        if manuscript.part_opening(index).is_some() {
+            part_number += 1;
+            manifest.push_str(&format!(
+                "    <item id=\"part-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+                part_number,
+                part_href(part_number)
+            ));
+            spine.push_str(&format!("    <itemref idref=\"part-{}\"/>\n", part_number));
+        }
+        manifest.push_str(&format!(
+            "    <item id=\"chapter-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+            index + 1,
+            chapter_href(index)
+        ));
+        spine.push_str(&format!("    <itemref idref=\"chapter-{}\"/>\n", index + 1));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="{lang}">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+{metadata}  </metadata>
+  <manifest>
+{manifest}  </manifest>
+  <spine toc="ncx">
+{spine}  </spine>
+</package>
+"#,
+        lang = escape(&manuscript.language),
+        metadata = metadata,
+        manifest = manifest,
+        spine = spine
+    )
+}
+
+fn read_entry<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+/// Collect every value of `name="..."` in a markup document
+fn attribute_values(markup: &str, name: &str) -> Vec<String> {
+    let needle = format!(" {}=\"", name);
+    let mut values = Vec::new();
+    let mut rest = markup;
+
+    while let Some(start) = rest.find(&needle) {
+        rest = &rest[start + needle.len()..];
+        if let Some(end) = rest.find('"') {
+            values.push(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+    }
+
+    values
+}
+
+/// Minimal XML well-formedness check: balanced tags and known entities
+fn check_well_formed(markup: &str) -> std::result::Result<(), String> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut rest = markup;
+
+    while let Some(pos) = rest.find(['<', '&']) {
+        if rest[pos..].starts_with('&') {
+            let entity = &rest[pos + 1..];
+            let end = entity
+                .find(';')
+                .ok_or_else(|| "unterminated entity".to_string())?;
+            let name = &entity[..end];
+            let known = matches!(name, "amp" | "lt" | "gt" | "quot" | "apos")
+                || (name.starts_with('#') && name.len() > 1);
+            if !known {
+                return Err(format!("undefined entity &{};", name));
+            }
+            rest = &entity[end + 1..];
+            continue;
+        }
+
+        let tag_start = &rest[pos..];
+        if tag_start.starts_with("<!--") {
+            let end = tag_start
+                .find("-->")
+                .ok_or_else(|| "unterminated comment".to_string())?;
+            rest = &tag_start[end + 3..];
+            continue;
+        }
+
+        let end = tag_start
+            .find('>')
+            .ok_or_else(|| "unterminated tag".to_string())?;
+        let tag = &tag_start[1..end];
+        rest = &tag_start[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+
+        if let Some(closing) = tag.strip_prefix('/') {
+            let closing = closing.trim();
+            match stack.pop() {
+                Some(open) if open == closing => {}
+                Some(open) => return Err(format!("</{}> closes <{}>", closing, open)),
+                None => return Err(format!("unexpected </{}>", closing)),
+            }
+        } else {
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            stack.push(name.to_string());
+        }
+    }
+
+    match stack.pop() {
+        Some(open) => Err(format!("<{}> is never closed", open)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::manuscript::Chapter;
+
+    fn manuscript(created: &str, body: &str) -> Manuscript {
+        Manuscript {
+            title: "The Book".to_string(),
+            author: "A & B".to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: created.to_string(),
+            chapters: vec![Chapter {
+                title: "One".to_string(),
+                part: None,
+                body: body.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn written_epub_validates_with_raw_html() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.epub");
+        let book = manuscript(
+            "2024-03-01",
+            "Line one<br>\nline two.\n\n<div>\n\nOpen block\n",
+        );
+
+        write(&book, &path).unwrap();
+
+        assert_eq!(validate(&path).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn date_is_omitted_unless_valid() {
+        assert_eq!(
+            publication_date("2024-03-01"),
+            Some("2024-03-01".to_string())
+        );
+        assert_eq!(
+            publication_date("2024-03-01T10:00:00Z"),
+            Some("2024-03-01".to_string())
+        );
+        assert_eq!(publication_date(""), None);
+        assert_eq!(publication_date("someday!!"), None);
+
+        assert!(package_document(&manuscript("", ""), None).contains("<dc:title>"));
+        assert!(!package_document(&manuscript("", ""), None).contains("<dc:date>"));
+        assert!(package_document(&manuscript("2024-03-01", ""), None)
+            .contains("<dc:date>2024-03-01</dc:date>"));
+    }
+
+    #[test]
+    fn cover_formats() {
+        assert_eq!(image_media_type(Path::new("c.JPG")).unwrap(), "image/jpeg");
+        assert_eq!(image_media_type(Path::new("c.png")).unwrap(), "image/png");
+        assert_eq!(image_media_type(Path::new("c.gif")).unwrap(), "image/gif");
+        assert!(image_media_type(Path::new("c.webp"))
+            .is_err_and(|e| e.to_string().contains("JPEG, PNG or GIF")));
+    }
+
+    #[test]
+    fn well_formed_markup() {
+        assert!(check_well_formed("<p>a &amp; b<br/></p><!-- <x> -->").is_ok());
+        assert!(check_well_formed("<p>a<br></p>").is_err());
+        assert!(check_well_formed("<p>a &nbsp; b</p>").is_err());
+        assert!(check_well_formed("<p><em>a</p></em>").is_err());
+        assert!(check_well_formed("<p>a").is_err());
+    }
+
+    #[test]
+    fn attribute_values_in_order() {
+        assert_eq!(
+            attribute_values(r#"<a href="x.xhtml"/><b id="c" href="y.css"/>"#, "href"),
+            vec!["x.xhtml", "y.css"]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use pulldown_cmark::{html, Event, Options, Parser};
+
+use super::Manuscript;
+
+const STYLESHEET: &str = r#"
+body { font-family: Georgia, "Times New Roman", serif; line-height: 1.6; max-width: 40em; margin: 0 auto; padding: 2em; color: #222; }
+.title-page { text-align: center; margin: 30vh 0; page-break-after: always; }
+.title-page h1 { font-size: 2.5em; margin-bottom: 0.5em; }
+.title-page .author { font-size: 1.3em; font-style: italic; }
+.part { page-break-before: always; page-break-after: always; text-align: center; margin: 30vh 0; }
+.chapter { page-break-before: always; margin-top: 4em; }
+.chapter h1 { text-align: center; margin-bottom: 2em; }
+p { text-indent: 1.5em; margin: 0; }
+h1 + p, h2 + p, h3 + p, hr + p { text-indent: 0; }
+hr { border: none; text-align: center; margin: 1.5em 0; }
+hr::after { content: "* * *"; }
+"#;
+
+/// Render the manuscript as a standalone HTML document
+pub fn render(manuscript: &Manuscript) -> String {
+    let mut output = String::new();
+
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n");
+    output.push_str("<meta charset=\"utf-8\">\n");
+    output.push_str(&format!(
+        "<meta name=\"author\" content=\"{}\">\n",
+        escape(&manuscript.author)
+    ));
+    output.push_str(&format!("<title>{}</title>\n", escape(&manuscript.title)));
+    output.push_str(&format!("<style>{}</style>\n", STYLESHEET));
+    output.push_str("</head>\n<body>\n");
+
+    // Title page
+    output.push_str("<section class=\"title-page\">\n");
+    output.push_str(&format!("<h1>{}</h1>\n", escape(&manuscript.title)));
+    output.push_str(&format!(
+        "<p class=\"author\">by {}</p>\n",
+        escape(&manuscript.author)
+    ));
+    output.push_str("</section>\n");
+
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        if let Some(part) = manuscript.part_opening(index) {
+            output.push_str(&format!(
+                "<section class=\"part\">\n<h1>{}</h1>\n</section>\n",
+                escape(part)
+            ));
+        }
+        output.push_str("<section class=\"chapter\">\n");
+        if !chapter.title.is_empty() {
+            output.push_str(&format!("<h1>{}</h1>\n", escape(&chapter.title)));
+        }
+        output.push_str(&markdown_to_html(&chapter.body));
+        output.push_str("</section>\n");
+    }
+
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+/// Convert a markdown fragment to HTML
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new_ext(markdown, options()));
+    output
+}
+
+/// Convert a markdown fragment to XHTML. Raw HTML in the source is shown as
+/// text, since tags like `<br>` aren't well-formed XML and break EPUB readers.
+pub fn markdown_to_xhtml(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, options()).map(|event| match event {
+        Event::Html(markup) => Event::Text(markup),
+        event => event,
+    });
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+fn options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    options
+}
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn raw_html_passes_through_html_but_not_xhtml() {
+        assert_eq!(markdown_to_html("a<br>b"), "<p>a<br>b</p>\n");
+        assert_eq!(markdown_to_xhtml("a<br>b"), "<p>a&lt;br&gt;b</p>\n");
+        assert_eq!(markdown_to_xhtml("a  \nb"), "<p>a<br />\nb</p>\n");
+    }
+
+    #[test]
+    fn smart_punctuation() {
+        assert_eq!(markdown_to_html("\"Hi\" -- ok"), "<p>“Hi” – ok</p>\n");
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tokio::fs as async_fs;
+
+use crate::utils::{frontmatter, slugify, title_from_name, Part, ProjectConfig};
+
+/// A single chapter ready to be rendered by one of the export formats
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub title: String,
+    /// Title of the part or act this chapter belongs to
+    pub part: Option<String>,
+    /// Markdown body with the leading chapter heading removed
+    pub body: String,
+}
+
+/// The compiled manuscript: title page information plus chapters in reading order
+#[derive(Debug, Clone)]
+pub struct Manuscript {
+    pub title: String,
+    pub author: String,
+    pub language: String,
+    pub isbn: Option<String>,
+    pub cover: Option<PathBuf>,
+    /// Creation date of the project (YYYY-MM-DD)
+    pub created: String,
+    pub chapters: Vec<Chapter>,
+}
+
+impl Manuscript {
+    /// Read the given parts (already in order) into a manuscript
+    pub async fn compile(config: &ProjectConfig, parts: &[Part]) -> Result<Self> {
+        let mut chapters = Vec::new();
+
+        for part in parts {
+            for file in &part.chapters {
+                let content = async_fs::read_to_string(file).await?;
+                let mut chapter = Chapter::from_markdown(file, &content);
+                chapter.part = part.title.clone();
+                chapters.push(chapter);
+            }
+        }
+
+        Ok(Self {
+            title: config.name.clone(),
+            author: config.author.clone(),
+            language: config.language.clone().unwrap_or_else(|| "en".to_string()),
+            isbn: config.isbn.clone(),
+            cover: config.cover.as_ref().map(PathBuf::from),
+            created: config.created.chars().take(10).collect(),
+            chapters,
+        })
+    }
+
+    /// Read a single story as an untitled-chapter manuscript named after the story
+    pub async fn compile_story(config: &ProjectConfig, file: &Path) -> Result<Self> {
+        let part = Part {
+            title: None,
+            chapters: vec![file.to_path_buf()],
+        };
+        let mut manuscript = Self::compile(config, &[part]).await?;
+
+        if let Some(chapter) = manuscript.chapters.first_mut() {
+            manuscript.title = std::mem::take(&mut chapter.title);
+        }
+
+        Ok(manuscript)
+    }
+
+    /// Label for a chapter in headings and tables of contents. Single stories
+    /// have one untitled chapter, which is labelled with the manuscript title.
+    pub fn chapter_label<'a>(&'a self, chapter: &'a Chapter) -> &'a str {
+        if chapter.title.is_empty() {
+            &self.title
+        } else {
+            &chapter.title
+        }
+    }
+
+    /// The part title to print before a chapter, if the chapter opens a new part
+    pub fn part_opening(&self, index: usize) -> Option<&str> {
+        let part = self.chapters.get(index)?.part.as_deref()?;
+        let previous = index
+            .checked_sub(1)
+            .and_then(|i| self.chapters.get(i))
+            .and_then(|chapter| chapter.part.as_deref());
+
+        if previous == Some(part) {
+            None
+        } else {
+            Some(part)
+        }
+    }
+
+    /// File name stem used for default export paths
+    pub fn slug(&self) -> String {
+        let slug = slugify(&self.title);
+
+        if slug.is_empty() {
+            "manuscript".to_string()
+        } else {
+            slug
+        }
+    }
+}
+
+impl Chapter {
+    /// Build a chapter from its markdown source. The title comes from the
+    /// frontmatter, then the first level-one heading, then the file name.
+    pub fn from_markdown(path: &Path, content: &str) -> Self {
+        let (meta, content) = frontmatter::parse(content);
+        let mut lines = content.lines().skip_while(|line| line.trim().is_empty());
+
+        let (title, body) = match lines.next() {
+            Some(first) if first.starts_with("# ") => {
+                let body = lines.collect::<Vec<_>>().join("\n");
+                (first[2..].trim().to_string(), body)
+            }
+            _ => (title_from_path(path), content.to_string()),
+        };
+
+        Self {
+            title: meta.title.unwrap_or(title),
+            part: meta.part,
+            body: body.trim().to_string(),
+        }
+    }
+}
+
+/// Turn a file name like `chapter-01` into `Chapter 01`
+fn title_from_path(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Untitled");
+
+    title_from_name(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(title: &str, part: Option<&str>) -> Chapter {
+        Chapter {
+            title: title.to_string(),
+            part: part.map(str::to_string),
+            body: String::new(),
+        }
+    }
+
+    fn manuscript(title: &str, chapters: Vec<Chapter>) -> Manuscript {
+        Manuscript {
+            title: title.to_string(),
+            author: "A".to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: String::new(),
+            chapters,
+        }
+    }
+
+    #[test]
+    fn title_from_heading_frontmatter_or_file_name() {
+        let from_heading =
+            Chapter::from_markdown(Path::new("c.md"), "\n# The Storm\n\nRain fell.\n");
+        assert_eq!(from_heading.title, "The Storm");
+        assert_eq!(from_heading.body, "Rain fell.");
+
+        let from_meta = Chapter::from_markdown(
+            Path::new("c.md"),
+            "---\ntitle: Calm\npart: One\n---\n# The Storm\nRain fell.\n",
+        );
+        assert_eq!(from_meta.title, "Calm");
+        assert_eq!(from_meta.part.as_deref(), Some("One"));
+        assert_eq!(from_meta.body, "Rain fell.");
+
+        let from_name = Chapter::from_markdown(Path::new("chapter-01.md"), "Rain fell.\n");
+        assert_eq!(from_name.title, "Chapter 01");
+        assert_eq!(from_name.body, "Rain fell.");
+    }
+
+    #[test]
+    fn parts_open_once() {
+        let book = manuscript(
+            "Book",
+            vec![
+                chapter("1", Some("One")),
+                chapter("2", Some("One")),
+                chapter("3", Some("Two")),
+                chapter("4", None),
+            ],
+        );
+
+        assert_eq!(book.part_opening(0), Some("One"));
+        assert_eq!(book.part_opening(1), None);
+        assert_eq!(book.part_opening(2), Some("Two"));
+        assert_eq!(book.part_opening(3), None);
+        assert_eq!(book.part_opening(9), None);
+    }
+
+    #[test]
+    fn labels_and_slugs() {
+        let book = manuscript("The Long Night", vec![chapter("", None)]);
+        assert_eq!(book.chapter_label(&book.chapters[0]), "The Long Night");
+        assert_eq!(book.slug(), "the-long-night");
+        assert_eq!(manuscript("!!!", Vec::new()).slug(), "manuscript");
+    }
+}
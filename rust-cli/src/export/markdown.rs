@@ -0,0 +1,81 @@
+use super::Manuscript;
+
+/// Render the manuscript as a single Markdown document
+pub fn render(manuscript: &Manuscript) -> String {
+    let mut output = String::new();
+
+    // Title page
+    output.push_str(&format!("# {}\n\n", manuscript.title));
+    output.push_str(&format!("*by {}*\n", manuscript.author));
+
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        if let Some(part) = manuscript.part_opening(index) {
+            output.push_str(&format!("\n---\n\n# {}\n", part));
+        }
+        output.push_str("\n---\n\n");
+        if !chapter.title.is_empty() {
+            output.push_str(&format!("## {}\n\n", chapter.title));
+        }
+        if !chapter.body.is_empty() {
+            output.push_str(&demote_headings(&chapter.body));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// Push headings inside a chapter one level down so they nest under the chapter heading
+fn demote_headings(body: &str) -> String {
+    let mut in_code_block = false;
+
+    body.lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if !in_code_block && line.starts_with('#') && !line.starts_with("######") {
+                format!("#{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::manuscript::Chapter;
+
+    #[test]
+    fn headings_nest_under_the_chapter() {
+        assert_eq!(
+            demote_headings("# Scene\n```\n# code\n```\n###### Deep"),
+            "## Scene\n```\n# code\n```\n###### Deep"
+        );
+    }
+
+    #[test]
+    fn renders_title_parts_and_chapters() {
+        let manuscript = Manuscript {
+            title: "Book".to_string(),
+            author: "A".to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: String::new(),
+            chapters: vec![Chapter {
+                title: "One".to_string(),
+                part: Some("Part I".to_string()),
+                body: "# Scene\nText.".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            render(&manuscript),
+            "# Book\n\n*by A*\n\n---\n\n# Part I\n\n---\n\n## One\n\n## Scene\nText.\n"
+        );
+    }
+}
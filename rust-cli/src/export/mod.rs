@@ -0,0 +1,9 @@
+pub mod docx;
+pub mod epub;
+pub mod html;
+pub mod manuscript;
+pub mod markdown;
+pub mod pdf;
+pub mod selection;
+
+pub use manuscript::Manuscript;
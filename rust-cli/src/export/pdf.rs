@@ -0,0 +1,852 @@
+use anyhow::{anyhow, Result};
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str, TextStr};
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::path::Path;
+
+use super::Manuscript;
+use crate::utils::ProjectSettings;
+
+const POINTS_PER_INCH: f32 = 72.0;
+const LINE_SPACING: f32 = 1.45;
+
+/// One of the standard PDF fonts, which every viewer has built in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FontFamily {
+    Times,
+    Helvetica,
+    Courier,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Regular,
+    Italic,
+    Bold,
+}
+
+impl Style {
+    fn resource_name(self) -> Name<'static> {
+        match self {
+            Style::Regular => Name(b"F1"),
+            Style::Italic => Name(b"F2"),
+            Style::Bold => Name(b"F3"),
+        }
+    }
+}
+
+/// Page and type settings for the PDF typesetter
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    /// Page width in points
+    pub width: f32,
+    /// Page height in points
+    pub height: f32,
+    pub family: FontFamily,
+    pub font_size: f32,
+}
+
+impl PdfOptions {
+    pub fn from_settings(settings: Option<&ProjectSettings>) -> Result<Self> {
+        let trim = settings
+            .and_then(|s| s.pdf_trim_size.clone())
+            .unwrap_or_else(|| "6x9".to_string());
+        let (width, height) = parse_trim_size(&trim)?;
+
+        let family = match settings
+            .and_then(|s| s.pdf_font.clone())
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "helvetica" | "sans" => FontFamily::Helvetica,
+            "courier" | "mono" => FontFamily::Courier,
+            _ => FontFamily::Times,
+        };
+
+        let font_size = settings
+            .and_then(|s| s.pdf_font_size)
+            .unwrap_or(11.0)
+            .clamp(6.0, 24.0);
+
+        Ok(Self {
+            width,
+            height,
+            family,
+            font_size,
+        })
+    }
+
+    fn margin(&self) -> f32 {
+        (self.width / 8.0).clamp(45.0, POINTS_PER_INCH)
+    }
+
+    fn line_height(&self) -> f32 {
+        self.font_size * LINE_SPACING
+    }
+}
+
+/// Parse a trim size such as "6x9" or "5.5x8.5" (inches), or a named paper size
+fn parse_trim_size(trim: &str) -> Result<(f32, f32)> {
+    match trim.trim().to_lowercase().as_str() {
+        "letter" => Ok((8.5 * POINTS_PER_INCH, 11.0 * POINTS_PER_INCH)),
+        "a4" => Ok((595.0, 842.0)),
+        "a5" => Ok((420.0, 595.0)),
+        other => {
+            let parsed = other.split_once('x').and_then(|(w, h)| {
+                Some((w.trim().parse::<f32>().ok()?, h.trim().parse::<f32>().ok()?))
+            });
+
+            match parsed {
+                Some((w, h)) if w >= 3.0 && h >= 4.0 => {
+                    Ok((w * POINTS_PER_INCH, h * POINTS_PER_INCH))
+                }
+                _ => Err(anyhow!(
+                    "Invalid pdf_trim_size '{}' (use e.g. \"6x9\", \"letter\", \"a4\" or \"a5\")",
+                    trim
+                )),
+            }
+        }
+    }
+}
+
+impl FontFamily {
+    fn base_font(self, style: Style) -> Name<'static> {
+        Name(match (self, style) {
+            (FontFamily::Times, Style::Regular) => b"Times-Roman",
+            (FontFamily::Times, Style::Italic) => b"Times-Italic",
+            (FontFamily::Times, Style::Bold) => b"Times-Bold",
+            (FontFamily::Helvetica, Style::Regular) => b"Helvetica",
+            (FontFamily::Helvetica, Style::Italic) => b"Helvetica-Oblique",
+            (FontFamily::Helvetica, Style::Bold) => b"Helvetica-Bold",
+            (FontFamily::Courier, Style::Regular) => b"Courier",
+            (FontFamily::Courier, Style::Italic) => b"Courier-Oblique",
+            (FontFamily::Courier, Style::Bold) => b"Courier-Bold",
+        })
+    }
+
+    /// Advance width of a WinAnsi-encoded byte in thousandths of an em
+    fn width(self, byte: u8) -> u16 {
+        match self {
+            FontFamily::Courier => 600,
+            FontFamily::Times => match byte {
+                32..=126 => TIMES_WIDTHS[(byte - 32) as usize],
+                0x85 | 0x97 => 1000,
+                0x91 | 0x92 => 333,
+                0x93 | 0x94 => 444,
+                0x95 => 350,
+                0x96 => 500,
+                _ => 500,
+            },
+            FontFamily::Helvetica => match byte {
+                32..=126 => HELVETICA_WIDTHS[(byte - 32) as usize],
+                0x85 | 0x97 => 1000,
+                0x91 | 0x92 => 222,
+                0x93 | 0x94 => 333,
+                0x95 => 350,
+                0x96 => 556,
+                _ => 556,
+            },
+        }
+    }
+}
+
+/// Widths of ASCII 32..=126 from the Adobe core font metrics
+#[rustfmt::skip]
+const TIMES_WIDTHS: [u16; 95] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278,
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444,
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722,
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500,
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500,
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Map a character to WinAnsiEncoding, replacing anything unsupported
+fn encode_char(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{2026}' => 0x85,
+        _ => b'?',
+    }
+}
+
+fn encode(text: &str) -> Vec<u8> {
+    text.chars().map(encode_char).collect()
+}
+
+/// A run of text without spaces, in a single style
+#[derive(Debug, Clone)]
+struct Fragment {
+    text: Vec<u8>,
+    style: Style,
+    space_before: bool,
+}
+
+#[derive(Debug)]
+enum Block {
+    Paragraph {
+        fragments: Vec<Fragment>,
+        indent: bool,
+        inset: f32,
+    },
+    Heading(Vec<Fragment>),
+    SceneBreak,
+}
+
+struct Line {
+    fragments: Vec<Fragment>,
+    width: f32,
+    spaces: usize,
+}
+
+/// Split markdown text into fragments, tracking whether each one follows a space
+fn push_text(fragments: &mut Vec<Fragment>, text: &str, style: Style, pending_space: &mut bool) {
+    let mut current = String::new();
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !current.is_empty() {
+                fragments.push(Fragment {
+                    text: encode(&current),
+                    style,
+                    space_before: *pending_space,
+                });
+                current.clear();
+            }
+            *pending_space = true;
+        } else {
+            if current.is_empty() && fragments.is_empty() {
+                *pending_space = false;
+            }
+            current.push(c);
+        }
+    }
+
+    if !current.is_empty() {
+        fragments.push(Fragment {
+            text: encode(&current),
+            style,
+            space_before: *pending_space,
+        });
+        *pending_space = false;
+    }
+}
+
+/// Turn a chapter's markdown into typesetting blocks
+fn parse_blocks(markdown: &str, font_size: f32) -> Vec<Block> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+
+    let mut blocks = Vec::new();
+    let mut fragments = Vec::new();
+    let mut pending_space = false;
+    let mut italic = 0;
+    let mut bold = 0;
+    let mut quote_depth = 0;
+    let mut list_depth = 0;
+    let mut indent_next = false;
+
+    for event in Parser::new_ext(markdown, options) {
+        let style = if bold > 0 {
+            Style::Bold
+        } else if italic > 0 {
+            Style::Italic
+        } else {
+            Style::Regular
+        };
+
+        match event {
+            Event::Start(Tag::Emphasis) => italic += 1,
+            Event::End(Tag::Emphasis) => italic -= 1,
+            Event::Start(Tag::Strong) => bold += 1,
+            Event::End(Tag::Strong) => bold -= 1,
+            Event::Start(Tag::BlockQuote) => quote_depth += 1,
+            Event::End(Tag::BlockQuote) => quote_depth -= 1,
+            Event::Start(Tag::List(_)) => list_depth += 1,
+            Event::End(Tag::List(_)) => list_depth -= 1,
+            Event::Start(Tag::Item) => {
+                push_text(
+                    &mut fragments,
+                    "\u{2022} ",
+                    Style::Regular,
+                    &mut pending_space,
+                );
+            }
+            Event::End(Tag::Heading(..)) => {
+                if fragments.is_empty() {
+                    // An empty heading (a lone `#`) is a scene break
+                    blocks.push(Block::SceneBreak);
+                } else {
+                    blocks.push(Block::Heading(std::mem::take(&mut fragments)));
+                }
+                indent_next = false;
+            }
+            Event::End(Tag::Paragraph) | Event::End(Tag::Item) => {
+                if fragments.is_empty() {
+                    continue;
+                }
+                let inset = (quote_depth + list_depth) as f32 * font_size * 2.0;
+                blocks.push(Block::Paragraph {
+                    fragments: std::mem::take(&mut fragments),
+                    indent: indent_next && inset == 0.0,
+                    inset,
+                });
+                indent_next = inset == 0.0;
+            }
+            Event::Text(text) | Event::Code(text) => {
+                push_text(&mut fragments, &text, style, &mut pending_space);
+            }
+            Event::SoftBreak | Event::HardBreak => pending_space = true,
+            Event::Rule => {
+                blocks.push(Block::SceneBreak);
+                indent_next = false;
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+struct Page {
+    content: Content,
+    /// Chapter openers and the title page have no running header
+    running_header: bool,
+}
+
+/// Lays out text onto pages, tracking the vertical position on the current page
+struct Typesetter<'a> {
+    options: &'a PdfOptions,
+    title: String,
+    author: String,
+    pages: Vec<Page>,
+    y: f32,
+}
+
+impl<'a> Typesetter<'a> {
+    fn new(options: &'a PdfOptions, manuscript: &Manuscript) -> Self {
+        Self {
+            options,
+            title: manuscript.title.clone(),
+            author: manuscript.author.clone(),
+            pages: Vec::new(),
+            y: 0.0,
+        }
+    }
+
+    fn top(&self) -> f32 {
+        self.options.height - self.options.margin() - self.options.font_size
+    }
+
+    fn bottom(&self) -> f32 {
+        self.options.margin() + self.options.font_size
+    }
+
+    fn text_width(&self) -> f32 {
+        self.options.width - 2.0 * self.options.margin()
+    }
+
+    fn measure(&self, text: &[u8], style: Style, size: f32) -> f32 {
+        let units: u32 = text
+            .iter()
+            .map(|&b| self.options.family.width(b) as u32)
+            .sum();
+        let width = units as f32 * size / 1000.0;
+        // Bold faces run slightly wider than the regular metrics
+        if style == Style::Bold && self.options.family != FontFamily::Courier {
+            width * 1.05
+        } else {
+            width
+        }
+    }
+
+    fn space_width(&self, size: f32) -> f32 {
+        self.measure(b" ", Style::Regular, size)
+    }
+
+    fn new_page(&mut self, running_header: bool) {
+        self.pages.push(Page {
+            content: Content::new(),
+            running_header,
+        });
+        self.y = self.top();
+    }
+
+    fn content(&mut self) -> &mut Content {
+        if self.pages.is_empty() {
+            self.new_page(true);
+        }
+        &mut self.pages.last_mut().expect("page exists").content
+    }
+
+    /// Move down by `height`, starting a new page when the bottom margin is reached
+    fn advance(&mut self, height: f32) {
+        if self.y - height < self.bottom() || self.pages.is_empty() {
+            self.new_page(true);
+        }
+        self.y -= height;
+    }
+
+    fn draw(&mut self, x: f32, y: f32, fragments: &[Fragment], size: f32, word_spacing: f32) {
+        let content = self.content();
+        content.begin_text();
+        content.set_word_spacing(word_spacing);
+        content.set_text_matrix([1.0, 0.0, 0.0, 1.0, x, y]);
+
+        let mut current_style = None;
+        for (index, fragment) in fragments.iter().enumerate() {
+            if current_style != Some(fragment.style) {
+                content.set_font(fragment.style.resource_name(), size);
+                current_style = Some(fragment.style);
+            }
+
+            let mut bytes = Vec::with_capacity(fragment.text.len() + 1);
+            if fragment.space_before && index > 0 {
+                bytes.push(b' ');
+            }
+            bytes.extend_from_slice(&fragment.text);
+            content.show(Str(&bytes));
+        }
+
+        content.end_text();
+    }
+
+    fn break_lines(
+        &self,
+        fragments: &[Fragment],
+        first_width: f32,
+        width: f32,
+        size: f32,
+    ) -> Vec<Line> {
+        // Group fragments into words: a word is a run of fragments with no space between
+        let mut words: Vec<Vec<Fragment>> = Vec::new();
+        for fragment in fragments {
+            match words.last_mut() {
+                Some(word) if !fragment.space_before => word.push(fragment.clone()),
+                _ => words.push(vec![fragment.clone()]),
+            }
+        }
+
+        let space = self.space_width(size);
+        let mut lines = Vec::new();
+        let mut current: Vec<Fragment> = Vec::new();
+        let mut current_width = 0.0;
+        let mut spaces = 0;
+        let mut available = first_width;
+
+        for mut word in words {
+            let word_width: f32 = word
+                .iter()
+                .map(|f| self.measure(&f.text, f.style, size))
+                .sum();
+
+            if !current.is_empty() && current_width + space + word_width > available {
+                lines.push(Line {
+                    fragments: std::mem::take(&mut current),
+                    width: current_width,
+                    spaces,
+                });
+                current_width = 0.0;
+                spaces = 0;
+                available = width;
+            }
+
+            if current.is_empty() {
+                word[0].space_before = false;
+            } else {
+                word[0].space_before = true;
+                current_width += space;
+                spaces += 1;
+            }
+
+            current_width += word_width;
+            current.extend(word);
+        }
+
+        if !current.is_empty() {
+            lines.push(Line {
+                fragments: current,
+                width: current_width,
+                spaces,
+            });
+        }
+
+        lines
+    }
+
+    fn paragraph(&mut self, fragments: &[Fragment], indent: bool, inset: f32) {
+        let size = self.options.font_size;
+        let line_height = self.options.line_height();
+        let left = self.options.margin() + inset;
+        let width = self.text_width() - 2.0 * inset;
+        let indent_width = if indent { size * 1.5 } else { 0.0 };
+
+        let lines = self.break_lines(fragments, width - indent_width, width, size);
+        let count = lines.len();
+
+        for (index, line) in lines.into_iter().enumerate() {
+            self.advance(line_height);
+
+            let offset = if index == 0 { indent_width } else { 0.0 };
+            let available = width - offset;
+
+            // Justify every line but the last, unless the gap would be unsightly
+            let word_spacing = if index + 1 < count && line.spaces > 0 {
+                let extra = (available - line.width) / line.spaces as f32;
+                if extra < self.space_width(size) * 2.0 {
+                    extra
+                } else {
+                    0.0
+                }
+            } else {
+                0.0
+            };
+
+            let y = self.y;
+            self.draw(left + offset, y, &line.fragments, size, word_spacing);
+        }
+    }
+
+    fn centered(&mut self, fragments: &[Fragment], size: f32) {
+        let line_height = size * LINE_SPACING;
+        let width = self.text_width();
+        let lines = self.break_lines(fragments, width, width, size);
+
+        for line in lines {
+            self.advance(line_height);
+            let x = self.options.margin() + (width - line.width) / 2.0;
+            let y = self.y;
+            self.draw(x, y, &line.fragments, size, 0.0);
+        }
+    }
+
+    fn centered_text(&mut self, text: &str, style: Style, size: f32) {
+        let mut fragments = Vec::new();
+        let mut pending_space = false;
+        push_text(&mut fragments, text, style, &mut pending_space);
+        self.centered(&fragments, size);
+    }
+
+    fn title_page(&mut self) {
+        let size = self.options.font_size;
+        self.new_page(false);
+        self.y = self.options.height * 0.66;
+
+        let title = self.title.clone();
+        let author = self.author.clone();
+        self.centered_text(&title, Style::Bold, size * 2.0);
+        self.y -= size * 2.0;
+        self.centered_text(&author, Style::Italic, size * 1.3);
+    }
+
+    fn chapter_opener(&mut self, title: &str) {
+        let size = self.options.font_size;
+        self.new_page(false);
+        self.y = self.options.height * 0.66;
+
+        if !title.is_empty() {
+            self.centered_text(title, Style::Bold, size * 1.6);
+        }
+        self.y -= self.options.line_height() * 2.0;
+    }
+
+    /// A part title alone on its page
+    fn part_page(&mut self, title: &str) {
+        let size = self.options.font_size;
+        self.new_page(false);
+        self.y = self.options.height * 0.6;
+        self.centered_text(title, Style::Bold, size * 2.0);
+    }
+
+    fn scene_break(&mut self) {
+        let size = self.options.font_size;
+        self.advance(self.options.line_height() * 0.5);
+        self.centered_text("*   *   *", Style::Regular, size);
+        self.y -= self.options.line_height() * 0.5;
+    }
+
+    fn chapter(&mut self, title: &str, body: &str) {
+        self.chapter_opener(title);
+
+        let size = self.options.font_size;
+        for block in parse_blocks(body, size) {
+            match block {
+                Block::Paragraph {
+                    fragments,
+                    indent,
+                    inset,
+                } => self.paragraph(&fragments, indent, inset),
+                Block::Heading(fragments) => {
+                    self.advance(self.options.line_height() * 0.5);
+                    self.centered(&fragments, size);
+                }
+                Block::SceneBreak => self.scene_break(),
+            }
+        }
+    }
+
+    /// Add running headers and page numbers, then hand back the finished pages
+    fn finish(mut self) -> Vec<Page> {
+        let size = self.options.font_size * 0.85;
+        let margin = self.options.margin();
+        let width = self.options.width;
+        let header_y = self.options.height - margin * 0.6;
+        let folio_y = margin * 0.6;
+
+        let mut pages = std::mem::take(&mut self.pages);
+
+        for (index, page) in pages.iter_mut().enumerate() {
+            let number = index + 1;
+            if number == 1 {
+                continue;
+            }
+
+            if page.running_header {
+                // Author on left-hand (even) pages, title on right-hand (odd) pages
+                let label = if number % 2 == 0 {
+                    &self.author
+                } else {
+                    &self.title
+                };
+                let text = encode(&label.to_uppercase());
+                let x = (width - self.measure(&text, Style::Regular, size)) / 2.0;
+                page.content
+                    .begin_text()
+                    .set_word_spacing(0.0)
+                    .set_font(Style::Regular.resource_name(), size)
+                    .set_text_matrix([1.0, 0.0, 0.0, 1.0, x, header_y])
+                    .show(Str(&text))
+                    .end_text();
+            }
+
+            let folio = number.to_string().into_bytes();
+            let x = (width - self.measure(&folio, Style::Regular, size)) / 2.0;
+            page.content
+                .begin_text()
+                .set_word_spacing(0.0)
+                .set_font(Style::Regular.resource_name(), size)
+                .set_text_matrix([1.0, 0.0, 0.0, 1.0, x, folio_y])
+                .show(Str(&folio))
+                .end_text();
+        }
+
+        pages
+    }
+}
+
+/// Typeset the manuscript and write it as a PDF using the standard PDF fonts
+pub fn write(manuscript: &Manuscript, options: &PdfOptions, path: &Path) -> Result<()> {
+    let mut typesetter = Typesetter::new(options, manuscript);
+    typesetter.title_page();
+    for (index, chapter) in manuscript.chapters.iter().enumerate() {
+        if let Some(part) = manuscript.part_opening(index) {
+            typesetter.part_page(part);
+        }
+        typesetter.chapter(&chapter.title, &chapter.body);
+    }
+    let pages = typesetter.finish();
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let info_id = Ref::new(3);
+    let styles = [Style::Regular, Style::Italic, Style::Bold];
+    let font_ids = [Ref::new(4), Ref::new(5), Ref::new(6)];
+    let mut next_id = 7;
+
+    let mut pdf = Pdf::new();
+    let mut page_ids = Vec::new();
+
+    for page in pages {
+        let page_id = Ref::new(next_id);
+        let content_id = Ref::new(next_id + 1);
+        next_id += 2;
+
+        let mut page_writer = pdf.page(page_id);
+        page_writer
+            .media_box(Rect::new(0.0, 0.0, options.width, options.height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        let mut resources = page_writer.resources();
+        let mut fonts = resources.fonts();
+        for (style, font_id) in styles.iter().zip(font_ids) {
+            fonts.pair(style.resource_name(), font_id);
+        }
+        drop(fonts);
+        drop(resources);
+        drop(page_writer);
+
+        pdf.stream(content_id, &page.content.finish());
+        page_ids.push(page_id);
+    }
+
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+
+    for (style, font_id) in styles.iter().zip(font_ids) {
+        pdf.type1_font(font_id)
+            .base_font(options.family.base_font(*style))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    pdf.document_info(info_id)
+        .title(TextStr(&manuscript.title))
+        .author(TextStr(&manuscript.author))
+        .creator(TextStr("Writers CLI"));
+
+    std::fs::write(path, pdf.finish())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> PdfOptions {
+        PdfOptions::from_settings(None).unwrap()
+    }
+
+    fn fragments(text: &str) -> Vec<Fragment> {
+        let mut fragments = Vec::new();
+        push_text(&mut fragments, text, Style::Regular, &mut false);
+        fragments
+    }
+
+    #[test]
+    fn trim_sizes() {
+        assert_eq!(parse_trim_size("6x9").unwrap(), (432.0, 648.0));
+        assert_eq!(parse_trim_size(" 5.5 x 8.5 ").unwrap(), (396.0, 612.0));
+        assert_eq!(parse_trim_size("A4").unwrap(), (595.0, 842.0));
+        assert!(parse_trim_size("2x3").is_err());
+        assert!(parse_trim_size("large").is_err());
+    }
+
+    #[test]
+    fn options_from_settings() {
+        let defaults = options();
+        assert_eq!(defaults.family, FontFamily::Times);
+        assert_eq!(defaults.font_size, 11.0);
+        assert_eq!(defaults.margin(), 54.0);
+
+        let settings: ProjectSettings = serde_json::from_value(serde_json::json!({
+            "pdf_trim_size": "letter",
+            "pdf_font": "Sans",
+            "pdf_font_size": 40.0
+        }))
+        .unwrap();
+        let options = PdfOptions::from_settings(Some(&settings)).unwrap();
+        assert_eq!(options.family, FontFamily::Helvetica);
+        assert_eq!(options.font_size, 24.0);
+        assert_eq!(options.margin(), POINTS_PER_INCH);
+    }
+
+    #[test]
+    fn win_ansi_encoding() {
+        assert_eq!(
+            encode("a\u{e9}\u{2014}\u{201c}"),
+            vec![b'a', 0xe9, 0x97, 0x93]
+        );
+        assert_eq!(encode("\u{3042}"), b"?".to_vec());
+    }
+
+    #[test]
+    fn fragments_remember_spaces() {
+        let mut fragments = Vec::new();
+        let mut pending_space = false;
+        push_text(&mut fragments, "one ", Style::Regular, &mut pending_space);
+        push_text(&mut fragments, "two", Style::Italic, &mut pending_space);
+        push_text(&mut fragments, "s", Style::Regular, &mut pending_space);
+
+        let spaces: Vec<_> = fragments.iter().map(|f| f.space_before).collect();
+        assert_eq!(spaces, vec![false, true, false]);
+        assert_eq!(fragments[1].style, Style::Italic);
+    }
+
+    #[test]
+    fn blocks_from_markdown() {
+        let blocks = parse_blocks("First.\n\nSecond.\n\n---\n\nThird.\n\n> Quote\n\n#", 10.0);
+
+        let summary: Vec<String> = blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph { indent, inset, .. } => format!("p {} {}", indent, inset),
+                Block::Heading(_) => "h".to_string(),
+                Block::SceneBreak => "*".to_string(),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec!["p false 0", "p true 0", "*", "p false 0", "p false 20", "*"]
+        );
+    }
+
+    #[test]
+    fn lines_break_at_the_width() {
+        let options = options();
+        let manuscript = Manuscript {
+            title: "T".to_string(),
+            author: "A".to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: String::new(),
+            chapters: Vec::new(),
+        };
+        let typesetter = Typesetter::new(&options, &manuscript);
+        let words = fragments("aaaa aaaa aaaa");
+        let four = typesetter.measure(b"aaaa", Style::Regular, 10.0);
+        let space = typesetter.space_width(10.0);
+
+        let lines = typesetter.break_lines(&words, four * 2.0 + space, four, 10.0);
+        let counts: Vec<_> = lines.iter().map(|line| line.fragments.len()).collect();
+        assert_eq!(counts, vec![2, 1]);
+        assert_eq!(lines[0].spaces, 1);
+        assert!(!lines[1].fragments[0].space_before);
+    }
+
+    #[test]
+    fn writes_a_pdf() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.pdf");
+        let manuscript = Manuscript {
+            title: "T".to_string(),
+            author: "A".to_string(),
+            language: "en".to_string(),
+            isbn: None,
+            cover: None,
+            created: String::new(),
+            chapters: vec![crate::export::manuscript::Chapter {
+                title: "One".to_string(),
+                part: None,
+                body: "Some *text*.".to_string(),
+            }],
+        };
+
+        write(&manuscript, &options(), &path).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"%PDF-"));
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/Times-Italic"));
+    }
+}
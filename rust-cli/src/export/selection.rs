@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use tokio::fs as async_fs;
+
+use crate::utils::frontmatter;
+
+/// A list of 1-based, inclusive chapter ranges such as `1-5,8,12-`
+#[derive(Debug, Clone)]
+pub struct ChapterRanges(Vec<(usize, Option<usize>)>);
+
+impl ChapterRanges {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut ranges = Vec::new();
+
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let range = match part.split_once('-') {
+                Some((start, end)) => {
+                    let start = if start.trim().is_empty() {
+                        1
+                    } else {
+                        parse_number(start, part)?
+                    };
+                    let end = if end.trim().is_empty() {
+                        None
+                    } else {
+                        Some(parse_number(end, part)?)
+                    };
+
+                    if matches!(end, Some(end) if end < start) {
+                        return Err(anyhow!("Range '{}' ends before it starts", part));
+                    }
+                    (start, end)
+                }
+                None => {
+                    let number = parse_number(part, part)?;
+                    (number, Some(number))
+                }
+            };
+            ranges.push(range);
+        }
+
+        if ranges.is_empty() {
+            return Err(anyhow!("No chapters given in '{}'", spec));
+        }
+
+        Ok(Self(ranges))
+    }
+
+    pub fn contains(&self, number: usize) -> bool {
+        self.0
+            .iter()
+            .any(|&(start, end)| number >= start && end.is_none_or(|end| number <= end))
+    }
+}
+
+fn parse_number(text: &str, part: &str) -> Result<usize> {
+    match text.trim().parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(anyhow!(
+            "Invalid chapter range '{}' (chapters are numbered from 1)",
+            part
+        )),
+    }
+}
+
+/// Keep the chapters matching the ranges (by position in the sorted list) and
+/// whose frontmatter `status` is one of the comma-separated statuses
+pub async fn select(
+    chapters: Vec<PathBuf>,
+    ranges: Option<&ChapterRanges>,
+    statuses: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    let statuses: Option<Vec<String>> = statuses.map(|s| {
+        s.split(',')
+            .map(|status| status.trim().to_lowercase())
+            .filter(|status| !status.is_empty())
+            .collect()
+    });
+
+    let mut selected = Vec::new();
+
+    for (index, chapter) in chapters.into_iter().enumerate() {
+        if let Some(ranges) = ranges {
+            if !ranges.contains(index + 1) {
+                continue;
+            }
+        }
+
+        if let Some(statuses) = &statuses {
+            let content = async_fs::read_to_string(&chapter).await?;
+            let status = frontmatter::parse(&content)
+                .0
+                .status_key()
+                .unwrap_or_default();
+            if !statuses.contains(&status) {
+                continue;
+            }
+        }
+
+        selected.push(chapter);
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        let ranges = ChapterRanges::parse("1-3, 8,12-").unwrap();
+        let picked: Vec<usize> = (1..=14).filter(|&n| ranges.contains(n)).collect();
+        assert_eq!(picked, vec![1, 2, 3, 8, 12, 13, 14]);
+
+        let open_start = ChapterRanges::parse("-2").unwrap();
+        assert!(open_start.contains(1) && open_start.contains(2) && !open_start.contains(3));
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        for spec in ["", " , ", "0", "5-2", "a-3", "1-b"] {
+            assert!(ChapterRanges::parse(spec).is_err(), "{:?}", spec);
+        }
+    }
+
+    #[tokio::test]
+    async fn select_by_range_and_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let chapters: Vec<PathBuf> = ["draft", "final", "Draft"]
+            .iter()
+            .enumerate()
+            .map(|(index, status)| {
+                let path = dir.path().join(format!("{}.md", index + 1));
+                std::fs::write(&path, format!("---\nstatus: {}\n---\nText\n", status)).unwrap();
+                path
+            })
+            .collect();
+        let name = |paths: Vec<PathBuf>| -> Vec<String> {
+            paths
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+                .collect()
+        };
+
+        let ranges = ChapterRanges::parse("2-").unwrap();
+        let selected = select(chapters.clone(), Some(&ranges), None).await.unwrap();
+        assert_eq!(name(selected), vec!["2.md", "3.md"]);
+
+        let selected = select(chapters.clone(), None, Some("DRAFT, revised"))
+            .await
+            .unwrap();
+        assert_eq!(name(selected), vec!["1.md", "3.md"]);
+
+        let selected = select(chapters, Some(&ranges), Some("draft"))
+            .await
+            .unwrap();
+        assert_eq!(name(selected), vec!["3.md"]);
+    }
+}
@@ -1,11 +1,19 @@
+use chrono::NaiveDate;
 use clap::{CommandFactory, Parser, Subcommand};
 use colored::*;
+use std::path::PathBuf;
 use std::process;
 
 mod commands;
 mod editor;
+mod export;
 mod utils;
 
+use commands::story::StoryOptions;
+use utils::output::OutputFormat;
+use utils::submissions::Status;
+use utils::ProjectManager;
+
 #[derive(Parser)]
 #[command(
     name = "writers",
@@ -13,6 +21,14 @@ mod utils;
     version = "1.0.0"
 )]
 struct Cli {
+    /// Project directory (defaults to the nearest parent with a writers.config.json)
+    #[arg(long, global = true, value_name = "DIR")]
+    project: Option<PathBuf>,
+
+    /// Output format (defaults to color, or plain when NO_COLOR is set)
+    #[arg(long = "format", global = true, value_enum, value_name = "FORMAT")]
+    output_format: Option<OutputFormat>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -27,16 +43,225 @@ enum Commands {
         /// Author name
         #[arg(short, long)]
         author: Option<String>,
+        /// Project template from the user template directory
+        #[arg(short, long)]
+        template: Option<String>,
     },
     /// Edit a file with the built-in editor
     Edit {
         /// File to edit (optional - shows selection menu if not provided)
         file: Option<String>,
     },
+    /// Create a new chapter, scene, character, note, story or blog post
+    New {
+        /// Content type (chapter, scene, character, note, story, blogpost)
+        content_type: String,
+        /// Name or title (chapters and scenes are numbered automatically)
+        name: Option<String>,
+        /// Template to start from
+        #[arg(short, long)]
+        template: Option<String>,
+        /// Open the new file in the editor straight away
+        #[arg(long)]
+        open: bool,
+    },
     /// Show project statistics
-    Stats,
+    Stats {
+        /// Break the manuscript down by chapter, with a chart of their lengths
+        #[arg(long)]
+        by_file: bool,
+    },
     /// List all content
     List,
+    /// Show words written per day and week, and your writing streak
+    Progress {
+        /// Number of days to show
+        #[arg(long, default_value_t = 14)]
+        days: usize,
+    },
+    /// Compile the manuscript into a single file
+    Export {
+        /// Export format (markdown, html, epub, docx, pdf)
+        #[arg(default_value = "html")]
+        format: String,
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Chapters to export by position, e.g. "1-5,8,12-"
+        #[arg(long)]
+        chapters: Option<String>,
+        /// Only export chapters whose frontmatter status matches (e.g. "final" or "revised,final")
+        #[arg(long)]
+        status: Option<String>,
+        /// Export a single story from shortstories/ or drafts/ instead of the chapters
+        #[arg(long)]
+        story: Option<String>,
+    },
+    /// Change the reading order of chapters (shows the order when run without options)
+    Reorder {
+        /// Chapter to move: a file name, part of one, or its position
+        chapter: Option<String>,
+        /// Move the chapter one place earlier
+        #[arg(long, conflicts_with_all = ["down", "to"])]
+        up: bool,
+        /// Move the chapter one place later
+        #[arg(long, conflicts_with = "to")]
+        down: bool,
+        /// Move the chapter to this position
+        #[arg(long, value_name = "POSITION")]
+        to: Option<usize>,
+        /// Rename files to chapter-01.md, chapter-02.md, ... and update "Chapter N" headings
+        #[arg(long)]
+        renumber: bool,
+    },
+    /// Analyze the manuscript's prose
+    Analyze {
+        #[command(subcommand)]
+        action: AnalyzeAction,
+    },
+    /// Manage short stories: list, status, move, copy, archive, tags, notes and submit
+    Story {
+        /// Action to perform (list, status, move, copy, archive, tags, notes, submit)
+        action: String,
+        /// Name of the story (required for everything but list)
+        story: Option<String>,
+        /// Filter by status (list), or set the status (status)
+        #[arg(long)]
+        status: Option<String>,
+        /// Filter by genre (list), or set the genre (status)
+        #[arg(long)]
+        genre: Option<String>,
+        /// Filter by tag (list)
+        #[arg(long)]
+        tag: Option<String>,
+        /// Sort by name, length, status or modified (list)
+        #[arg(long)]
+        sort: Option<String>,
+        /// Show a table with genre and modification date (list)
+        #[arg(long)]
+        detailed: bool,
+        /// Destination folder (move) or market (submit)
+        #[arg(long)]
+        to: Option<String>,
+        /// Title for the copy (copy)
+        #[arg(long = "as", value_name = "TITLE")]
+        r#as: Option<String>,
+        /// Comma-separated tags (tags) or a note (notes) to add
+        #[arg(long)]
+        add: Option<String>,
+        /// Comma-separated tags to remove (tags)
+        #[arg(long)]
+        remove: Option<String>,
+    },
+    /// Track where stories have been sent and how they fared
+    Submissions {
+        #[command(subcommand)]
+        action: SubmissionsAction,
+    },
+    /// Run a writing workflow (daily, submission, revision, collection, prompt, sprint, publish, backup)
+    Workflow {
+        /// Workflow type
+        workflow_type: String,
+        /// What to focus on this session
+        #[arg(long)]
+        goal: Option<String>,
+        /// Time limit in minutes (sprints default to 15)
+        #[arg(long)]
+        time: Option<u32>,
+        /// Word target, replacing the day's goal (daily) or for the sprint (sprint)
+        #[arg(long)]
+        words: Option<u32>,
+        /// File to write in (sprint; asks when not given)
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Look back at writing sprints
+    Sprint {
+        #[command(subcommand)]
+        action: SprintAction,
+    },
+    /// Check content files against the project's style rules
+    Lint {
+        /// Files to check (defaults to every content file)
+        files: Vec<String>,
+        /// Show the rules and their severities instead of checking
+        #[arg(long)]
+        rules: bool,
+    },
+    /// Manage the project configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum AnalyzeAction {
+    /// Report overused words, crutch words, adverbs, filter words and repeated phrases
+    Words {
+        /// Number of frequent words, adverbs and phrases to show
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum SubmissionsAction {
+    /// Log a story as sent to a market
+    Log {
+        /// Name of the story
+        story: String,
+        /// The market it was sent to
+        #[arg(long)]
+        to: String,
+        /// When it was sent, as YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// Record a response, a withdrawal or a payment
+    Update {
+        /// Submission number, as shown by "writers submissions list"
+        id: u32,
+        /// The submission's new status
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+        /// When the response came, as YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// What the market paid
+        #[arg(long)]
+        payment: Option<f64>,
+    },
+    /// Show outstanding submissions
+    List {
+        /// Show every submission, not just the outstanding ones
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show response times, acceptances and payments per market
+    Markets,
+    /// Show stories that have never been submitted
+    Unsubmitted,
+}
+
+#[derive(Subcommand)]
+enum SprintAction {
+    /// Show personal bests and recent sprints
+    History {
+        /// Number of recent sprints to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Upgrade a writers.config.json from an older version of Writers
+    Migrate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 fn print_banner() {
@@ -64,6 +289,7 @@ fn print_banner() {
 
 fn main() {
     let cli = Cli::parse();
+    utils::output::init(cli.output_format);
 
     // If no command is provided, show banner and help
     if cli.command.is_none() {
@@ -73,16 +299,155 @@ fn main() {
         return;
     }
 
+    let command = cli.command.unwrap();
+
+    // Init works on the given directory itself; everything else finds the enclosing project
+    let project = match (&command, cli.project) {
+        (Commands::Init { .. }, Some(dir)) => Ok(ProjectManager::at(dir)),
+        (Commands::Init { .. }, None) => std::env::current_dir()
+            .map(ProjectManager::at)
+            .map_err(Into::into),
+        (_, Some(dir)) => {
+            Ok(ProjectManager::discover_from(&dir).unwrap_or_else(|| ProjectManager::at(dir)))
+        }
+        (_, None) => ProjectManager::discover(),
+    };
+
+    let project = match project {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            process::exit(1);
+        }
+    };
+
     let rt = tokio::runtime::Runtime::new().unwrap();
-    let result = match cli.command.unwrap() {
-        Commands::Init { name, author } => rt.block_on(commands::init::run(name, author)),
-        Commands::Edit { file } => rt.block_on(commands::edit::run(file)),
-        Commands::Stats => rt.block_on(commands::stats::run()),
-        Commands::List => rt.block_on(commands::list::run()),
+    let result = match command {
+        Commands::Init {
+            name,
+            author,
+            template,
+        } => rt.block_on(commands::init::run(&project, name, author, template)),
+        Commands::Edit { file } => rt.block_on(commands::edit::run(&project, file)),
+        Commands::New {
+            content_type,
+            name,
+            template,
+            open,
+        } => rt.block_on(commands::new::run(
+            &project,
+            content_type,
+            name,
+            template,
+            open,
+        )),
+        Commands::Stats { by_file } => rt.block_on(commands::stats::run(&project, by_file)),
+        Commands::List => rt.block_on(commands::list::run(&project)),
+        Commands::Progress { days } => rt.block_on(commands::progress::run(&project, days)),
+        Commands::Export {
+            format,
+            output,
+            chapters,
+            status,
+            story,
+        } => rt.block_on(commands::export::run(
+            &project, format, output, chapters, status, story,
+        )),
+        Commands::Reorder {
+            chapter,
+            up,
+            down,
+            to,
+            renumber,
+        } => rt.block_on(commands::reorder::run(
+            &project, chapter, up, down, to, renumber,
+        )),
+        Commands::Analyze { action } => match action {
+            AnalyzeAction::Words { top } => rt.block_on(commands::analyze::words(&project, top)),
+        },
+        Commands::Story {
+            action,
+            story,
+            status,
+            genre,
+            tag,
+            sort,
+            detailed,
+            to,
+            r#as,
+            add,
+            remove,
+        } => rt.block_on(commands::story::run(
+            &project,
+            action,
+            story,
+            StoryOptions {
+                status,
+                genre,
+                tag,
+                sort,
+                detailed,
+                to,
+                r#as,
+                add,
+                remove,
+            },
+        )),
+        Commands::Submissions { action } => match action {
+            SubmissionsAction::Log { story, to, date } => {
+                rt.block_on(commands::submissions::log(&project, story, Some(to), date))
+            }
+            SubmissionsAction::Update {
+                id,
+                status,
+                date,
+                payment,
+            } => rt.block_on(commands::submissions::update(
+                &project, id, status, date, payment,
+            )),
+            SubmissionsAction::List { all } => {
+                rt.block_on(commands::submissions::list(&project, all))
+            }
+            SubmissionsAction::Markets => rt.block_on(commands::submissions::markets(&project)),
+            SubmissionsAction::Unsubmitted => {
+                rt.block_on(commands::submissions::unsubmitted(&project))
+            }
+        },
+        Commands::Workflow {
+            workflow_type,
+            goal,
+            time,
+            words,
+            file,
+        } => rt.block_on(commands::workflow::run(
+            &project,
+            workflow_type,
+            goal,
+            time,
+            words,
+            file,
+        )),
+        Commands::Sprint { action } => match action {
+            SprintAction::History { limit } => {
+                rt.block_on(commands::sprint::history(&project, limit))
+            }
+        },
+        Commands::Lint { files, rules } => rt.block_on(commands::lint::run(&project, files, rules)),
+        Commands::Config { action } => match action {
+            ConfigAction::Migrate { dry_run } => {
+                rt.block_on(commands::config::migrate(&project, dry_run))
+            }
+        },
     };
 
     if let Err(e) = result {
         eprintln!("{} {}", "Error:".red().bold(), e);
         process::exit(1);
     }
+
+    // Keep the word-count history current whatever the command was; a failure
+    // here shouldn't turn a successful command into an error
+    if project.is_writers_project() {
+        let _ = rt.block_on(utils::history::record(&project));
+    }
 }
@@ -0,0 +1,478 @@
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::frontmatter;
+use super::lint;
+use super::readability;
+use super::ProjectSettings;
+
+/// Common words left out of the frequency report, separated by whitespace
+pub const DEFAULT_STOPWORDS: &str = "
+    a about above after again against all am an and any are aren't as at be because been before
+    being below between both but by can can't could couldn't did didn't do does doesn't doing
+    don't down during each few for from further had hadn't has hasn't have haven't having he
+    he'd he'll he's her here here's hers herself him himself his how i i'd i'll i'm i've if in
+    into is isn't it it's its itself let's me more most my myself no nor not of off on once one
+    only or other ought our ours ourselves out over own said same she she'd she'll she's should
+    shouldn't so some such than that that's the their theirs them themselves then there there's
+    these they they'd they'll they're they've this those through to too under until up upon us
+    was wasn't we we'd we'll we're we've were weren't what what's when where which while who
+    whom why will with won't would wouldn't you you'd you'll you're you've your yours yourself
+    yourselves";
+
+/// Words that usually weaken a sentence
+pub const DEFAULT_CRUTCH_WORDS: &str = "
+    just really very suddenly actually basically literally quite rather somehow somewhat totally";
+
+/// Words that filter a scene through a character instead of showing it
+pub const DEFAULT_FILTER_WORDS: &str = "
+    saw see seen felt feel heard hear noticed watched wondered realized seemed knew decided";
+
+// Words ending in -ly that aren't adverbs, separated by whitespace
+const NOT_ADVERBS: &str = "
+    only family early reply supply apply rely holy ugly lovely friendly lonely lively likely
+    daily weekly monthly yearly belly jelly bully rally ally silly july italy butterfly assembly
+    anomaly costly deadly elderly ghostly homely curly wily surly burly lily";
+
+/// Shortest and longest repeated phrases reported, in words
+const PHRASE_LENGTHS: std::ops::RangeInclusive<usize> = 3..=5;
+
+/// The word lists an analysis uses, from the project settings or the defaults
+#[derive(Debug, Clone)]
+pub struct WordLists {
+    pub stopwords: HashSet<String>,
+    pub crutch_words: HashSet<String>,
+    pub filter_words: HashSet<String>,
+}
+
+impl WordLists {
+    pub fn from_settings(settings: Option<&ProjectSettings>) -> Self {
+        let list = |configured: Option<&Vec<String>>, default: &str| -> HashSet<String> {
+            match configured {
+                Some(words) => words.iter().map(|word| normalize(word)).collect(),
+                None => default.split_whitespace().map(String::from).collect(),
+            }
+        };
+
+        Self {
+            stopwords: list(
+                settings.and_then(|s| s.stopwords.as_ref()),
+                DEFAULT_STOPWORDS,
+            ),
+            crutch_words: list(
+                settings.and_then(|s| s.crutch_words.as_ref()),
+                DEFAULT_CRUTCH_WORDS,
+            ),
+            filter_words: list(
+                settings.and_then(|s| s.filter_words.as_ref()),
+                DEFAULT_FILTER_WORDS,
+            ),
+        }
+    }
+}
+
+/// A line in a content file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub path: String,
+    pub line: usize,
+}
+
+/// A flagged word or phrase with everywhere it's used
+#[derive(Debug, Clone, Serialize)]
+pub struct Usage {
+    pub text: String,
+    pub count: usize,
+    pub locations: Vec<Location>,
+}
+
+/// How often a word is used
+#[derive(Debug, Clone, Serialize)]
+pub struct Frequency {
+    pub word: String,
+    pub count: usize,
+}
+
+/// The results of a word analysis, most used first
+#[derive(Debug, Clone, Serialize)]
+pub struct WordReport {
+    pub files: usize,
+    pub words: usize,
+    pub frequent: Vec<Frequency>,
+    pub crutch_words: Vec<Usage>,
+    pub adverbs: Vec<Usage>,
+    pub filter_words: Vec<Usage>,
+    pub phrases: Vec<Usage>,
+}
+
+/// Collects word usage over any number of files
+pub struct Analyzer {
+    lists: WordLists,
+    /// Paths already analyzed
+    paths: HashSet<String>,
+    files: usize,
+    words: usize,
+    counts: HashMap<String, usize>,
+    crutch_words: BTreeMap<String, Vec<Location>>,
+    adverbs: BTreeMap<String, Vec<Location>>,
+    filter_words: BTreeMap<String, Vec<Location>>,
+    phrases: HashMap<String, Vec<Location>>,
+}
+
+impl Analyzer {
+    pub fn new(lists: WordLists) -> Self {
+        Self {
+            lists,
+            paths: HashSet::new(),
+            files: 0,
+            words: 0,
+            counts: HashMap::new(),
+            crutch_words: BTreeMap::new(),
+            adverbs: BTreeMap::new(),
+            filter_words: BTreeMap::new(),
+            phrases: HashMap::new(),
+        }
+    }
+
+    /// Analyze a file's prose. Line numbers count from the top of the file,
+    /// frontmatter included, so they match what an editor shows. A path that
+    /// was already added is skipped, so a file can't repeat itself.
+    pub fn add_file(&mut self, path: &str, content: &str) {
+        if !self.paths.insert(path.to_string()) {
+            return;
+        }
+        self.files += 1;
+        let body = frontmatter::body(content);
+        let offset_lines = content[..content.len() - body.len()].lines().count();
+
+        for paragraph in prose_paragraphs(body) {
+            // A phrase can run across a hard wrap, so read the paragraph as a
+            // whole, remembering where each line starts in it
+            let mut text = String::new();
+            let mut starts = Vec::with_capacity(paragraph.len());
+            for (_, line) in &paragraph {
+                starts.push(text.len());
+                text.push_str(line);
+                text.push('\n');
+            }
+
+            for sentence in readability::sentences(&text) {
+                // Sentences are slices of the text, so their offset is the pointer difference
+                let offset = sentence.as_ptr() as usize - text.as_ptr() as usize;
+                let index = starts.partition_point(|start| *start <= offset) - 1;
+                let location = Location {
+                    path: path.to_string(),
+                    line: offset_lines + paragraph[index].0 + 1,
+                };
+                self.add_sentence(sentence, &location);
+            }
+        }
+    }
+
+    fn add_sentence(&mut self, sentence: &str, location: &Location) {
+        let words: Vec<String> = readability::words(sentence)
+            .into_iter()
+            .map(|(word, _)| normalize(word))
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        for word in &words {
+            self.words += 1;
+
+            if self.lists.crutch_words.contains(word) {
+                add_location(&mut self.crutch_words, word, location);
+            }
+            if self.lists.filter_words.contains(word) {
+                add_location(&mut self.filter_words, word, location);
+            }
+            if is_adverb(word) && !self.lists.crutch_words.contains(word) {
+                add_location(&mut self.adverbs, word, location);
+            }
+            if !self.lists.stopwords.contains(word) && !word.chars().all(|c| c.is_numeric()) {
+                *self.counts.entry(word.clone()).or_insert(0) += 1;
+            }
+        }
+
+        for length in PHRASE_LENGTHS {
+            for window in words.windows(length) {
+                // Runs of stopwords like "out of the" say nothing about style
+                if window
+                    .iter()
+                    .all(|word| self.lists.stopwords.contains(word))
+                {
+                    continue;
+                }
+                self.phrases
+                    .entry(window.join(" "))
+                    .or_default()
+                    .push(location.clone());
+            }
+        }
+    }
+
+    /// Summarize the analysis, keeping the `top` most frequent words, adverbs
+    /// and phrases. Crutch and filter words are always reported in full.
+    pub fn report(self, top: usize) -> WordReport {
+        let mut frequent: Vec<Frequency> = self
+            .counts
+            .into_iter()
+            .map(|(word, count)| Frequency { word, count })
+            .collect();
+        frequent.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+        frequent.truncate(top);
+
+        let mut adverbs = usages(self.adverbs);
+        adverbs.truncate(top);
+
+        let mut phrases = repeated_phrases(self.phrases);
+        phrases.truncate(top);
+
+        WordReport {
+            files: self.files,
+            words: self.words,
+            frequent,
+            crutch_words: usages(self.crutch_words),
+            adverbs,
+            filter_words: usages(self.filter_words),
+            phrases,
+        }
+    }
+}
+
+/// The prose of `body` in paragraphs: runs of consecutive lines, each line's
+/// readable text with its index in `body`. A list item or block quote starts
+/// a new paragraph. Headings, tables, code blocks and HTML comments are
+/// skipped, and inline markup, link targets and images are removed.
+fn prose_paragraphs(body: &str) -> Vec<Vec<(usize, String)>> {
+    let mut paragraphs: Vec<Vec<(usize, String)>> = Vec::new();
+    let mut previous: Option<usize> = None;
+    let mut in_code = false;
+    let mut in_comment = false;
+
+    for (index, line) in body.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_comment {
+            in_comment = !trimmed.contains("-->");
+            continue;
+        }
+        if trimmed.starts_with("<!--") {
+            in_comment = !trimmed.contains("-->");
+            continue;
+        }
+        if in_code || trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('|') {
+            continue;
+        }
+
+        let mut text = String::new();
+        let mut in_image = false;
+        for event in Parser::new(trimmed) {
+            match event {
+                Event::Start(Tag::Image(..)) => in_image = true,
+                Event::End(Tag::Image(..)) => in_image = false,
+                Event::Text(t) | Event::Code(t) if !in_image => text.push_str(&t),
+                _ => {}
+            }
+        }
+        let continues =
+            previous.is_some_and(|line| line + 1 == index) && !lint::starts_block(trimmed);
+        match paragraphs.last_mut() {
+            Some(paragraph) if continues => paragraph.push((index, text)),
+            _ => paragraphs.push(vec![(index, text)]),
+        }
+        previous = Some(index);
+    }
+
+    paragraphs
+}
+
+/// Lowercase a word and straighten its apostrophes
+fn normalize(word: &str) -> String {
+    word.trim().to_lowercase().replace('’', "'")
+}
+
+fn is_adverb(word: &str) -> bool {
+    word.len() > 4
+        && word.ends_with("ly")
+        && !NOT_ADVERBS
+            .split_whitespace()
+            .any(|exception| exception == word)
+}
+
+fn add_location(map: &mut BTreeMap<String, Vec<Location>>, word: &str, location: &Location) {
+    map.entry(word.to_string())
+        .or_default()
+        .push(location.clone());
+}
+
+fn usages(map: BTreeMap<String, Vec<Location>>) -> Vec<Usage> {
+    let mut usages: Vec<Usage> = map
+        .into_iter()
+        .map(|(text, locations)| Usage {
+            text,
+            count: locations.len(),
+            locations,
+        })
+        .collect();
+    usages.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.text.cmp(&b.text)));
+    usages
+}
+
+/// Phrases used more than once, most used first. A phrase is left out when a
+/// longer repeated phrase containing it is used just as often, so "at the end
+/// of the" isn't also reported as "at the end" and "the end of".
+fn repeated_phrases(phrases: HashMap<String, Vec<Location>>) -> Vec<Usage> {
+    let mut repeated: Vec<Usage> = phrases
+        .into_iter()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(text, locations)| Usage {
+            text,
+            count: locations.len(),
+            locations,
+        })
+        .collect();
+    // Longest first, so the phrases that absorb shorter ones are kept first
+    repeated.sort_by(|a, b| {
+        let length = |usage: &Usage| usage.text.split(' ').count();
+        length(b)
+            .cmp(&length(a))
+            .then_with(|| b.count.cmp(&a.count))
+            .then_with(|| a.text.cmp(&b.text))
+    });
+
+    let mut kept: Vec<Usage> = Vec::new();
+    for usage in repeated {
+        let padded = format!(" {} ", usage.text);
+        let absorbed = kept.iter().any(|longer| {
+            longer.count == usage.count && format!(" {} ", longer.text).contains(&padded)
+        });
+        if !absorbed {
+            kept.push(usage);
+        }
+    }
+
+    kept.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| b.text.len().cmp(&a.text.len()))
+            .then_with(|| a.text.cmp(&b.text))
+    });
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{config_json, project_with};
+
+    fn analyze(files: &[(&str, &str)]) -> WordReport {
+        let mut analyzer = Analyzer::new(WordLists::from_settings(None));
+        for (path, content) in files {
+            analyzer.add_file(path, content);
+        }
+        analyzer.report(10)
+    }
+
+    #[tokio::test]
+    async fn a_drafts_project_has_no_self_repeats() {
+        let (_dir, project) = project_with(&[
+            ("writers.config.json", &config_json("simple-short-story")),
+            (
+                "drafts/alpha.md",
+                "The cat slept on the mat near the door today.\n",
+            ),
+        ]);
+        let mut analyzer = Analyzer::new(WordLists::from_settings(None));
+        for file in project.get_all_content_files().await.unwrap() {
+            let content = std::fs::read_to_string(&file).unwrap();
+            analyzer.add_file(&project.display_path(&file), &content);
+        }
+        let report = analyzer.report(10);
+
+        assert_eq!(report.files, 1);
+        assert!(report.phrases.is_empty());
+        assert!(report.frequent.iter().all(|f| f.count == 1));
+    }
+
+    #[test]
+    fn phrases_run_across_wrapped_lines() {
+        let report = analyze(&[
+            (
+                "a.md",
+                "---\ntitle: A\n---\nShe walked to the end\nof the road.\n",
+            ),
+            ("b.md", "He ran to the end of the road.\n"),
+        ]);
+        let phrases: Vec<_> = report.phrases.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(phrases, ["the end of the road", "to the end of the"]);
+        assert_eq!(report.phrases[0].count, 2);
+        assert_eq!(report.phrases[0].locations[0].line, 4);
+    }
+
+    #[test]
+    fn the_same_path_is_only_counted_once() {
+        let text = "Rain fell on the old red roof.";
+        let report = analyze(&[("a.md", text), ("a.md", text)]);
+        assert_eq!(report.files, 1);
+        assert_eq!(report.words, 7);
+        assert!(report.phrases.is_empty());
+    }
+
+    #[test]
+    fn phrases_repeated_across_files_are_reported_once_at_full_length() {
+        let report = analyze(&[
+            ("a.md", "She walked to the end of the road."),
+            ("b.md", "He ran to the end of the road."),
+        ]);
+        let phrases: Vec<_> = report.phrases.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(phrases, ["the end of the road", "to the end of the"]);
+        assert_eq!(report.phrases[0].locations[1].path, "b.md");
+    }
+
+    #[test]
+    fn lines_count_from_the_top_of_the_file() {
+        let report = analyze(&[(
+            "a.md",
+            "---\ntitle: A\n---\n# Heading\n\nIt was just fine.\n",
+        )]);
+        assert_eq!(report.crutch_words[0].text, "just");
+        assert_eq!(report.crutch_words[0].locations[0].line, 6);
+    }
+
+    #[test]
+    fn adverbs_skip_crutch_words_and_exceptions() {
+        let report = analyze(&[("a.md", "He really quickly made a lovely daily reply.")]);
+        let adverbs: Vec<_> = report.adverbs.iter().map(|u| u.text.as_str()).collect();
+        assert_eq!(adverbs, ["quickly"]);
+    }
+
+    #[test]
+    fn configured_lists_replace_the_defaults() {
+        let settings = ProjectSettings {
+            crutch_words: Some(vec!["Basically".to_string()]),
+            ..Default::default()
+        };
+        let lists = WordLists::from_settings(Some(&settings));
+        assert!(lists.crutch_words.contains("basically"));
+        assert!(!lists.crutch_words.contains("just"));
+        assert!(lists.stopwords.contains("the"));
+    }
+
+    #[test]
+    fn code_comments_and_images_are_not_prose() {
+        let paragraphs = prose_paragraphs(
+            "Real text.\n```\ncode here\n```\n<!-- a\nnote -->\n![alt words](x.png) Tail.",
+        );
+        assert_eq!(
+            paragraphs,
+            [
+                vec![(0, "Real text.".to_string())],
+                vec![(6, " Tail.".to_string())]
+            ]
+        );
+    }
+}
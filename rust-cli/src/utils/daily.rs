@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::{store, ProjectManager};
+
+/// Daily-writing streak, relative to the project root
+pub const DAILY_FILE: &str = ".writers/daily.json";
+
+/// The daily workflow's streak: consecutive days the day's goal was met
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyState {
+    /// Last day the goal was met
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_met: Option<NaiveDate>,
+    /// Days in the streak that ended on `last_met`
+    #[serde(default)]
+    pub streak: usize,
+    #[serde(default)]
+    pub longest: usize,
+    /// Daily sessions ever started
+    #[serde(default)]
+    pub sessions: usize,
+}
+
+impl DailyState {
+    /// The streak as of `today`. It survives until the end of today, so a
+    /// streak that ended yesterday still counts.
+    pub fn current(&self, today: NaiveDate) -> usize {
+        match self.last_met {
+            Some(day) if day == today || day + Duration::days(1) == today => self.streak,
+            _ => 0,
+        }
+    }
+
+    /// Count `today` as a day the goal was met, once per day. Returns whether
+    /// the streak grew.
+    pub fn mark_met(&mut self, today: NaiveDate) -> bool {
+        if self.last_met == Some(today) {
+            return false;
+        }
+        self.streak = self.current(today) + 1;
+        self.longest = self.longest.max(self.streak);
+        self.last_met = Some(today);
+        true
+    }
+}
+
+pub async fn load(project: &ProjectManager) -> Result<DailyState> {
+    store::load_json(project, DAILY_FILE).await
+}
+
+pub async fn save(project: &ProjectManager, state: &DailyState) -> Result<()> {
+    store::save_json(project, DAILY_FILE, state).await
+}
+
+/// The dated page for a day's writing. Daily pages live with the notes, so
+/// they count towards the word-count history without joining the manuscript.
+pub async fn page_path(project: &ProjectManager, day: NaiveDate) -> PathBuf {
+    project
+        .content_dir("notes")
+        .await
+        .join("daily")
+        .join(format!("{}.md", day.format("%Y-%m-%d")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{date, project_with};
+
+    #[test]
+    fn streak_lasts_until_the_end_of_the_next_day() {
+        let state = DailyState {
+            last_met: Some(date(10)),
+            streak: 4,
+            ..Default::default()
+        };
+        assert_eq!(state.current(date(10)), 4);
+        assert_eq!(state.current(date(11)), 4);
+        assert_eq!(state.current(date(12)), 0);
+        assert_eq!(DailyState::default().current(date(10)), 0);
+    }
+
+    #[test]
+    fn meeting_the_goal_grows_or_restarts_the_streak() {
+        let mut state = DailyState::default();
+        assert!(state.mark_met(date(30)));
+        assert!(!state.mark_met(date(30)));
+        // Rolls over the end of the month
+        assert!(state.mark_met(date(31)));
+        assert!(state.mark_met(NaiveDate::from_ymd_opt(2026, 4, 1).unwrap()));
+        assert_eq!(state.streak, 3);
+        assert_eq!(state.longest, 3);
+
+        assert!(state.mark_met(NaiveDate::from_ymd_opt(2026, 4, 5).unwrap()));
+        assert_eq!(state.streak, 1);
+        assert_eq!(state.longest, 3);
+    }
+
+    #[tokio::test]
+    async fn daily_pages_live_with_the_notes() {
+        let (dir, project) = project_with(&[]);
+        assert_eq!(
+            page_path(&project, date(3)).await,
+            dir.path().join("notes/daily/2026-03-03.md")
+        );
+    }
+}
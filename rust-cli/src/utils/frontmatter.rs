@@ -0,0 +1,318 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Metadata from the YAML frontmatter block at the top of a content file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Workflow status such as "draft", "revised" or "final"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Point-of-view character
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pov: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synopsis: Option<String>,
+    /// Part or act the chapter belongs to, overriding its subdirectory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part: Option<String>,
+    /// Explicit position in reading order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_words: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+    /// Dated working notes, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Any other keys, kept so that rewriting a file doesn't lose them
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// A frontmatter key whose value couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldProblem {
+    /// The key, or "frontmatter" when the whole block isn't valid YAML
+    pub field: String,
+    pub reason: String,
+}
+
+impl fmt::Display for FieldProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+
+impl ContentMeta {
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Read frontmatter a key at a time, so one bad value only costs that key.
+    /// Values of the wrong shape are converted where the meaning is clear, as
+    /// with `order: "3"` or `tags: foo`. Keys that still don't fit are kept in
+    /// `extra`, so rewriting the file doesn't lose them, and reported.
+    pub fn from_yaml_lenient(yaml: &str) -> (Self, Vec<FieldProblem>) {
+        if let Ok(meta) = Self::from_yaml(yaml) {
+            return (meta, Vec::new());
+        }
+
+        let mapping = match serde_yaml::from_str::<Value>(yaml) {
+            Ok(Value::Mapping(mapping)) => mapping,
+            Ok(_) => return (Self::default(), vec![block_problem("not a list of keys")]),
+            Err(e) => return (Self::default(), vec![block_problem(&e.to_string())]),
+        };
+
+        let mut good = Mapping::new();
+        let mut bad = Vec::new();
+        let mut problems = Vec::new();
+        for (key, value) in mapping {
+            let Some(name) = key.as_str().map(str::to_string) else {
+                problems.push(FieldProblem {
+                    field: format!("{:?}", key),
+                    reason: "keys must be text".to_string(),
+                });
+                continue;
+            };
+
+            let read = |value: &Value| {
+                let single = Mapping::from_iter([(key.clone(), value.clone())]);
+                serde_yaml::from_value::<Self>(Value::Mapping(single))
+            };
+            match candidates(&value).into_iter().find(|c| read(c).is_ok()) {
+                Some(candidate) => {
+                    good.insert(key, candidate);
+                }
+                None => {
+                    let reason = read(&value).err().map(|e| e.to_string());
+                    problems.push(FieldProblem {
+                        field: name.clone(),
+                        reason: reason.unwrap_or_default(),
+                    });
+                    bad.push((name, value));
+                }
+            }
+        }
+
+        let mut meta: Self = serde_yaml::from_value(Value::Mapping(good)).unwrap_or_default();
+        meta.extra.extend(bad);
+        (meta, problems)
+    }
+
+    /// Status in lowercase, for comparisons
+    pub fn status_key(&self) -> Option<String> {
+        self.status.as_ref().map(|s| s.trim().to_lowercase())
+    }
+}
+
+fn block_problem(reason: &str) -> FieldProblem {
+    FieldProblem {
+        field: "frontmatter".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Ways to read a value: as written, then converted to a number, to text,
+/// or to a one-item list
+fn candidates(value: &Value) -> Vec<Value> {
+    let mut candidates = vec![value.clone()];
+    let text = match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    };
+    if let Some(text) = text {
+        if let Ok(number) = text.trim().parse::<i64>() {
+            candidates.push(Value::from(number));
+        }
+        candidates.push(Value::String(text.clone()));
+        candidates.push(Value::Sequence(vec![Value::String(text)]));
+    }
+    candidates
+}
+
+/// Parse a file into its metadata and body. Bad frontmatter values are
+/// skipped (see [`ContentMeta::from_yaml_lenient`]) so a typo never hides a file.
+pub fn parse(content: &str) -> (ContentMeta, &str) {
+    let (meta, body, _) = parse_checked(content);
+    (meta, body)
+}
+
+/// [`parse`], also returning the frontmatter keys that had to be skipped
+pub fn parse_checked(content: &str) -> (ContentMeta, &str, Vec<FieldProblem>) {
+    match split(content) {
+        (Some(yaml), body) => {
+            let (meta, problems) = ContentMeta::from_yaml_lenient(yaml);
+            (meta, body, problems)
+        }
+        (None, body) => (ContentMeta::default(), body, Vec::new()),
+    }
+}
+
+/// Change the frontmatter of a file's content, keeping its body and any keys
+/// `ContentMeta` doesn't know about or couldn't read. Frontmatter that isn't
+/// valid YAML at all is an error, so it's never overwritten.
+pub fn update(content: &str, change: impl FnOnce(&mut ContentMeta)) -> Result<String> {
+    let (yaml, body) = split(content);
+    let (mut meta, problems) = ContentMeta::from_yaml_lenient(yaml.unwrap_or_default());
+    if let Some(problem) = problems.iter().find(|p| p.field == "frontmatter") {
+        return Err(anyhow!(
+            "The frontmatter isn't valid YAML: {}",
+            problem.reason
+        ));
+    }
+    change(&mut meta);
+
+    // A key set by the change replaces the unreadable value kept for it
+    let set = serde_yaml::to_value(ContentMeta {
+        extra: BTreeMap::new(),
+        ..meta.clone()
+    })?;
+    if let Value::Mapping(set) = set {
+        meta.extra
+            .retain(|key, _| !set.contains_key(Value::String(key.clone())));
+    }
+    render(&meta, body)
+}
+
+/// Put a frontmatter block in front of a body. Empty metadata leaves the body bare.
+pub fn render(meta: &ContentMeta, body: &str) -> Result<String> {
+    if *meta == ContentMeta::default() {
+        return Ok(body.to_string());
+    }
+    Ok(format!(
+        "---\n{}---\n{}",
+        serde_yaml::to_string(meta)?,
+        body
+    ))
+}
+
+/// The text of a file with any frontmatter removed
+pub fn body(content: &str) -> &str {
+    split(content).1
+}
+
+/// Split a file into its frontmatter block (without the `---` fences) and the body.
+/// Files without frontmatter return `None` and the whole content as the body.
+pub fn split(content: &str) -> (Option<&str>, &str) {
+    let rest = match content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    {
+        Some(rest) => rest,
+        None => return (None, content),
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_is_split_from_the_body() {
+        assert_eq!(
+            split("---\ntitle: A\n---\nText\n"),
+            (Some("title: A\n"), "Text\n")
+        );
+        assert_eq!(
+            split("---\r\ntitle: A\r\n...\r\nText"),
+            (Some("title: A\r\n"), "Text")
+        );
+        assert_eq!(split("No frontmatter"), (None, "No frontmatter"));
+        // An unclosed block is just text
+        assert_eq!(split("---\ntitle: A\n"), (None, "---\ntitle: A\n"));
+    }
+
+    #[test]
+    fn metadata_round_trips_with_unknown_keys() {
+        let content = "---\ntitle: Storm\norder: 3\ntags:\n- sea\nmood: grim\n---\nText\n";
+        let (meta, body) = parse(content);
+        assert_eq!(meta.title.as_deref(), Some("Storm"));
+        assert_eq!(meta.order, Some(3));
+        assert_eq!(meta.tags, ["sea"]);
+        assert_eq!(body, "Text\n");
+
+        let rendered = render(&meta, body).unwrap();
+        assert_eq!(parse(&rendered).0, meta);
+        assert!(rendered.contains("mood: grim"));
+        assert_eq!(render(&ContentMeta::default(), "Bare").unwrap(), "Bare");
+    }
+
+    #[test]
+    fn values_of_the_wrong_shape_are_converted() {
+        let (meta, body, problems) =
+            parse_checked("---\ntitle: 1984\norder: \"3\"\ntags: foo\n---\nText");
+        assert!(problems.is_empty());
+        assert_eq!(meta.title.as_deref(), Some("1984"));
+        assert_eq!(meta.order, Some(3));
+        assert_eq!(meta.tags, ["foo"]);
+        assert_eq!(body, "Text");
+    }
+
+    #[test]
+    fn one_bad_field_keeps_the_others() {
+        let (meta, _, problems) =
+            parse_checked("---\ntitle: Storm\nstatus: draft\norder: soon\n---\n");
+        assert_eq!(meta.title.as_deref(), Some("Storm"));
+        assert_eq!(meta.status.as_deref(), Some("draft"));
+        assert_eq!(meta.order, None);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "order");
+        assert_eq!(meta.extra["order"], Value::from("soon"));
+    }
+
+    #[test]
+    fn invalid_yaml_is_reported_as_a_whole() {
+        let (meta, body, problems) = parse_checked("---\ntitle: [unclosed\n---\nText");
+        assert_eq!(meta, ContentMeta::default());
+        assert_eq!(body, "Text");
+        assert_eq!(problems[0].field, "frontmatter");
+        assert!(update("---\ntitle: [unclosed\n---\nText", |_| {}).is_err());
+    }
+
+    #[test]
+    fn updates_keep_the_body_and_replace_unreadable_values() {
+        let content = "---\norder: soon\nmood: grim\n---\nText\n";
+        let updated = update(content, |meta| meta.status = Some("final".into())).unwrap();
+        let (meta, body) = parse(&updated);
+        assert_eq!(meta.status.as_deref(), Some("final"));
+        assert_eq!(meta.extra["order"], Value::from("soon"));
+        assert_eq!(body, "Text\n");
+
+        let updated = update(content, |meta| meta.order = Some(2)).unwrap();
+        assert_eq!(updated.matches("order:").count(), 1);
+        assert_eq!(parse(&updated).0.order, Some(2));
+    }
+
+    #[test]
+    fn status_keys_ignore_case_and_spacing() {
+        let meta = ContentMeta {
+            status: Some(" Draft ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(meta.status_key().as_deref(), Some("draft"));
+    }
+}
@@ -0,0 +1,311 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::history::{self, Snapshot};
+use super::{ContentFile, ProjectConfig, ProjectManager};
+
+/// Progress towards the project's word goal, paced against its deadline
+#[derive(Debug, Clone)]
+pub struct Pace {
+    pub goal: usize,
+    pub written: usize,
+    pub deadline: Option<NaiveDate>,
+    /// Days left before the deadline, counting today
+    pub days_left: Option<i64>,
+    /// Words to write today: the configured daily goal, or what the deadline requires
+    pub daily_target: Option<usize>,
+    /// Words written so far today, when there is a history
+    pub today: Option<i64>,
+    /// How far ahead (positive) or behind a steady pace towards the deadline
+    pub ahead_by: Option<i64>,
+}
+
+impl Pace {
+    /// Work out the pace for `written` words, or `None` when there's no word goal.
+    /// The steady pace runs from the first history entry (or the project's
+    /// creation) to the deadline.
+    pub fn new(
+        config: &ProjectConfig,
+        written: usize,
+        snapshots: &[Snapshot],
+        today: NaiveDate,
+    ) -> Option<Self> {
+        let goal = config.word_goal.filter(|goal| *goal > 0)?;
+        let deadline = config.deadline;
+
+        let days_left = deadline.map(|deadline| (deadline - today).num_days() + 1);
+        let today_words =
+            history::total_before(snapshots, today).map(|start| written as i64 - start as i64);
+
+        let start = match snapshots.first() {
+            Some(first) => Some((first.time.date_naive(), first.total)),
+            None => {
+                NaiveDate::parse_from_str(config.created.get(..10).unwrap_or_default(), "%Y-%m-%d")
+                    .ok()
+                    .map(|date| (date, 0))
+            }
+        };
+        let ahead_by = match (start, deadline) {
+            (Some((start_date, start_total)), Some(deadline)) if start_date <= deadline => {
+                // Where a steady pace would have got to by the start of today
+                let span = (deadline - start_date).num_days() + 1;
+                let elapsed = (today - start_date).num_days().clamp(0, span);
+                let expected =
+                    start_total as i64 + (goal as i64 - start_total as i64) * elapsed / span;
+                Some(written as i64 - expected)
+            }
+            _ => None,
+        };
+
+        Some(Self {
+            goal,
+            written,
+            deadline,
+            days_left,
+            daily_target: daily_target(config, written, snapshots, today),
+            today: today_words,
+            ahead_by,
+        })
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.goal.saturating_sub(self.written)
+    }
+
+    pub fn percent(&self) -> f64 {
+        (self.written as f64 / self.goal as f64 * 100.0).min(100.0)
+    }
+}
+
+/// Words to write today. A configured `daily_goal` wins; otherwise the words
+/// left at the start of today are spread over the days until the deadline.
+pub fn daily_target(
+    config: &ProjectConfig,
+    written: usize,
+    snapshots: &[Snapshot],
+    today: NaiveDate,
+) -> Option<usize> {
+    if let Some(daily) = config.daily_goal.filter(|daily| *daily > 0) {
+        return Some(daily);
+    }
+
+    let goal = config.word_goal?;
+    let days_left = (config.deadline? - today).num_days() + 1;
+    if days_left <= 0 {
+        return None;
+    }
+
+    let start = history::total_before(snapshots, today).unwrap_or(written);
+    let remaining = goal.saturating_sub(start);
+    Some(remaining.div_ceil(days_left as usize))
+}
+
+/// The files that make up the manuscript for the project type, in reading order
+pub async fn manuscript_files(
+    project: &ProjectManager,
+    config: &ProjectConfig,
+) -> Result<Vec<PathBuf>> {
+    match config.r#type.as_str() {
+        "novel" => project.get_chapters().await,
+        "blog" => project.get_blog_posts().await,
+        _ => project.get_short_stories().await,
+    }
+}
+
+/// The word-count history of the manuscript files alone, so notes,
+/// character sheets and daily pages don't count towards the goal
+pub async fn manuscript_history(
+    project: &ProjectManager,
+    config: &ProjectConfig,
+) -> Result<Vec<Snapshot>> {
+    let names: HashSet<String> = manuscript_files(project, config)
+        .await?
+        .iter()
+        .map(|path| project.display_path(path))
+        .collect();
+    Ok(history::restrict(&history::load(project).await?, &names))
+}
+
+/// Pace towards the word goal, counting only the manuscript
+pub async fn manuscript_pace(
+    project: &ProjectManager,
+    config: &ProjectConfig,
+) -> Result<Option<Pace>> {
+    let written: usize = project
+        .read_contents(&manuscript_files(project, config).await?)
+        .await
+        .iter()
+        .map(ContentFile::word_count)
+        .sum();
+    let snapshots = manuscript_history(project, config).await?;
+    Ok(Pace::new(
+        config,
+        written,
+        &snapshots,
+        Local::now().date_naive(),
+    ))
+}
+
+/// The day's word goal for an editing session on `file`, with the words
+/// written today in the other manuscript files. Today's progress is then
+/// that offset plus the live word count of the file. A given `goal`
+/// replaces the project's.
+pub async fn daily_goal_for(
+    project: &ProjectManager,
+    file: &Path,
+    goal: Option<usize>,
+) -> Result<Option<(usize, i64)>> {
+    let config = project.get_config().await?;
+    history::record(project).await?;
+    let snapshots = manuscript_history(project, &config).await?;
+
+    let Some(latest) = snapshots.last() else {
+        return Ok(None);
+    };
+    let today = Local::now().date_naive();
+    let Some(goal) = goal.or_else(|| daily_target(&config, latest.total, &snapshots, today)) else {
+        return Ok(None);
+    };
+
+    let file_words = latest
+        .files
+        .get(&project.display_path(file))
+        .copied()
+        .unwrap_or(0);
+    let start = history::total_before(&snapshots, today).unwrap_or(latest.total);
+
+    Ok(Some((
+        goal,
+        latest.total as i64 - file_words as i64 - start as i64,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{config_json, date, project_with};
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+
+    fn config(extra: serde_json::Value) -> ProjectConfig {
+        let mut value = serde_json::json!({
+            "name": "T",
+            "author": "A",
+            "type": "novel",
+            "version": "1.0.0",
+            "created": "2026-03-01T00:00:00Z",
+            "settings": null,
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn snapshot(day: u32, total: usize) -> Snapshot {
+        Snapshot {
+            time: Local.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+            total,
+            files: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn no_goal_means_no_pace() {
+        assert!(Pace::new(&config(serde_json::json!({})), 500, &[], date(5)).is_none());
+    }
+
+    #[test]
+    fn pace_runs_from_the_creation_date_without_history() {
+        let config = config(serde_json::json!({ "word_goal": 1000, "deadline": "2026-03-10" }));
+        let pace = Pace::new(&config, 300, &[], date(6)).unwrap();
+
+        assert_eq!(pace.days_left, Some(5));
+        assert_eq!(pace.remaining(), 700);
+        assert_eq!(pace.percent(), 30.0);
+        assert_eq!(pace.today, None);
+        // 5 of 10 days gone: a steady pace would be at 500
+        assert_eq!(pace.ahead_by, Some(-200));
+        assert_eq!(pace.daily_target, Some(140));
+    }
+
+    #[test]
+    fn todays_words_come_from_the_history() {
+        let config = config(serde_json::json!({ "word_goal": 1000, "deadline": "2026-03-10" }));
+        let snapshots = [snapshot(1, 0), snapshot(5, 400), snapshot(6, 450)];
+        let pace = Pace::new(&config, 480, &snapshots, date(6)).unwrap();
+
+        assert_eq!(pace.today, Some(80));
+        // The 600 words left at the start of today, over 5 days
+        assert_eq!(pace.daily_target, Some(120));
+    }
+
+    #[test]
+    fn a_daily_goal_wins_over_the_deadline() {
+        let config = config(serde_json::json!({
+            "word_goal": 1000,
+            "deadline": "2026-03-10",
+            "daily_goal": 250,
+        }));
+        assert_eq!(daily_target(&config, 0, &[], date(6)), Some(250));
+    }
+
+    #[test]
+    fn a_passed_deadline_has_no_daily_target() {
+        let config = config(serde_json::json!({ "word_goal": 1000, "deadline": "2026-03-10" }));
+        assert_eq!(daily_target(&config, 0, &[], date(11)), None);
+        assert_eq!(daily_target(&config, 0, &[], date(10)), Some(1000));
+    }
+
+    #[tokio::test]
+    async fn manuscript_files_follow_the_project_type() {
+        let (dir, project) = project_with(&[
+            ("chapters/chapter-01.md", "Words."),
+            ("drafts/story.md", "Words."),
+        ]);
+
+        let novel = manuscript_files(&project, &config(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(novel, vec![dir.path().join("chapters/chapter-01.md")]);
+        let stories = manuscript_files(
+            &project,
+            &config(serde_json::json!({ "type": "short-story" })),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stories, vec![dir.path().join("drafts/story.md")]);
+    }
+
+    #[tokio::test]
+    async fn notes_dont_move_the_editors_goal() {
+        let mut config: serde_json::Value = serde_json::from_str(&config_json("novel")).unwrap();
+        config["word_goal"] = 1000.into();
+        config["deadline"] = (Local::now().date_naive() + chrono::Duration::days(9))
+            .to_string()
+            .into();
+        let (dir, project) = project_with(&[
+            ("writers.config.json", &config.to_string()),
+            (
+                "chapters/chapter-01.md",
+                "one two three four five six seven eight nine ten",
+            ),
+            ("notes/ideas.md", ""),
+        ]);
+        let chapter = dir.path().join("chapters/chapter-01.md");
+        let note = dir.path().join("notes/ideas.md");
+
+        let before = daily_goal_for(&project, &chapter, None).await.unwrap();
+        // No history before today: the chapter's words were already there
+        assert_eq!(before, Some((99, -10)));
+
+        std::fs::write(&note, "word ".repeat(500)).unwrap();
+        let after = daily_goal_for(&project, &chapter, None).await.unwrap();
+        assert_eq!(after, before);
+        let in_the_note = daily_goal_for(&project, &note, None).await.unwrap();
+        assert_eq!(in_the_note, Some((99, 0)));
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::{store, ProjectManager};
+
+/// Word-count history, one JSON snapshot per line, relative to the project root
+pub const HISTORY_FILE: &str = ".writers/history.jsonl";
+
+/// Word counts of every content file at one moment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub time: DateTime<Local>,
+    pub total: usize,
+    /// Words per file, keyed by path relative to the project root
+    pub files: BTreeMap<String, usize>,
+}
+
+/// Count every content file and append a snapshot to the history, unless
+/// nothing changed since the last one. Returns whether a snapshot was written.
+pub async fn record(project: &ProjectManager) -> Result<bool> {
+    let files = count_files(project).await?;
+
+    if let Some(last) = load(project).await?.last() {
+        if last.files == files {
+            return Ok(false);
+        }
+    }
+
+    let snapshot = Snapshot {
+        time: Local::now(),
+        total: files.values().sum(),
+        files,
+    };
+
+    store::append_jsonl(project, HISTORY_FILE, &snapshot).await?;
+
+    Ok(true)
+}
+
+/// Words in each content file, keyed by path relative to the project root.
+/// Each file is counted once, even if it's listed under two content types.
+pub async fn count_files(project: &ProjectManager) -> Result<BTreeMap<String, usize>> {
+    let mut files = BTreeMap::new();
+    for file in project
+        .read_contents(&project.get_all_content_files().await?)
+        .await
+    {
+        files
+            .entry(project.display_path(&file.path))
+            .or_insert_with(|| file.word_count());
+    }
+    Ok(files)
+}
+
+/// Read the history, oldest first. Lines that can't be parsed are skipped.
+pub async fn load(project: &ProjectManager) -> Result<Vec<Snapshot>> {
+    store::load_jsonl(project, HISTORY_FILE).await
+}
+
+/// The history of just some files: each snapshot keeps only the named
+/// files, with its total recounted from them
+pub fn restrict(snapshots: &[Snapshot], files: &HashSet<String>) -> Vec<Snapshot> {
+    snapshots
+        .iter()
+        .map(|snapshot| {
+            let kept: BTreeMap<String, usize> = snapshot
+                .files
+                .iter()
+                .filter(|(path, _)| files.contains(*path))
+                .map(|(path, words)| (path.clone(), *words))
+                .collect();
+            Snapshot {
+                time: snapshot.time,
+                total: kept.values().sum(),
+                files: kept,
+            }
+        })
+        .collect()
+}
+
+/// Net words written on each day with a snapshot: the day's last total minus
+/// the total before it. The very first snapshot is the baseline, so an
+/// existing manuscript doesn't count as a day's writing.
+pub fn words_per_day(snapshots: &[Snapshot]) -> BTreeMap<NaiveDate, i64> {
+    let mut days = BTreeMap::new();
+    let Some(first) = snapshots.first() else {
+        return days;
+    };
+
+    let mut previous_total = first.total as i64;
+    let mut current: Option<(NaiveDate, i64)> = None;
+
+    for snapshot in snapshots {
+        let date = snapshot.time.date_naive();
+        match current {
+            Some((day, total)) if day != date => {
+                days.insert(day, total - previous_total);
+                previous_total = total;
+            }
+            _ => {}
+        }
+        current = Some((date, snapshot.total as i64));
+    }
+
+    if let Some((day, total)) = current {
+        days.insert(day, total - previous_total);
+    }
+
+    days
+}
+
+/// The project total at the start of `day`: the last total recorded before it,
+/// or the first one if the history starts that day
+pub fn total_before(snapshots: &[Snapshot], day: NaiveDate) -> Option<usize> {
+    snapshots
+        .iter()
+        .rev()
+        .find(|snapshot| snapshot.time.date_naive() < day)
+        .or_else(|| snapshots.first())
+        .map(|snapshot| snapshot.total)
+}
+
+/// Net words written in each week, keyed by the week's Monday
+pub fn words_per_week(days: &BTreeMap<NaiveDate, i64>) -> BTreeMap<NaiveDate, i64> {
+    let mut weeks = BTreeMap::new();
+    for (day, words) in days {
+        *weeks.entry(week_start(*day)).or_insert(0) += words;
+    }
+    weeks
+}
+
+/// The Monday of the week containing `day`
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
+
+/// Current and longest runs of consecutive days with words added. The current
+/// streak survives until the end of today, so it counts from yesterday if
+/// nothing has been written yet today.
+pub fn streaks(days: &BTreeMap<NaiveDate, i64>, today: NaiveDate) -> (usize, usize) {
+    let wrote = |day: NaiveDate| days.get(&day).is_some_and(|words| *words > 0);
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for (&day, &words) in days {
+        if words <= 0 {
+            run = 0;
+        } else if previous.is_some_and(|p| p + Duration::days(1) == day) && run > 0 {
+            run += 1;
+        } else {
+            run = 1;
+        }
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+
+    let mut day = if wrote(today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    let mut current = 0;
+    while wrote(day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+
+    (current, longest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{config_json, date, project_with};
+    use chrono::TimeZone;
+
+    fn snapshot(day: u32, hour: u32, total: usize) -> Snapshot {
+        Snapshot {
+            time: Local.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap(),
+            total,
+            files: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn drafts_count_once_in_a_snapshot() {
+        let (_dir, project) = project_with(&[
+            ("writers.config.json", &config_json("simple-short-story")),
+            ("drafts/alpha.md", "One two three."),
+        ]);
+        assert!(record(&project).await.unwrap());
+        assert!(!record(&project).await.unwrap());
+
+        let snapshots = load(&project).await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].total, 3);
+        assert_eq!(
+            snapshots[0].files.keys().collect::<Vec<_>>(),
+            ["drafts/alpha.md"]
+        );
+    }
+
+    #[test]
+    fn restricted_snapshots_recount_their_totals() {
+        let mut full = snapshot(1, 9, 160);
+        full.files = BTreeMap::from([
+            ("chapters/a.md".to_string(), 100),
+            ("notes/daily/2026-03-01.md".to_string(), 60),
+        ]);
+        let kept = restrict(&[full], &HashSet::from(["chapters/a.md".to_string()]));
+        assert_eq!(kept[0].total, 100);
+        assert_eq!(kept[0].files.len(), 1);
+    }
+
+    #[test]
+    fn the_first_snapshot_is_the_baseline() {
+        let snapshots = [
+            snapshot(1, 9, 1000),
+            snapshot(1, 18, 1300),
+            snapshot(2, 10, 1250),
+            snapshot(4, 10, 1600),
+        ];
+        let days = words_per_day(&snapshots);
+        assert_eq!(days[&date(1)], 300);
+        assert_eq!(days[&date(2)], -50);
+        assert_eq!(days[&date(4)], 350);
+        assert!(words_per_day(&[]).is_empty());
+    }
+
+    #[test]
+    fn total_before_uses_the_previous_day() {
+        let snapshots = [
+            snapshot(1, 9, 100),
+            snapshot(2, 9, 200),
+            snapshot(2, 20, 400),
+        ];
+        assert_eq!(total_before(&snapshots, date(2)), Some(100));
+        assert_eq!(total_before(&snapshots, date(3)), Some(400));
+        assert_eq!(total_before(&snapshots, date(1)), Some(100));
+        assert_eq!(total_before(&[], date(1)), None);
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        // 2026-03-04 is a Wednesday
+        assert_eq!(week_start(date(4)), date(2));
+        assert_eq!(week_start(date(2)), date(2));
+
+        let days = BTreeMap::from([(date(1), 10), (date(2), 20), (date(8), 5), (date(9), 1)]);
+        let weeks = words_per_week(&days);
+        assert_eq!(weeks[&NaiveDate::from_ymd_opt(2026, 2, 23).unwrap()], 10);
+        assert_eq!(weeks[&date(2)], 25);
+        assert_eq!(weeks[&date(9)], 1);
+    }
+
+    #[test]
+    fn streaks_survive_until_the_end_of_today() {
+        let days = BTreeMap::from([
+            (date(1), 100),
+            (date(2), 100),
+            (date(3), 100),
+            (date(5), 50),
+            (date(6), 0),
+            (date(7), 10),
+            (date(8), 20),
+        ]);
+        assert_eq!(streaks(&days, date(8)), (2, 3));
+        // Nothing written yet on the 9th: the streak still stands
+        assert_eq!(streaks(&days, date(9)), (2, 3));
+        assert_eq!(streaks(&days, date(10)), (0, 3));
+    }
+}
@@ -0,0 +1,797 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::frontmatter;
+use super::readability;
+use super::ProjectSettings;
+
+/// How seriously a rule's findings are taken. `Off` disables the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Off => "off",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A problem a rule found, at a 1-based line and character column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// A finding with the rule that made it and the rule's severity
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// A line of the file being linted. Frontmatter, code blocks, HTML comments,
+/// headings and tables aren't prose, so most rules skip them.
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+    pub prose: bool,
+}
+
+/// A style check run over every line of a file
+pub trait Rule {
+    /// Name used in output and in the `lint_rules` setting
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> Severity;
+    fn check(&self, lines: &[Line]) -> Vec<Finding>;
+}
+
+/// The built-in rules, each with the severity the project gives it
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Severity)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::with_rules(&LintOptions::default())
+    }
+}
+
+/// Rule options from the project settings
+#[derive(Debug, Clone, Default)]
+struct LintOptions {
+    max_sentence_words: Option<usize>,
+    quote_style: Option<QuoteStyle>,
+}
+
+impl Linter {
+    /// Build the linter from the project settings. Unknown rule names and
+    /// quote styles are errors, so a typo doesn't silently change the house style.
+    pub fn new(settings: Option<&ProjectSettings>) -> Result<Self> {
+        let quote_style = match settings.and_then(|s| s.quote_style.as_deref()) {
+            None => None,
+            Some("straight") => Some(QuoteStyle::Straight),
+            Some("curly") => Some(QuoteStyle::Curly),
+            Some(other) => bail!(
+                "Unknown quote_style \"{}\" in settings (use \"straight\" or \"curly\")",
+                other
+            ),
+        };
+
+        let mut linter = Self::with_rules(&LintOptions {
+            max_sentence_words: settings.and_then(|s| s.max_sentence_words),
+            quote_style,
+        });
+
+        if let Some(severities) = settings.and_then(|s| s.lint_rules.as_ref()) {
+            for (name, severity) in severities {
+                match linter
+                    .rules
+                    .iter_mut()
+                    .find(|(rule, _)| rule.name() == name)
+                {
+                    Some((_, current)) => *current = *severity,
+                    None => {
+                        let known: Vec<_> =
+                            linter.rules.iter().map(|(rule, _)| rule.name()).collect();
+                        bail!(
+                            "Unknown lint rule \"{}\" in settings (rules: {})",
+                            name,
+                            known.join(", ")
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(linter)
+    }
+
+    fn with_rules(options: &LintOptions) -> Self {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(PassiveVoice),
+            Box::new(Cliches),
+            Box::new(DoubledWords),
+            Box::new(QuoteStyles {
+                style: options.quote_style,
+            }),
+            Box::new(TrailingWhitespace),
+            Box::new(LongSentences {
+                max_words: options.max_sentence_words.unwrap_or(40),
+            }),
+        ];
+
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| {
+                    let severity = rule.default_severity();
+                    (rule, severity)
+                })
+                .collect(),
+        }
+    }
+
+    /// Every rule with its severity for this project
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Severity)> {
+        self.rules
+            .iter()
+            .map(|(rule, severity)| (rule.as_ref(), *severity))
+    }
+
+    /// Run the enabled rules over a file, returning diagnostics in file order
+    pub fn check(&self, content: &str) -> Vec<Diagnostic> {
+        let lines = lines(content);
+        let mut diagnostics: Vec<Diagnostic> = self
+            .rules
+            .iter()
+            .filter(|(_, severity)| *severity != Severity::Off)
+            .flat_map(|(rule, severity)| {
+                rule.check(&lines).into_iter().map(|finding| Diagnostic {
+                    rule: rule.name(),
+                    severity: *severity,
+                    line: finding.line,
+                    column: finding.column,
+                    message: finding.message,
+                })
+            })
+            .collect();
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        diagnostics
+    }
+}
+
+/// Split a file into lines, marking which of them are prose
+pub fn lines(content: &str) -> Vec<Line<'_>> {
+    let body = frontmatter::body(content);
+    let frontmatter_lines = content[..content.len() - body.len()].lines().count();
+
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let mut in_comment = false;
+
+    for (index, text) in content.lines().enumerate() {
+        let trimmed = text.trim();
+        let prose = if index < frontmatter_lines {
+            false
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            false
+        } else if in_comment || trimmed.starts_with("<!--") {
+            in_comment = !trimmed.contains("-->");
+            false
+        } else {
+            !in_code
+                && !trimmed.is_empty()
+                && !trimmed.starts_with('#')
+                && !trimmed.starts_with('|')
+        };
+
+        lines.push(Line {
+            number: index + 1,
+            text,
+            prose,
+        });
+    }
+
+    lines
+}
+
+/// The words of a line with their byte offsets. Inline code is skipped.
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut in_code = false;
+
+    for (index, c) in text.char_indices() {
+        if c == '`' {
+            in_code = !in_code;
+        }
+        let is_word_char = !in_code && (c.is_alphanumeric() || matches!(c, '\'' | '’'));
+        if is_word_char && (c.is_alphanumeric() || start.is_some()) {
+            start.get_or_insert(index);
+        } else if let Some(word_start) = start.take() {
+            words.push((
+                word_start,
+                text[word_start..index].trim_end_matches(['\'', '’']),
+            ));
+        }
+    }
+    if let Some(word_start) = start {
+        words.push((word_start, text[word_start..].trim_end_matches(['\'', '’'])));
+    }
+
+    words
+}
+
+/// 1-based character column of a byte offset
+fn column(text: &str, offset: usize) -> usize {
+    text[..offset].chars().count() + 1
+}
+
+fn prose_lines<'a, 'b>(lines: &'b [Line<'a>]) -> impl Iterator<Item = &'b Line<'a>> {
+    lines.iter().filter(|line| line.prose)
+}
+
+/// Runs of consecutive prose lines. A list item or block quote starts a new
+/// run, so separate items aren't read as one sentence.
+fn paragraphs<'a, 'b>(lines: &'b [Line<'a>]) -> Vec<Paragraph<'a, 'b>> {
+    let mut paragraphs: Vec<Vec<&Line>> = Vec::new();
+    let mut previous: Option<usize> = None;
+
+    for line in prose_lines(lines) {
+        let continues = previous == Some(line.number - 1) && !starts_block(line.text);
+        match paragraphs.last_mut() {
+            Some(paragraph) if continues => paragraph.push(line),
+            _ => paragraphs.push(vec![line]),
+        }
+        previous = Some(line.number);
+    }
+
+    paragraphs.into_iter().map(Paragraph::new).collect()
+}
+
+/// A paragraph's lines joined into one text with single spaces, so sentences,
+/// phrases and doubled words can run across a hard wrap
+struct Paragraph<'a, 'b> {
+    lines: Vec<&'b Line<'a>>,
+    text: String,
+    /// Where each line starts in `text`, and the indentation trimmed from it
+    starts: Vec<(usize, usize)>,
+}
+
+impl<'a, 'b> Paragraph<'a, 'b> {
+    fn new(lines: Vec<&'b Line<'a>>) -> Self {
+        let mut text = String::new();
+        let mut starts = Vec::with_capacity(lines.len());
+        for line in &lines {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let trimmed = line.text.trim();
+            starts.push((text.len(), line.text.trim_end().len() - trimmed.len()));
+            text.push_str(trimmed);
+        }
+        Self {
+            lines,
+            text,
+            starts,
+        }
+    }
+
+    /// The line number and column of a byte offset in the text
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let index = self.starts.partition_point(|(start, _)| *start <= offset) - 1;
+        let (start, indent) = self.starts[index];
+        let line = self.lines[index];
+        (line.number, column(line.text, indent + offset - start))
+    }
+}
+
+/// Whether a line opens a list item or block quote
+pub fn starts_block(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    let numbered = trimmed.trim_start_matches(|c: char| c.is_ascii_digit());
+    trimmed.starts_with('>')
+        || ["- ", "* ", "+ "]
+            .iter()
+            .any(|marker| trimmed.starts_with(marker))
+        || (numbered.len() < trimmed.len()
+            && [". ", ") "]
+                .iter()
+                .any(|marker| numbered.starts_with(marker)))
+}
+
+/// A form of "to be" followed by a past participle, as in "was taken"
+struct PassiveVoice;
+
+const TO_BE: &[&str] = &["am", "is", "are", "was", "were", "be", "been", "being"];
+
+// Common irregular past participles
+const IRREGULAR_PARTICIPLES: &str = "
+    beaten bitten blown broken brought built caught chosen done drawn driven eaten fallen
+    forbidden forgotten forgiven found frozen given gone grown held hidden hit hung hurt kept
+    known laid led left lost made meant met paid put read ridden risen run said seen sent set
+    shaken shown shut sold sought spent spoken stolen struck sung sworn taken taught thrown told
+    torn understood won woken worn written";
+
+// Words ending in -ed that usually describe rather than act after "to be"
+const ED_ADJECTIVES: &str = "
+    aged beloved bored crooked excited hundred interested jagged married naked ragged rugged
+    sacred scared supposed tired used wicked worried wretched";
+
+impl Rule for PassiveVoice {
+    fn name(&self) -> &'static str {
+        "passive-voice"
+    }
+
+    fn description(&self) -> &'static str {
+        "A form of \"to be\" followed by a past participle"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        let is_participle = |word: &str| {
+            let word = word.to_lowercase();
+            IRREGULAR_PARTICIPLES.split_whitespace().any(|w| w == word)
+                || (word.len() > 4
+                    && word.ends_with("ed")
+                    && !word.ends_with("eed")
+                    && !ED_ADJECTIVES.split_whitespace().any(|w| w == word))
+        };
+
+        let mut findings = Vec::new();
+        for line in prose_lines(lines) {
+            let words = words(line.text);
+            for (index, (offset, word)) in words.iter().enumerate() {
+                if !TO_BE.contains(&word.to_lowercase().as_str()) {
+                    continue;
+                }
+
+                // Allow one adverb in between, as in "was quickly taken"
+                let mut next = index + 1;
+                if words
+                    .get(next)
+                    .is_some_and(|(_, w)| w.len() > 4 && w.ends_with("ly"))
+                {
+                    next += 1;
+                }
+                if let Some((end, participle)) = words.get(next) {
+                    let phrase = &line.text[*offset..end + participle.len()];
+                    // Punctuation between the words, as in "was "gone"", breaks the phrase
+                    let joined = phrase.chars().all(|c| {
+                        c.is_alphanumeric() || c.is_whitespace() || matches!(c, '\'' | '’')
+                    });
+                    if joined && is_participle(participle) {
+                        findings.push(Finding {
+                            line: line.number,
+                            column: column(line.text, *offset),
+                            message: format!("\"{}\" may be passive voice", phrase),
+                        });
+                    }
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Overused stock phrases
+struct Cliches;
+
+const CLICHES: &[&str] = &[
+    "a chill ran down",
+    "a matter of time",
+    "all of a sudden",
+    "at the end of the day",
+    "avoid like the plague",
+    "better late than never",
+    "calm before the storm",
+    "cold as ice",
+    "crystal clear",
+    "dead as a doornail",
+    "easier said than done",
+    "every fiber of",
+    "few and far between",
+    "heart of gold",
+    "in the nick of time",
+    "last but not least",
+    "let out a breath",
+    "light as a feather",
+    "only time will tell",
+    "quiet as a mouse",
+    "sent shivers down",
+    "sharp as a tack",
+    "think outside the box",
+    "time stood still",
+    "tip of the iceberg",
+    "without a doubt",
+];
+
+impl Rule for Cliches {
+    fn name(&self) -> &'static str {
+        "cliche"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stock phrases like \"all of a sudden\""
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for paragraph in paragraphs(lines) {
+            // ASCII lowercasing keeps byte offsets the same as the original
+            let lower = paragraph.text.to_ascii_lowercase();
+
+            for cliche in CLICHES {
+                let mut from = 0;
+                while let Some(found) = lower[from..].find(cliche) {
+                    let start = from + found;
+                    let end = start + cliche.len();
+                    let boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+                    if boundary(lower[..start].chars().next_back())
+                        && boundary(lower[end..].chars().next())
+                    {
+                        let (line, column) = paragraph.position(start);
+                        findings.push(Finding {
+                            line,
+                            column,
+                            message: format!("\"{}\" is a cliché", &paragraph.text[start..end]),
+                        });
+                    }
+                    from = end;
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// The same word twice in a row, as in "the the"
+struct DoubledWords;
+
+// Words that are often correctly doubled
+const MAY_DOUBLE: &[&str] = &["had", "that", "no", "so"];
+
+impl Rule for DoubledWords {
+    fn name(&self) -> &'static str {
+        "doubled-word"
+    }
+
+    fn description(&self) -> &'static str {
+        "The same word twice in a row, as in \"the the\""
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for paragraph in paragraphs(lines) {
+            for pair in words(&paragraph.text).windows(2) {
+                let ((first_offset, first), (second_offset, second)) = (pair[0], pair[1]);
+                let between = &paragraph.text[first_offset + first.len()..second_offset];
+                if first.eq_ignore_ascii_case(second)
+                    && between.chars().all(char::is_whitespace)
+                    && !first.chars().all(|c| c.is_numeric())
+                    && !MAY_DOUBLE.contains(&first.to_lowercase().as_str())
+                {
+                    let (line, column) = paragraph.position(second_offset);
+                    findings.push(Finding {
+                        line,
+                        column,
+                        message: format!("\"{}\" is repeated", second),
+                    });
+                }
+            }
+        }
+        findings
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteStyle {
+    Straight,
+    Curly,
+}
+
+impl QuoteStyle {
+    fn name(self) -> &'static str {
+        match self {
+            QuoteStyle::Straight => "straight",
+            QuoteStyle::Curly => "curly",
+        }
+    }
+}
+
+/// Straight and curly quotes mixed in one file. The `quote_style` setting
+/// picks the house style; otherwise the file's most common style wins.
+struct QuoteStyles {
+    style: Option<QuoteStyle>,
+}
+
+impl QuoteStyles {
+    /// Double quotes and apostrophes outside inline code, with their style
+    fn quotes<'a>(lines: &'a [Line]) -> Vec<(&'a Line<'a>, usize, QuoteStyle)> {
+        let mut quotes = Vec::new();
+        for line in prose_lines(lines) {
+            let mut in_code = false;
+            for (offset, c) in line.text.char_indices() {
+                match c {
+                    '`' => in_code = !in_code,
+                    _ if in_code => {}
+                    '"' | '\'' => quotes.push((line, offset, QuoteStyle::Straight)),
+                    '“' | '”' | '‘' | '’' => quotes.push((line, offset, QuoteStyle::Curly)),
+                    _ => {}
+                }
+            }
+        }
+        quotes
+    }
+}
+
+impl Rule for QuoteStyles {
+    fn name(&self) -> &'static str {
+        "quote-style"
+    }
+
+    fn description(&self) -> &'static str {
+        "Straight and curly quotes mixed in one file"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        let quotes = Self::quotes(lines);
+        let curly = quotes
+            .iter()
+            .filter(|(_, _, style)| *style == QuoteStyle::Curly)
+            .count();
+        let expected = self.style.unwrap_or(if curly * 2 > quotes.len() {
+            QuoteStyle::Curly
+        } else {
+            QuoteStyle::Straight
+        });
+        let reason = match self.style {
+            Some(_) => "the house style",
+            None => "most of this file",
+        };
+
+        quotes
+            .into_iter()
+            .filter(|(_, _, style)| *style != expected)
+            .map(|(line, offset, style)| Finding {
+                line: line.number,
+                column: column(line.text, offset),
+                message: format!(
+                    "{} quote where {} uses {} quotes",
+                    style.name(),
+                    reason,
+                    expected.name()
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Spaces or tabs at the end of a line. Exactly two spaces after text are a
+/// markdown line break and are allowed.
+struct TrailingWhitespace;
+
+impl Rule for TrailingWhitespace {
+    fn name(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Spaces or tabs at the end of a line"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        lines
+            .iter()
+            .filter_map(|line| {
+                let trimmed = line.text.trim_end();
+                let trailing = &line.text[trimmed.len()..];
+                let line_break = trailing == "  " && !trimmed.is_empty();
+                (!trailing.is_empty() && !line_break).then(|| Finding {
+                    line: line.number,
+                    column: column(line.text, trimmed.len()),
+                    message: "trailing whitespace".to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Sentences longer than the `max_sentence_words` setting (40 by default)
+struct LongSentences {
+    max_words: usize,
+}
+
+impl Rule for LongSentences {
+    fn name(&self) -> &'static str {
+        "long-sentence"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sentences longer than max_sentence_words (40 by default)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, lines: &[Line]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for paragraph in paragraphs(lines) {
+            for sentence in readability::sentences(&paragraph.text) {
+                let count = readability::words(sentence).len();
+                if count > self.max_words {
+                    // Sentences are slices of the text, so their offset is the pointer difference
+                    let offset = sentence.as_ptr() as usize - paragraph.text.as_ptr() as usize;
+                    let (line, column) = paragraph.position(offset);
+                    findings.push(Finding {
+                        line,
+                        column,
+                        message: format!(
+                            "sentence has {} words (more than {})",
+                            count, self.max_words
+                        ),
+                    });
+                }
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_found(linter: &Linter, content: &str) -> Vec<(&'static str, usize, usize)> {
+        linter
+            .check(content)
+            .into_iter()
+            .map(|d| (d.rule, d.line, d.column))
+            .collect()
+    }
+
+    fn linter_with(settings: serde_json::Value) -> Result<Linter> {
+        let settings: ProjectSettings = serde_json::from_value(settings).unwrap();
+        Linter::new(Some(&settings))
+    }
+
+    #[test]
+    fn only_prose_lines_are_checked() {
+        let content = "---\ntitle: the the\n---\n# The the\n\n```\nthe the\n```\n<!-- the the -->\nIt was the the end.\n";
+        let prose: Vec<_> = lines(content)
+            .iter()
+            .filter(|line| line.prose)
+            .map(|line| line.number)
+            .collect();
+        assert_eq!(prose, [10]);
+        assert_eq!(
+            rules_found(&Linter::default(), content),
+            [("doubled-word", 10, 12)]
+        );
+    }
+
+    #[test]
+    fn passive_voice_allows_one_adverb_and_skips_adjectives() {
+        let found = rules_found(
+            &Linter::default(),
+            "The door was quickly opened. She was tired. It is taken.\n",
+        );
+        assert_eq!(found, [("passive-voice", 1, 10), ("passive-voice", 1, 48)]);
+    }
+
+    #[test]
+    fn cliches_match_whole_words_only() {
+        let found = rules_found(
+            &Linter::default(),
+            "All of a sudden it rained. Overall of a suddenness.\n",
+        );
+        assert_eq!(found, [("cliche", 1, 1)]);
+    }
+
+    #[test]
+    fn cliches_and_doubled_words_are_found_across_a_wrap() {
+        let found = rules_found(
+            &Linter::default(),
+            "It came all of a\n  sudden, and the\nthe rain fell very\nvery hard.\n",
+        );
+        assert_eq!(
+            found,
+            [
+                ("cliche", 1, 9),
+                ("doubled-word", 3, 1),
+                ("doubled-word", 4, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn some_words_may_double() {
+        let found = rules_found(&Linter::default(), "It had had its day, and and more.\n");
+        assert_eq!(found, [("doubled-word", 1, 25)]);
+    }
+
+    #[test]
+    fn mixed_quotes_follow_the_majority_or_the_house_style() {
+        let content = "“Hi,” she said. “Bye.” He said \"no\".\n";
+        let found = rules_found(&Linter::default(), content);
+        assert_eq!(found, [("quote-style", 1, 32), ("quote-style", 1, 35)]);
+
+        let straight = linter_with(serde_json::json!({ "quote_style": "straight" })).unwrap();
+        assert_eq!(rules_found(&straight, content).len(), 4);
+    }
+
+    #[test]
+    fn trailing_whitespace_is_found_except_for_line_breaks() {
+        let found = rules_found(&Linter::default(), "Done. \t\nBreak here  \nend.\n");
+        assert_eq!(found, [("trailing-whitespace", 1, 6)]);
+    }
+
+    #[test]
+    fn long_sentences_are_measured_across_wrapped_lines() {
+        let linter = linter_with(serde_json::json!({ "max_sentence_words": 8 })).unwrap();
+        let content = "Short one. Then a sentence that keeps\ngoing well past the limit\nbefore it ends.\n\nA new paragraph.\n";
+        assert_eq!(rules_found(&linter, content), [("long-sentence", 1, 12)]);
+    }
+
+    #[test]
+    fn list_items_are_not_one_sentence() {
+        let linter = linter_with(serde_json::json!({ "max_sentence_words": 4 })).unwrap();
+        let content = "- one two three\n- four five six\n1. seven eight\n";
+        assert!(rules_found(&linter, content).is_empty());
+    }
+
+    #[test]
+    fn settings_change_severities_and_reject_typos() {
+        let linter = linter_with(serde_json::json!({
+            "lint_rules": { "doubled-word": "off", "cliche": "error" }
+        }))
+        .unwrap();
+        let diagnostics = linter.check("The the calm before the storm.\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        let typo = linter_with(serde_json::json!({ "lint_rules": { "cliches": "off" } }));
+        assert!(typo.is_err_and(|e| e.to_string().contains("Unknown lint rule")));
+        let style = linter_with(serde_json::json!({ "quote_style": "smart" }));
+        assert!(style.is_err());
+    }
+}
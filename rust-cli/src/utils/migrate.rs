@@ -0,0 +1,179 @@
+use serde_json::{Map, Value};
+
+/// Version of the `writers.config.json` layout written by this CLI. Configs
+/// without a `schema_version` were written by the Node CLI.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Whether a raw config predates the current schema
+pub fn needs_migration(config: &Value) -> bool {
+    config
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .is_none_or(|version| version < SCHEMA_VERSION)
+}
+
+/// Bring a raw config up to the current schema in place, returning a
+/// description of each change made
+pub fn migrate(config: &mut Value) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    let Some(root) = config.as_object_mut() else {
+        return changes;
+    };
+
+    if let Some(goal) = root.shift_remove("wordGoal") {
+        if !root.contains_key("word_goal") {
+            root.insert("word_goal".to_string(), goal);
+            changes.push("wordGoal → word_goal".to_string());
+        }
+    }
+
+    if !root.contains_key("type") {
+        let project_type = infer_type(root);
+        root.insert("type".to_string(), Value::String(project_type.to_string()));
+        changes.push(format!("added type \"{}\"", project_type));
+    }
+
+    for (key, default) in [("version", "1.0.0"), ("created", "")] {
+        if !root.contains_key(key) {
+            root.insert(key.to_string(), Value::String(default.to_string()));
+            changes.push(format!("added {}", key));
+        }
+    }
+
+    if let Some(Value::Object(settings)) = root.get_mut("settings") {
+        migrate_settings(settings, &mut changes);
+    }
+
+    if needs_migration(config) {
+        config["schema_version"] = Value::from(SCHEMA_VERSION);
+        changes.push(format!("schema_version {}", SCHEMA_VERSION));
+    }
+
+    changes
+}
+
+/// Node configs have no type; blog projects are recognisable by their `posts` directory
+fn infer_type(root: &Map<String, Value>) -> &'static str {
+    let structure = root.get("structure").and_then(Value::as_object);
+    match structure {
+        Some(structure) if structure.contains_key("posts") => "blog",
+        Some(structure) if !structure.contains_key("chapters") => "short-story",
+        _ => "novel",
+    }
+}
+
+/// Flatten `settings.editor` and rename camelCase keys to snake_case
+fn migrate_settings(settings: &mut Map<String, Value>, changes: &mut Vec<String>) {
+    if let Some(Value::Object(editor)) = settings.shift_remove("editor") {
+        for (key, value) in editor {
+            let new_key = snake_case(&key);
+            changes.push(format!("settings.editor.{} → settings.{}", key, new_key));
+            settings.entry(new_key).or_insert(value);
+        }
+    }
+
+    let camel_keys: Vec<String> = settings
+        .keys()
+        .filter(|key| key.chars().any(|c| c.is_ascii_uppercase()))
+        .cloned()
+        .collect();
+
+    for key in camel_keys {
+        if let Some(value) = settings.shift_remove(&key) {
+            let new_key = snake_case(&key);
+            changes.push(format!("settings.{} → settings.{}", key, new_key));
+            settings.entry(new_key).or_insert(value);
+        }
+    }
+}
+
+fn snake_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn node_config_is_migrated() {
+        let mut config = json!({
+            "name": "Book",
+            "wordGoal": 80000,
+            "structure": { "chapters": "chapters" },
+            "settings": {
+                "autoSave": true,
+                "editor": { "theme": "dark", "tabSize": 4 }
+            }
+        });
+        assert!(needs_migration(&config));
+
+        let changes = migrate(&mut config);
+
+        assert_eq!(config["word_goal"], 80000);
+        assert!(config.get("wordGoal").is_none());
+        assert_eq!(config["type"], "novel");
+        assert_eq!(config["version"], "1.0.0");
+        assert_eq!(config["created"], "");
+        assert_eq!(
+            config["settings"],
+            json!({ "auto_save": true, "theme": "dark", "tab_size": 4 })
+        );
+        assert_eq!(config["schema_version"], SCHEMA_VERSION);
+        assert!(changes.contains(&"wordGoal → word_goal".to_string()));
+        assert!(!needs_migration(&config));
+    }
+
+    #[test]
+    fn migrating_twice_changes_nothing() {
+        let mut config = json!({ "name": "Book", "wordGoal": 1000 });
+        migrate(&mut config);
+        let migrated = config.clone();
+
+        assert!(migrate(&mut config).is_empty());
+        assert_eq!(config, migrated);
+    }
+
+    #[test]
+    fn existing_keys_win_over_renamed_ones() {
+        let mut config = json!({
+            "type": "novel",
+            "word_goal": 5,
+            "wordGoal": 9,
+            "settings": { "autoSave": true, "auto_save": false }
+        });
+        migrate(&mut config);
+
+        assert_eq!(config["word_goal"], 5);
+        assert_eq!(config["settings"], json!({ "auto_save": false }));
+    }
+
+    #[test]
+    fn project_type_from_structure() {
+        let infer = |config: Value| infer_type(config.as_object().unwrap());
+        assert_eq!(infer(json!({ "structure": { "posts": "posts" } })), "blog");
+        assert_eq!(
+            infer(json!({ "structure": { "drafts": "drafts" } })),
+            "short-story"
+        );
+        assert_eq!(infer(json!({ "structure": { "chapters": "ch" } })), "novel");
+        assert_eq!(infer(json!({})), "novel");
+    }
+
+    #[test]
+    fn snake_case_keys() {
+        assert_eq!(snake_case("autoSaveInterval"), "auto_save_interval");
+        assert_eq!(snake_case("theme"), "theme");
+    }
+}
@@ -1,3 +1,21 @@
+pub mod analysis;
+pub mod daily;
+pub mod frontmatter;
+pub mod goals;
+pub mod history;
+pub mod lint;
+pub mod migrate;
+pub mod order;
+pub mod output;
+pub mod pomodoros;
 pub mod project;
+pub mod readability;
+pub mod sprints;
+pub mod store;
+pub mod submissions;
+pub mod templates;
+#[cfg(test)]
+pub mod test_support;
+pub mod wordcount;
 
 pub use project::*;
@@ -0,0 +1,138 @@
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// Compare strings so that runs of digits compare by value: `chapter-2` sorts
+/// before `chapter-10`
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chunks = chunks(a);
+    let mut b_chunks = chunks(b);
+
+    loop {
+        match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ordering = match (is_number(x), is_number(y)) {
+                    (true, true) => {
+                        let x = x.trim_start_matches('0');
+                        let y = y.trim_start_matches('0');
+                        x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+                    }
+                    _ => x.to_lowercase().cmp(&y.to_lowercase()),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Split a string into alternating runs of digits and non-digits
+fn chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let digit = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+fn is_number(chunk: &str) -> bool {
+    chunk.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Sort paths naturally by their full path
+pub fn sort_paths(paths: &mut [PathBuf]) {
+    paths.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+}
+
+/// Whether a manifest entry names this file, with or without the `.md`
+/// extension. Entries may include folders, e.g. `part-1/chapter-01.md`.
+pub fn manifest_matches(entry: &str, path: &Path) -> bool {
+    path.ends_with(entry) || path.with_extension("").ends_with(entry)
+}
+
+/// Put chapters in reading order. Files named in the manifest come first, in
+/// manifest order; the rest follow by their frontmatter `order`, then by name.
+pub fn arrange(mut files: Vec<(PathBuf, Option<i64>)>, manifest: &[String]) -> Vec<PathBuf> {
+    let mut ordered = Vec::with_capacity(files.len());
+
+    for entry in manifest {
+        if let Some(index) = files
+            .iter()
+            .position(|(path, _)| manifest_matches(entry, path))
+        {
+            ordered.push(files.remove(index).0);
+        }
+    }
+
+    files.sort_by(|(a_path, a_order), (b_path, b_order)| {
+        let a_order = a_order.unwrap_or(i64::MAX);
+        let b_order = b_order.unwrap_or(i64::MAX);
+        a_order
+            .cmp(&b_order)
+            .then_with(|| natural_cmp(&a_path.to_string_lossy(), &b_path.to_string_lossy()))
+    });
+
+    ordered.extend(files.into_iter().map(|(path, _)| path));
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn numbers_compare_by_value() {
+        assert_eq!(natural_cmp("chapter-2", "chapter-10"), Ordering::Less);
+        assert_eq!(natural_cmp("chapter-02", "chapter-2"), Ordering::Less);
+        assert_eq!(natural_cmp("Chapter-3", "chapter-3b"), Ordering::Less);
+        assert_eq!(natural_cmp("b", "A"), Ordering::Greater);
+
+        let mut files = paths(&["ch-10.md", "ch-9.md", "ch-1.md"]);
+        sort_paths(&mut files);
+        assert_eq!(files, paths(&["ch-1.md", "ch-9.md", "ch-10.md"]));
+    }
+
+    #[test]
+    fn manifest_entries_may_skip_the_extension() {
+        let path = Path::new("/book/chapters/part-1/chapter-01.md");
+        assert!(manifest_matches("chapter-01", path));
+        assert!(manifest_matches("part-1/chapter-01.md", path));
+        assert!(!manifest_matches("part-2/chapter-01", path));
+        assert!(!manifest_matches("apter-01", path));
+    }
+
+    #[test]
+    fn manifest_then_order_key_then_name() {
+        let files = vec![
+            (PathBuf::from("c/ch-10.md"), None),
+            (PathBuf::from("c/ch-2.md"), None),
+            (PathBuf::from("c/prologue.md"), Some(0)),
+            (PathBuf::from("c/epilogue.md"), None),
+            (PathBuf::from("c/ch-1.md"), None),
+        ];
+        let manifest = ["epilogue".to_string(), "missing".to_string()];
+        assert_eq!(
+            arrange(files, &manifest),
+            paths(&[
+                "c/epilogue.md",
+                "c/prologue.md",
+                "c/ch-1.md",
+                "c/ch-2.md",
+                "c/ch-10.md"
+            ])
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+/// How commands present their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colors and icons, for people at a terminal
+    Color,
+    /// Text without colors or icons, for screen readers and logs
+    Plain,
+    /// A JSON document on stdout, for scripts; messages go to stderr
+    Json,
+}
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Set the output format for the rest of the run. Without an explicit
+/// format, a set `NO_COLOR` selects plain output.
+pub fn init(format: Option<OutputFormat>) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let format = format.unwrap_or(if no_color {
+        OutputFormat::Plain
+    } else {
+        OutputFormat::Color
+    });
+
+    if format != OutputFormat::Color {
+        colored::control::set_override(false);
+    }
+    let _ = FORMAT.set(format);
+}
+
+pub fn format() -> OutputFormat {
+    FORMAT.get().copied().unwrap_or(OutputFormat::Color)
+}
+
+pub fn is_json() -> bool {
+    format() == OutputFormat::Json
+}
+
+/// Print a line of human-readable output. Plain mode drops the icons, and
+/// JSON mode sends the line to stderr so stdout stays valid JSON. Write
+/// errors are ignored, so piping into `head` doesn't panic.
+pub fn line(text: &str) {
+    let _ = match format() {
+        OutputFormat::Color => writeln!(io::stdout(), "{}", text),
+        OutputFormat::Plain => writeln!(io::stdout(), "{}", strip_icons(text)),
+        OutputFormat::Json => writeln!(io::stderr(), "{}", strip_icons(text)),
+    };
+}
+
+/// Print a command's result as pretty JSON on stdout
+pub fn json<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    let _ = writeln!(io::stdout(), "{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Remove emoji along with the space after them, e.g. "📊 Stats" → "Stats"
+pub fn strip_icons(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !is_icon(c) {
+            out.push(c);
+            continue;
+        }
+
+        while chars.peek().is_some_and(|&next| is_icon(next)) {
+            chars.next();
+        }
+        if out.is_empty() || out.ends_with(char::is_whitespace) {
+            while chars.peek().is_some_and(|next| *next == ' ') {
+                chars.next();
+            }
+        }
+    }
+
+    out
+}
+
+fn is_icon(c: char) -> bool {
+    // Check marks carry meaning in plain text too
+    c != '✓'
+        && matches!(c,
+            '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{FE0F}'
+            | '\u{200D}'
+        )
+}
+
+/// `println!` for command output, going through [`line`] so it follows the
+/// output format
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::utils::output::line("")
+    };
+    ($($arg:tt)*) => {
+        $crate::utils::output::line(&format!($($arg)*))
+    };
+}
+
+/// "1 day", "3 days": the count with its unit, plural unless it is one
+pub fn plural(count: impl std::fmt::Display, unit: &str) -> String {
+    let count = count.to_string();
+    if count == "1" {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// Shorten `text` to at most `width` characters, ending with "…" when cut
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut short: String = text.chars().take(width.saturating_sub(1)).collect();
+        short.push('…');
+        short
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icons_are_stripped_with_their_space() {
+        assert_eq!(strip_icons("📊 Project Statistics"), "Project Statistics");
+        assert_eq!(strip_icons("⚠️  No chapters found."), "No chapters found.");
+        assert_eq!(strip_icons("  • 👩‍💻 coder"), "  • coder");
+        assert_eq!(strip_icons("Done 🎉"), "Done ");
+        assert_eq!(strip_icons("Saved ✓"), "Saved ✓");
+        assert_eq!(strip_icons("plain — text…"), "plain — text…");
+    }
+
+    #[test]
+    fn counts_come_with_their_unit() {
+        assert_eq!(plural(1, "day"), "1 day");
+        assert_eq!(plural(0usize, "word"), "0 words");
+        assert_eq!(plural(-3i64, "day"), "-3 days");
+    }
+
+    #[test]
+    fn text_is_truncated_with_an_ellipsis() {
+        assert_eq!(truncate("chapter-01.md", 20), "chapter-01.md");
+        assert_eq!(truncate("chapter-01.md", 13), "chapter-01.md");
+        assert_eq!(truncate("chapter-01.md", 8), "chapter…");
+        assert_eq!(truncate("é-long", 1), "…");
+        assert_eq!(truncate("anything", 0), "…");
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{store, ProjectManager};
+
+/// Completed Pomodoros, one JSON record per line, relative to the project root
+pub const POMODOROS_FILE: &str = ".writers/pomodoros.jsonl";
+
+/// One finished Pomodoro work session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PomodoroRecord {
+    /// When the work session ended
+    pub time: DateTime<Local>,
+    /// The file being edited, relative to the project root
+    pub file: String,
+    pub minutes: u32,
+}
+
+/// Pomodoros and focused minutes for one file
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct FileFocus {
+    pub pomodoros: usize,
+    pub minutes: u32,
+}
+
+pub async fn append(project: &ProjectManager, record: &PomodoroRecord) -> Result<()> {
+    store::append_jsonl(project, POMODOROS_FILE, record).await
+}
+
+/// Read the Pomodoro log, oldest first. Lines that can't be parsed are skipped.
+pub async fn load(project: &ProjectManager) -> Result<Vec<PomodoroRecord>> {
+    store::load_jsonl(project, POMODOROS_FILE).await
+}
+
+/// Totals per file, keyed by path relative to the project root
+pub fn per_file(records: &[PomodoroRecord]) -> BTreeMap<String, FileFocus> {
+    let mut files: BTreeMap<String, FileFocus> = BTreeMap::new();
+    for record in records {
+        let focus = files.entry(record.file.clone()).or_default();
+        focus.pomodoros += 1;
+        focus.minutes += record.minutes;
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(file: &str, minutes: u32) -> PomodoroRecord {
+        PomodoroRecord {
+            time: Local::now(),
+            file: file.to_string(),
+            minutes,
+        }
+    }
+
+    #[test]
+    fn totals_per_file() {
+        let files = per_file(&[
+            record("drafts/a.md", 25),
+            record("drafts/b.md", 25),
+            record("drafts/a.md", 50),
+        ]);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files["drafts/a.md"].pomodoros, 2);
+        assert_eq!(files["drafts/a.md"].minutes, 75);
+        assert_eq!(files["drafts/b.md"].pomodoros, 1);
+    }
+}
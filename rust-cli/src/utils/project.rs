@@ -1,8 +1,19 @@
 use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use colored::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tokio::fs as async_fs;
 
+use super::frontmatter::{self, ContentMeta, FieldProblem};
+use super::lint::Severity;
+use super::migrate;
+use super::order;
+use super::wordcount;
+use crate::outln;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub name: String,
@@ -10,21 +21,143 @@ pub struct ProjectConfig {
     pub r#type: String, // "novel", "short-story", "simple-short-story", "blog"
     pub version: String,
     pub created: String,
+    /// Config layout version; see `migrate::SCHEMA_VERSION`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub settings: Option<ProjectSettings>,
+    /// Target length of the whole project in words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_goal: Option<usize>,
+    /// Date the word goal should be reached by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<NaiveDate>,
+    /// Words to write each day, instead of the pace the deadline requires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_goal: Option<usize>,
+    /// Reading order of chapter files, overriding their names and `order` keys
+    #[serde(
+        default,
+        alias = "chapterOrder",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub chapter_order: Option<Vec<String>>,
+    /// Directory overrides, e.g. `"chapters": "manuscript/chapters"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structure: Option<BTreeMap<String, String>>,
+    /// BCP 47 language tag used for ebook metadata, e.g. "en" or "en-GB"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isbn: Option<String>,
+    /// Path to a cover image (JPEG or PNG), relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    /// Author contact details printed on submission manuscripts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<ContactInfo>,
+    /// Keys this CLI doesn't use, kept so saving doesn't drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ProjectConfig {
+    /// Directory for a kind of content, honoring the `structure` mapping
+    pub fn dir(&self, key: &str) -> PathBuf {
+        self.structure
+            .as_ref()
+            .and_then(|structure| structure.get(key))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(key))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContactInfo {
+    /// Legal name, if different from the byline in `author`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legal_name: Option<String>,
+    /// Postal address, one entry per line
+    #[serde(default)]
+    pub address: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_editor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auto_save: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auto_save_interval: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub show_word_count: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub show_reading_time: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tab_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wrap_text: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub typewriter_mode: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub typewriter_position: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub typewriter_focus_lines: Option<u32>,
+    /// Font for submission manuscripts: "courier" or "times"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manuscript_font: Option<String>,
+    /// PDF page size: a trim like "6x9" (inches), "letter", "a4" or "a5"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_trim_size: Option<String>,
+    /// PDF font family: "times", "helvetica" or "courier"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_font: Option<String>,
+    /// PDF body font size in points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_font_size: Option<f32>,
+    /// Words left out of `analyze words` frequencies, replacing the built-in list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopwords: Option<Vec<String>>,
+    /// Crutch words flagged by `analyze words`, replacing the built-in list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crutch_words: Option<Vec<String>>,
+    /// Filter words flagged by `analyze words`, replacing the built-in list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter_words: Option<Vec<String>>,
+    /// Severity of each lint rule, e.g. `"passive-voice": "off"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lint_rules: Option<BTreeMap<String, Severity>>,
+    /// Longest sentence, in words, before `long-sentence` flags it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sentence_words: Option<usize>,
+    /// House quote style for `quote-style`: "straight" or "curly"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_style: Option<String>,
+    /// Lengths of the editor's Pomodoro work sessions and breaks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pomodoro: Option<PomodoroSettings>,
+    /// Settings this CLI doesn't use, e.g. the Node CLI's `backups`
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Pomodoro timer settings; anything left out uses the classic 25/5/15
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PomodoroSettings {
+    pub work_minutes: Option<u32>,
+    pub short_break_minutes: Option<u32>,
+    pub long_break_minutes: Option<u32>,
+    /// Work sessions before a long break
+    pub long_break_every: Option<u32>,
+    /// Stop edits to the text during breaks
+    pub block_typing: Option<bool>,
 }
 
 impl Default for ProjectSettings {
@@ -40,38 +173,265 @@ impl Default for ProjectSettings {
             typewriter_mode: Some(false),
             typewriter_position: Some(0.66),
             typewriter_focus_lines: Some(1),
+            manuscript_font: Some("courier".to_string()),
+            pdf_trim_size: Some("6x9".to_string()),
+            pdf_font: Some("times".to_string()),
+            pdf_font_size: Some(11.0),
+            stopwords: None,
+            crutch_words: None,
+            filter_words: None,
+            lint_rules: None,
+            max_sentence_words: None,
+            quote_style: None,
+            pomodoro: None,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// A content file with its parsed frontmatter and prose
+#[derive(Debug, Clone)]
+pub struct ContentFile {
+    pub path: PathBuf,
+    pub meta: ContentMeta,
+    /// Text after the frontmatter block
+    pub body: String,
+}
+
+impl ContentFile {
+    pub fn word_count(&self) -> usize {
+        wordcount::count_markdown(&self.body)
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+    }
+}
+
+/// A content file as reported in JSON output
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSummary {
+    pub path: String,
+    pub words: usize,
+    pub characters: usize,
+    #[serde(flatten)]
+    pub meta: ContentMeta,
+}
+
+/// A part or act of a manuscript: consecutive chapters sharing a part title.
+/// Chapters outside any part are grouped under `None`.
+#[derive(Debug, Clone)]
+pub struct Part {
+    pub title: Option<String>,
+    pub chapters: Vec<PathBuf>,
+}
+
+/// Turn a file or directory name like `part-1_the-north` into `Part 1 The North`
+pub fn title_from_name(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Folders that hold short stories, relative to the project root
+pub const STORY_DIRS: &[&str] = &["shortstories", "short", "stories", "drafts"];
+
+/// Turn a title into a lowercase, hyphenated file name stem
+pub fn slugify(text: &str) -> String {
+    let slug: String = text
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The kinds of content `writers new` can create
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    Chapter,
+    Scene,
+    Character,
+    Note,
+    Story,
+    BlogPost,
+}
+
+impl ContentType {
+    pub const ALL: [ContentType; 6] = [
+        ContentType::Chapter,
+        ContentType::Scene,
+        ContentType::Character,
+        ContentType::Note,
+        ContentType::Story,
+        ContentType::BlogPost,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "chapter" | "chapters" => Some(Self::Chapter),
+            "scene" | "scenes" => Some(Self::Scene),
+            "character" | "characters" => Some(Self::Character),
+            "note" | "notes" => Some(Self::Note),
+            "story" | "stories" | "shortstory" | "short-story" => Some(Self::Story),
+            "blogpost" | "blog-post" | "post" | "blog" => Some(Self::BlogPost),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Chapter => "chapter",
+            Self::Scene => "scene",
+            Self::Character => "character",
+            Self::Note => "note",
+            Self::Story => "story",
+            Self::BlogPost => "blogpost",
+        }
+    }
+
+    /// Chapters and scenes are numbered (`chapter-07.md`); everything else is named
+    pub fn is_numbered(self) -> bool {
+        matches!(self, Self::Chapter | Self::Scene)
+    }
+
+    /// Directory this content lives in for the given project type
+    pub fn directory(self, project_type: &str) -> &'static str {
+        match self {
+            Self::Chapter => "chapters",
+            Self::Scene => "scenes",
+            Self::Character => "characters",
+            Self::Note => "notes",
+            Self::Story if project_type == "short-story" => "shortstories",
+            Self::Story | Self::BlogPost => "drafts",
         }
     }
 }
 
-pub struct ProjectManager;
+const CONFIG_FILE: &str = "writers.config.json";
+
+/// Access to a project's files, with every path resolved against the project root
+#[derive(Debug, Clone)]
+pub struct ProjectManager {
+    root: PathBuf,
+}
 
 impl ProjectManager {
-    /// Check if current directory is a Writers project
-    pub fn is_writers_project() -> bool {
-        Path::new("writers.config.json").exists()
+    /// A project rooted at the given directory, which may not be initialized yet
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Find the project containing the current directory by walking up to the
+    /// nearest writers.config.json. Falls back to the current directory.
+    pub fn discover() -> Result<Self> {
+        let cwd = std::env::current_dir()?;
+        Ok(Self::discover_from(&cwd).unwrap_or_else(|| Self::at(cwd)))
+    }
+
+    /// Find the nearest directory at or above `start` containing writers.config.json
+    pub fn discover_from(start: &Path) -> Option<Self> {
+        start
+            .ancestors()
+            .find(|dir| dir.join(CONFIG_FILE).is_file())
+            .map(Self::at)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a project-relative path
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    /// A path for display, relative to the project root when inside it
+    pub fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Summarize a content file for JSON output
+    pub fn summarize(&self, file: &ContentFile) -> FileSummary {
+        FileSummary {
+            path: self.display_path(&file.path),
+            words: file.word_count(),
+            characters: file.body.chars().count(),
+            meta: file.meta.clone(),
+        }
+    }
+
+    /// Check if the project root holds a Writers project
+    pub fn is_writers_project(&self) -> bool {
+        self.path(CONFIG_FILE).exists()
     }
 
     /// Get the project configuration
-    pub async fn get_config() -> Result<ProjectConfig> {
-        if !Self::is_writers_project() {
+    pub async fn get_config(&self) -> Result<ProjectConfig> {
+        if !self.is_writers_project() {
             return Err(anyhow!("Not a Writers project"));
         }
 
-        let config_content = async_fs::read_to_string("writers.config.json").await?;
-        let config: ProjectConfig = serde_json::from_str(&config_content)?;
-        Ok(config)
+        let mut raw = self.get_raw_config().await?;
+        // Older configs are upgraded in memory; `writers config migrate` saves the result
+        migrate::migrate(&mut raw);
+
+        serde_json::from_value(raw).map_err(|e| anyhow!("Invalid writers.config.json: {}", e))
+    }
+
+    /// Read the configuration as plain JSON, without any migration
+    pub async fn get_raw_config(&self) -> Result<serde_json::Value> {
+        let config_content = async_fs::read_to_string(self.path(CONFIG_FILE)).await?;
+        Ok(serde_json::from_str(&config_content)?)
+    }
+
+    /// Write plain JSON to writers.config.json as it is, for changes that
+    /// shouldn't migrate the rest of the file
+    pub async fn save_raw_config(&self, raw: &serde_json::Value) -> Result<()> {
+        async_fs::write(self.path(CONFIG_FILE), serde_json::to_string_pretty(raw)?).await?;
+        Ok(())
     }
 
-    /// Save the project configuration
-    pub async fn save_config(config: &ProjectConfig) -> Result<()> {
+    /// Save the project configuration. A config from an older version is
+    /// left alone: `writers config migrate` rewrites it and keeps a backup.
+    pub async fn save_config(&self, config: &ProjectConfig) -> Result<()> {
+        if self.is_writers_project() && migrate::needs_migration(&self.get_raw_config().await?) {
+            return Err(anyhow!(
+                "writers.config.json is from an older version of Writers. Run \"writers config migrate\" first."
+            ));
+        }
+
         let config_json = serde_json::to_string_pretty(config)?;
-        async_fs::write("writers.config.json", config_json).await?;
+        async_fs::write(self.path(CONFIG_FILE), config_json).await?;
         Ok(())
     }
 
     /// Create a new project configuration
     pub async fn create_project(
+        &self,
         name: String,
         author: String,
         project_type: String,
@@ -82,145 +442,254 @@ impl ProjectManager {
             r#type: project_type,
             version: "1.0.0".to_string(),
             created: chrono::Utc::now().to_rfc3339(),
+            schema_version: Some(migrate::SCHEMA_VERSION),
             settings: Some(ProjectSettings::default()),
+            word_goal: None,
+            deadline: None,
+            daily_goal: None,
+            chapter_order: None,
+            structure: None,
+            language: None,
+            isbn: None,
+            cover: None,
+            contact: None,
+            extra: Default::default(),
         };
 
-        Self::save_config(&config).await?;
+        self.save_config(&config).await?;
         Ok(config)
     }
 
-    /// Get all chapters in the project
-    pub async fn get_chapters() -> Result<Vec<PathBuf>> {
-        let mut chapters = Vec::new();
+    /// Directory for a kind of content, honoring the config's `structure` mapping
+    pub async fn content_dir(&self, key: &str) -> PathBuf {
+        match self.get_config().await {
+            Ok(config) => self.path(config.dir(key)),
+            Err(_) => self.path(key),
+        }
+    }
+
+    /// Markdown files in a directory and its subfolders (part folders, say),
+    /// skipping hidden ones, in natural path order
+    pub async fn markdown_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+
+        // Skip hidden entries like `.git`
+        while let Some(dir) = pending.pop() {
+            if !dir.is_dir() {
+                continue;
+            }
 
-        if Path::new("chapters").exists() {
-            let mut entries = async_fs::read_dir("chapters").await?;
+            let mut entries = async_fs::read_dir(&dir).await?;
             while let Some(entry) = entries.next_entry().await? {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                    chapters.push(entry.path());
+                let path = entry.path();
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+
+                if entry.file_type().await?.is_dir() {
+                    pending.push(path);
+                } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
+                    files.push(path);
                 }
             }
         }
 
-        chapters.sort();
-        Ok(chapters)
+        order::sort_paths(&mut files);
+        Ok(files)
     }
 
-    /// Get all scenes in the project
-    pub async fn get_scenes() -> Result<Vec<PathBuf>> {
-        let mut scenes = Vec::new();
+    /// Get all chapters in reading order: the config's `chapter_order` first,
+    /// then frontmatter `order` keys, then natural file name order
+    pub async fn get_chapters(&self) -> Result<Vec<PathBuf>> {
+        let files = self
+            .markdown_files(&self.content_dir("chapters").await)
+            .await?;
 
-        if Path::new("scenes").exists() {
-            let mut entries = async_fs::read_dir("scenes").await?;
-            while let Some(entry) = entries.next_entry().await? {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                    scenes.push(entry.path());
-                }
-            }
+        let mut keyed = Vec::with_capacity(files.len());
+        for file in files {
+            let order = match self.read_content(&file).await {
+                Ok(content) => content.meta.order,
+                Err(_) => None,
+            };
+            keyed.push((file, order));
         }
 
-        scenes.sort();
-        Ok(scenes)
+        let manifest = match self.get_config().await {
+            Ok(config) => config.chapter_order.unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+
+        Ok(order::arrange(keyed, &manifest))
     }
 
-    /// Get all characters in the project
-    pub async fn get_characters() -> Result<Vec<PathBuf>> {
-        let mut characters = Vec::new();
+    /// Get all chapters grouped into parts, in reading order
+    pub async fn get_parts(&self) -> Result<Vec<Part>> {
+        let chapters = self.get_chapters().await?;
+        Ok(self.group_parts(&chapters).await)
+    }
 
-        if Path::new("characters").exists() {
-            let mut entries = async_fs::read_dir("characters").await?;
-            while let Some(entry) = entries.next_entry().await? {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                    characters.push(entry.path());
-                }
+    /// Group chapters (already in order) into parts. A chapter's part comes
+    /// from its frontmatter `part`, then its top-level folder under `chapters/`.
+    pub async fn group_parts(&self, chapters: &[PathBuf]) -> Vec<Part> {
+        let chapters_dir = self.content_dir("chapters").await;
+        let mut parts: Vec<Part> = Vec::new();
+
+        for chapter in chapters {
+            let from_meta = match self.read_content(chapter).await {
+                Ok(content) => content.meta.part,
+                Err(_) => None,
+            };
+            let title = from_meta.or_else(|| {
+                let relative = chapter.strip_prefix(&chapters_dir).ok()?;
+                let mut components = relative.components();
+                let folder = components.next()?;
+                // Files directly in `chapters/` have no folder part
+                components.next()?;
+                Some(title_from_name(&folder.as_os_str().to_string_lossy()))
+            });
+
+            match parts.last_mut() {
+                Some(part) if part.title == title => part.chapters.push(chapter.clone()),
+                _ => parts.push(Part {
+                    title,
+                    chapters: vec![chapter.clone()],
+                }),
             }
         }
 
-        characters.sort();
-        Ok(characters)
+        parts
+    }
+
+    /// Get all scenes in the project
+    pub async fn get_scenes(&self) -> Result<Vec<PathBuf>> {
+        self.markdown_files(&self.content_dir("scenes").await).await
+    }
+
+    /// Get all characters in the project
+    pub async fn get_characters(&self) -> Result<Vec<PathBuf>> {
+        self.markdown_files(&self.content_dir("characters").await)
+            .await
     }
 
     /// Get all short stories in the project
-    pub async fn get_short_stories() -> Result<Vec<PathBuf>> {
+    pub async fn get_short_stories(&self) -> Result<Vec<PathBuf>> {
         let mut stories = Vec::new();
 
         // Check multiple possible directories
-        let story_dirs = ["shortstories", "short", "stories", "drafts"];
-
-        for dir in &story_dirs {
-            if Path::new(dir).exists() {
-                let mut entries = async_fs::read_dir(dir).await?;
-                while let Some(entry) = entries.next_entry().await? {
-                    if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                        stories.push(entry.path());
-                    }
-                }
+        let mut seen = Vec::new();
+        for dir in STORY_DIRS {
+            let dir = self.content_dir(dir).await;
+            if !seen.contains(&dir) {
+                stories.extend(self.markdown_files(&dir).await?);
+                seen.push(dir);
             }
         }
 
-        stories.sort();
+        order::sort_paths(&mut stories);
         Ok(stories)
     }
 
-    /// Get all blog posts in the project
-    pub async fn get_blog_posts() -> Result<Vec<PathBuf>> {
-        let mut posts = Vec::new();
+    /// Get stories moved to the archive folder by `writers story archive`
+    pub async fn get_archived_stories(&self) -> Result<Vec<PathBuf>> {
+        self.markdown_files(&self.content_dir("archive").await)
+            .await
+    }
 
-        if Path::new("drafts").exists() {
-            let mut entries = async_fs::read_dir("drafts").await?;
-            while let Some(entry) = entries.next_entry().await? {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                    posts.push(entry.path());
-                }
-            }
+    /// Find a story by exact or partial file name, looking in the story
+    /// folders before the archive
+    pub async fn find_story(&self, name: &str) -> Result<Option<PathBuf>> {
+        let mut stories = self.get_short_stories().await?;
+        stories.extend(self.get_archived_stories().await?);
+        let name_lower = name.to_lowercase();
+
+        let stem_of = |file: &PathBuf| {
+            file.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|stem| stem.to_lowercase())
+                .unwrap_or_default()
+        };
+
+        let found = stories
+            .iter()
+            .find(|file| stem_of(file) == name_lower || stem_of(file) == slugify(name))
+            .or_else(|| {
+                stories
+                    .iter()
+                    .find(|file| stem_of(file).contains(&name_lower))
+            });
+
+        Ok(found.cloned())
+    }
+
+    /// Get all blog posts in the project
+    pub async fn get_blog_posts(&self) -> Result<Vec<PathBuf>> {
+        let mut posts = self
+            .markdown_files(&self.content_dir("drafts").await)
+            .await?;
+
+        // Node-era blog projects keep posts in a `posts` directory
+        let config = self.get_config().await.ok();
+        if let Some(dir) = config
+            .as_ref()
+            .and_then(|c| c.structure.as_ref())
+            .and_then(|structure| structure.get("posts"))
+        {
+            posts.extend(self.markdown_files(&self.path(dir)).await?);
         }
 
-        posts.sort();
+        order::sort_paths(&mut posts);
         Ok(posts)
     }
 
     /// Get all notes in the project
-    pub async fn get_notes() -> Result<Vec<PathBuf>> {
-        let mut notes = Vec::new();
-
-        if Path::new("notes").exists() {
-            let mut entries = async_fs::read_dir("notes").await?;
-            while let Some(entry) = entries.next_entry().await? {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                    notes.push(entry.path());
-                }
-            }
-        }
-
-        notes.sort();
-        Ok(notes)
+    pub async fn get_notes(&self) -> Result<Vec<PathBuf>> {
+        self.markdown_files(&self.content_dir("notes").await).await
     }
 
     /// Find a file by name or partial name
-    pub async fn find_file(target: &str) -> Result<Option<PathBuf>> {
+    pub async fn find_file(&self, target: &str) -> Result<Option<PathBuf>> {
         let target_lower = target.to_lowercase();
 
         // First try exact matches
-        let possible_paths = vec![
-            format!("chapters/{}.md", target),
-            format!("scenes/{}.md", target),
-            format!("characters/{}.md", target),
-            format!("shortstories/{}.md", target),
-            format!("short/{}.md", target),
-            format!("stories/{}.md", target),
-            format!("drafts/{}.md", target),
-            format!("notes/{}.md", target),
-            format!("{}.md", target),
+        let dirs = [
+            "chapters",
+            "scenes",
+            "characters",
+            "shortstories",
+            "short",
+            "stories",
+            "drafts",
+            "notes",
         ];
 
-        for path in possible_paths {
-            if Path::new(&path).exists() {
-                return Ok(Some(PathBuf::from(path)));
+        let file_name = format!("{}.md", target);
+        for dir in dirs {
+            let path = self.content_dir(dir).await.join(&file_name);
+            if path.exists() {
+                return Ok(Some(path));
             }
         }
 
-        // Then try partial matches
-        let all_files = Self::get_all_content_files().await?;
+        let path = self.path(&file_name);
+        if path.exists() {
+            return Ok(Some(path));
+        }
+
+        // Then exact names in subfolders, then partial matches
+        let all_files = self.get_all_content_files().await?;
+        let stem_of = |file: &PathBuf| {
+            file.file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_lowercase)
+        };
+        if let Some(file) = all_files
+            .iter()
+            .find(|file| stem_of(file).as_deref() == Some(target_lower.as_str()))
+        {
+            return Ok(Some(file.clone()));
+        }
+
         for file in all_files {
             if let Some(stem) = file.file_stem().and_then(|s| s.to_str()) {
                 if stem.to_lowercase().contains(&target_lower) {
@@ -232,45 +701,48 @@ impl ProjectManager {
         Ok(None)
     }
 
-    /// Get all content files in the project
-    pub async fn get_all_content_files() -> Result<Vec<PathBuf>> {
+    /// Get all content files in the project, each once. Stories and blog
+    /// posts share `drafts/`, so the first listing of a file wins.
+    pub async fn get_all_content_files(&self) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
 
-        files.extend(Self::get_chapters().await?);
-        files.extend(Self::get_scenes().await?);
-        files.extend(Self::get_characters().await?);
-        files.extend(Self::get_short_stories().await?);
-        files.extend(Self::get_blog_posts().await?);
-        files.extend(Self::get_notes().await?);
+        files.extend(self.get_chapters().await?);
+        files.extend(self.get_scenes().await?);
+        files.extend(self.get_characters().await?);
+        files.extend(self.get_short_stories().await?);
+        files.extend(self.get_blog_posts().await?);
+        files.extend(self.get_notes().await?);
 
+        let mut seen = HashSet::new();
+        files.retain(|file| seen.insert(file.clone()));
         Ok(files)
     }
 
     /// Create necessary directories for a project type
-    pub async fn create_project_structure(project_type: &str) -> Result<()> {
+    pub async fn create_project_structure(&self, project_type: &str) -> Result<()> {
         match project_type {
             "novel" => {
-                async_fs::create_dir_all("chapters").await?;
-                async_fs::create_dir_all("scenes").await?;
-                async_fs::create_dir_all("characters").await?;
-                async_fs::create_dir_all("notes").await?;
-                async_fs::create_dir_all("exports").await?;
+                async_fs::create_dir_all(self.path("chapters")).await?;
+                async_fs::create_dir_all(self.path("scenes")).await?;
+                async_fs::create_dir_all(self.path("characters")).await?;
+                async_fs::create_dir_all(self.path("notes")).await?;
+                async_fs::create_dir_all(self.path("exports")).await?;
             }
             "short-story" => {
-                async_fs::create_dir_all("shortstories").await?;
-                async_fs::create_dir_all("characters").await?;
-                async_fs::create_dir_all("notes").await?;
-                async_fs::create_dir_all("exports").await?;
+                async_fs::create_dir_all(self.path("shortstories")).await?;
+                async_fs::create_dir_all(self.path("characters")).await?;
+                async_fs::create_dir_all(self.path("notes")).await?;
+                async_fs::create_dir_all(self.path("exports")).await?;
             }
             "simple-short-story" => {
-                async_fs::create_dir_all("drafts").await?;
-                async_fs::create_dir_all("notes").await?;
+                async_fs::create_dir_all(self.path("drafts")).await?;
+                async_fs::create_dir_all(self.path("notes")).await?;
             }
             "blog" => {
-                async_fs::create_dir_all("drafts").await?;
-                async_fs::create_dir_all("published").await?;
-                async_fs::create_dir_all("assets").await?;
-                async_fs::create_dir_all("templates").await?;
+                async_fs::create_dir_all(self.path("drafts")).await?;
+                async_fs::create_dir_all(self.path("published")).await?;
+                async_fs::create_dir_all(self.path("assets")).await?;
+                async_fs::create_dir_all(self.path("templates")).await?;
             }
             _ => {
                 return Err(anyhow!("Unknown project type: {}", project_type));
@@ -279,19 +751,66 @@ impl ProjectManager {
         Ok(())
     }
 
-    /// Get word count for a file
-    pub async fn get_word_count(file_path: &Path) -> Result<usize> {
+    /// Read a content file and parse its frontmatter
+    pub async fn read_content(&self, file_path: &Path) -> Result<ContentFile> {
         let content = async_fs::read_to_string(file_path).await?;
-        Ok(content.split_whitespace().count())
+        let (meta, body, problems) = frontmatter::parse_checked(&content);
+        if !problems.is_empty() {
+            self.warn_frontmatter(file_path, &problems);
+        }
+
+        Ok(ContentFile {
+            path: file_path.to_path_buf(),
+            meta,
+            body: body.to_string(),
+        })
+    }
+
+    /// Say which frontmatter keys of a file were skipped, once per file per run
+    fn warn_frontmatter(&self, file_path: &Path, problems: &[FieldProblem]) {
+        static WARNED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+        let first_time = WARNED
+            .lock()
+            .map(|mut warned| warned.insert(file_path.to_path_buf()))
+            .unwrap_or(false);
+        if !first_time {
+            return;
+        }
+
+        for problem in problems {
+            outln!(
+                "{} {} {}",
+                "⚠️  Ignoring frontmatter in".yellow(),
+                self.display_path(file_path).cyan(),
+                format!("({})", problem).bright_black()
+            );
+        }
+    }
+
+    /// Read several content files, skipping any that can't be read
+    pub async fn read_contents(&self, files: &[PathBuf]) -> Vec<ContentFile> {
+        let mut contents = Vec::new();
+        for file in files {
+            if let Ok(content) = self.read_content(file).await {
+                contents.push(content);
+            }
+        }
+        contents
+    }
+
+    /// Get word count for a file, excluding frontmatter
+    pub async fn get_word_count(&self, file_path: &Path) -> Result<usize> {
+        let content = async_fs::read_to_string(file_path).await?;
+        Ok(wordcount::count(&content))
     }
 
     /// Get total word count for the project
-    pub async fn get_total_word_count() -> Result<usize> {
-        let files = Self::get_all_content_files().await?;
+    pub async fn get_total_word_count(&self) -> Result<usize> {
+        let files = self.get_all_content_files().await?;
         let mut total = 0;
 
         for file in files {
-            if let Ok(count) = Self::get_word_count(&file).await {
+            if let Ok(count) = self.get_word_count(&file).await {
                 total += count;
             }
         }
@@ -300,14 +819,14 @@ impl ProjectManager {
     }
 
     /// Backup the project
-    pub async fn create_backup() -> Result<PathBuf> {
-        let config = Self::get_config().await?;
+    pub async fn create_backup(&self) -> Result<PathBuf> {
+        let config = self.get_config().await?;
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         let backup_name = format!("{}_{}.zip", config.name, timestamp);
-        let backup_path = PathBuf::from("backups").join(&backup_name);
+        let backup_path = self.path("backups").join(&backup_name);
 
         // Create backups directory if it doesn't exist
-        async_fs::create_dir_all("backups").await?;
+        async_fs::create_dir_all(self.path("backups")).await?;
 
         // TODO: Implement ZIP creation
         // For now, just create an empty file to indicate backup was attempted
@@ -316,3 +835,135 @@ impl ProjectManager {
         Ok(backup_path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::{config_json, project_with};
+
+    #[tokio::test]
+    async fn drafts_are_listed_once() {
+        let (_dir, project) = project_with(&[
+            ("writers.config.json", &config_json("simple-short-story")),
+            ("drafts/alpha.md", "One two three."),
+            ("notes/idea.md", "Four."),
+        ]);
+
+        let files = project.get_all_content_files().await.unwrap();
+        let names: Vec<_> = files.iter().map(|f| project.display_path(f)).collect();
+        assert_eq!(names, ["drafts/alpha.md", "notes/idea.md"]);
+    }
+
+    #[tokio::test]
+    async fn saving_keeps_unknown_keys_and_skips_unset_ones() {
+        let (_dir, project) = project_with(&[(
+            "writers.config.json",
+            r#"{"name":"T","author":"A","type":"novel","version":"1.0.0","created":"","schema_version":2,"publisher":"Small Press","settings":{"tab_size":4,"backups":{"keep":5}}}"#,
+        )]);
+
+        let mut config = project.get_config().await.unwrap();
+        config.word_goal = Some(1000);
+        project.save_config(&config).await.unwrap();
+
+        let saved = project.get_raw_config().await.unwrap();
+        assert_eq!(saved["publisher"], "Small Press");
+        assert_eq!(saved["settings"]["backups"]["keep"], 5);
+        assert_eq!(saved["word_goal"], 1000);
+        assert!(saved["settings"].get("pdf_font").is_none());
+        assert!(saved.get("deadline").is_none());
+    }
+
+    #[tokio::test]
+    async fn an_unmigrated_config_is_not_saved() {
+        let original = r#"{"name":"T","author":"A","wordGoal":500}"#;
+        let (_dir, project) = project_with(&[("writers.config.json", original)]);
+
+        let config = project.get_config().await.unwrap();
+        let error = project.save_config(&config).await.unwrap_err();
+        assert!(error.to_string().contains("writers config migrate"));
+        assert_eq!(
+            std::fs::read_to_string(project.path("writers.config.json")).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn folder_names_become_titles() {
+        assert_eq!(title_from_name("part-1_the-north"), "Part 1 The North");
+        assert_eq!(title_from_name("--act--two"), "Act Two");
+    }
+
+    #[tokio::test]
+    async fn chapters_are_grouped_by_folder_or_frontmatter_part() {
+        let (_dir, project) = project_with(&[
+            ("writers.config.json", &config_json("novel")),
+            ("chapters/prologue.md", "---\norder: 0\n---\nBefore."),
+            ("chapters/part-1/chapter-01.md", "One."),
+            ("chapters/part-1/chapter-02.md", "Two."),
+            (
+                "chapters/part-2_the-sea/chapter-03.md",
+                "---\npart: Interlude\n---\nThree.",
+            ),
+            ("chapters/part-2_the-sea/chapter-04.md", "Four."),
+        ]);
+
+        let parts = project.get_parts().await.unwrap();
+        let summary: Vec<_> = parts
+            .iter()
+            .map(|part| (part.title.as_deref(), part.chapters.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (None, 1),
+                (Some("Part 1"), 2),
+                (Some("Interlude"), 1),
+                (Some("Part 2 The Sea"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn discover_walks_up_to_the_config() {
+        let (dir, _) = project_with(&[("writers.config.json", "{}"), ("chapters/a.md", "")]);
+        let found = ProjectManager::discover_from(&dir.path().join("chapters")).unwrap();
+        assert_eq!(found.root(), dir.path());
+        assert!(ProjectManager::discover_from(Path::new("/")).is_none());
+    }
+
+    #[test]
+    fn display_paths_are_relative_to_the_root() {
+        let project = ProjectManager::at("/books/novel");
+        assert_eq!(
+            project.display_path(Path::new("/books/novel/chapters/a.md")),
+            "chapters/a.md"
+        );
+        assert_eq!(
+            project.display_path(Path::new("/elsewhere/b.md")),
+            "/elsewhere/b.md"
+        );
+    }
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slugify("The Long Night"), "the-long-night");
+        assert_eq!(slugify("  Chapter 7: Rain!  "), "chapter-7-rain");
+        assert_eq!(slugify("?!"), "");
+    }
+
+    #[test]
+    fn content_types() {
+        assert_eq!(ContentType::parse("Chapters"), Some(ContentType::Chapter));
+        assert_eq!(ContentType::parse("short-story"), Some(ContentType::Story));
+        assert_eq!(ContentType::parse("post"), Some(ContentType::BlogPost));
+        assert_eq!(ContentType::parse("poem"), None);
+        for content_type in ContentType::ALL {
+            assert_eq!(ContentType::parse(content_type.name()), Some(content_type));
+        }
+
+        assert!(ContentType::Scene.is_numbered());
+        assert!(!ContentType::Note.is_numbered());
+        assert_eq!(ContentType::Story.directory("short-story"), "shortstories");
+        assert_eq!(ContentType::Story.directory("novel"), "drafts");
+    }
+}
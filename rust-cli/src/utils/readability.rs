@@ -0,0 +1,415 @@
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::ops::AddAssign;
+
+/// Counts behind the readability scores. Counts from several files can be
+/// added together to score a whole manuscript.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProseMetrics {
+    pub words: usize,
+    pub sentences: usize,
+    pub paragraphs: usize,
+    pub syllables: usize,
+    /// Words of three or more syllables, for the Gunning fog index
+    pub complex_words: usize,
+    /// Words inside quotation marks
+    pub dialogue_words: usize,
+}
+
+impl ProseMetrics {
+    /// Measure the prose paragraphs of a markdown document. Headings, code
+    /// blocks and HTML comments are left out.
+    pub fn analyze(markdown: &str) -> Self {
+        let mut metrics = Self::default();
+        for paragraph in paragraphs(markdown) {
+            metrics.add_paragraph(&paragraph);
+        }
+        metrics
+    }
+
+    fn add_paragraph(&mut self, paragraph: &str) {
+        let sentences = sentences(paragraph);
+        if sentences.is_empty() {
+            return;
+        }
+
+        self.paragraphs += 1;
+        self.sentences += sentences.len();
+
+        for (word, in_dialogue) in words(paragraph) {
+            let count = syllables(word);
+            self.words += 1;
+            self.syllables += count;
+            if count >= 3 && syllables(strip_suffix(word)) >= 3 {
+                self.complex_words += 1;
+            }
+            if in_dialogue {
+                self.dialogue_words += 1;
+            }
+        }
+    }
+
+    pub fn has_prose(&self) -> bool {
+        self.words > 0 && self.sentences > 0
+    }
+
+    pub fn words_per_sentence(&self) -> f64 {
+        self.words as f64 / self.sentences.max(1) as f64
+    }
+
+    pub fn sentences_per_paragraph(&self) -> f64 {
+        self.sentences as f64 / self.paragraphs.max(1) as f64
+    }
+
+    pub fn words_per_paragraph(&self) -> f64 {
+        self.words as f64 / self.paragraphs.max(1) as f64
+    }
+
+    fn syllables_per_word(&self) -> f64 {
+        self.syllables as f64 / self.words.max(1) as f64
+    }
+
+    /// Flesch reading ease: higher is easier, 60-70 is plain English
+    pub fn flesch_reading_ease(&self) -> f64 {
+        206.835 - 1.015 * self.words_per_sentence() - 84.6 * self.syllables_per_word()
+    }
+
+    /// Flesch-Kincaid grade level: the US school grade needed to follow the text
+    pub fn flesch_kincaid_grade(&self) -> f64 {
+        0.39 * self.words_per_sentence() + 11.8 * self.syllables_per_word() - 15.59
+    }
+
+    /// Gunning fog index: years of education needed on a first reading
+    pub fn gunning_fog(&self) -> f64 {
+        let complex = self.complex_words as f64 / self.words.max(1) as f64;
+        0.4 * (self.words_per_sentence() + 100.0 * complex)
+    }
+
+    /// Share of words spoken in dialogue, as a percentage
+    pub fn dialogue_percent(&self) -> f64 {
+        self.dialogue_words as f64 / self.words.max(1) as f64 * 100.0
+    }
+}
+
+impl AddAssign for ProseMetrics {
+    fn add_assign(&mut self, other: Self) {
+        self.words += other.words;
+        self.sentences += other.sentences;
+        self.paragraphs += other.paragraphs;
+        self.syllables += other.syllables;
+        self.complex_words += other.complex_words;
+        self.dialogue_words += other.dialogue_words;
+    }
+}
+
+/// Describe a Flesch reading ease score in words
+pub fn ease_label(score: f64) -> &'static str {
+    match score {
+        s if s >= 90.0 => "very easy",
+        s if s >= 80.0 => "easy",
+        s if s >= 70.0 => "fairly easy",
+        s if s >= 60.0 => "plain English",
+        s if s >= 50.0 => "fairly difficult",
+        s if s >= 30.0 => "difficult",
+        _ => "very difficult",
+    }
+}
+
+/// The text of each prose paragraph (including list items and block quotes)
+pub fn paragraphs(markdown: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    // Depth inside headings, code blocks, tables and images, whose text isn't prose
+    let mut skipped = 0;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::Heading(..) | Tag::CodeBlock(_) | Tag::Table(_) | Tag::Image(..)) => {
+                skipped += 1
+            }
+            Event::End(Tag::Heading(..) | Tag::CodeBlock(_) | Tag::Table(_) | Tag::Image(..)) => {
+                skipped -= 1
+            }
+            Event::Text(t) | Event::Code(t) if skipped == 0 => current.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => current.push(' '),
+            Event::End(Tag::Paragraph | Tag::Item) => {
+                if !current.trim().is_empty() {
+                    paragraphs.push(std::mem::take(&mut current));
+                }
+                current.clear();
+            }
+            _ => {}
+        }
+    }
+
+    paragraphs
+}
+
+// Abbreviations whose full stop doesn't end a sentence
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "vs", "etc", "e.g", "i.e", "mt",
+];
+
+/// Whether "no." or "st." is an abbreviation, judging by what follows: a
+/// number as in "No. 5", or for "St." a name as in "St. Paul". Otherwise it's
+/// the word "no" ending a sentence.
+fn is_numbered_or_saint(word: &str, next: &str) -> bool {
+    match word {
+        "no" => next.starts_with(|c: char| c.is_ascii_digit()),
+        "st" => next.starts_with(|c: char| c.is_ascii_digit() || c.is_uppercase()),
+        _ => false,
+    }
+}
+
+/// Split a paragraph into sentences at `.`, `!`, `?` and `…`, allowing for
+/// closing quotes, decimals and common abbreviations
+pub fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?' | '…') {
+            continue;
+        }
+
+        // Take the rest of a run like "?!" or "..." and any closing quotes
+        let mut end = index + c.len_utf8();
+        while let Some(&(next_index, next)) = chars.peek() {
+            if matches!(next, '.' | '!' | '?' | '…' | '"' | '\'' | '”' | '’' | ')') {
+                end = next_index + next.len_utf8();
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        // A sentence ends only before whitespace or the end of the paragraph,
+        // and not before a lowercase word as in "“Why?” she asked."
+        if chars.peek().is_some_and(|&(_, next)| !next.is_whitespace())
+            || text[end..].trim_start().starts_with(char::is_lowercase)
+        {
+            continue;
+        }
+
+        if c == '.' {
+            let last_word = text[start..index]
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches(|c: char| !c.is_alphanumeric());
+            // Initials like "J. R. R." but not the pronoun "I."
+            let initial = last_word.chars().count() == 1
+                && last_word.chars().all(|c| c.is_uppercase() && c != 'I');
+            let last_word = last_word.to_lowercase();
+            if initial
+                || ABBREVIATIONS.contains(&last_word.as_str())
+                || is_numbered_or_saint(&last_word, text[end..].trim_start())
+            {
+                continue;
+            }
+        }
+
+        let sentence = text[start..end].trim();
+        if sentence.chars().any(char::is_alphanumeric) {
+            sentences.push(sentence);
+        }
+        start = end;
+    }
+
+    let rest = text[start..].trim();
+    if rest.chars().any(char::is_alphanumeric) {
+        sentences.push(rest);
+    }
+
+    sentences
+}
+
+/// The words of a paragraph, each with whether it's inside quotation marks.
+/// Quotes left open run to the end of the paragraph, as in fiction where a
+/// speech continues into the next paragraph.
+pub fn words(text: &str) -> Vec<(&str, bool)> {
+    let mut words = Vec::new();
+    let mut in_quote = false;
+    let mut word_start: Option<usize> = None;
+
+    for (index, c) in text.char_indices() {
+        let is_word_char = c.is_alphanumeric() || matches!(c, '\'' | '’' | '-');
+        if is_word_char && (c.is_alphanumeric() || word_start.is_some()) {
+            word_start.get_or_insert(index);
+            continue;
+        }
+
+        if let Some(start) = word_start.take() {
+            words.push((
+                text[start..index].trim_end_matches(['\'', '’', '-']),
+                in_quote,
+            ));
+        }
+
+        match c {
+            '“' => in_quote = true,
+            '”' => in_quote = false,
+            '"' => in_quote = !in_quote,
+            _ => {}
+        }
+    }
+
+    if let Some(start) = word_start {
+        words.push((text[start..].trim_end_matches(['\'', '’', '-']), in_quote));
+    }
+
+    words
+}
+
+/// Estimate the syllables in an English word by counting vowel groups, with
+/// the usual corrections for silent endings
+pub fn syllables(word: &str) -> usize {
+    let word: String = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if word.is_empty() {
+        return 0;
+    }
+    if word.chars().count() <= 3 {
+        return 1;
+    }
+
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let chars: Vec<char> = word.chars().collect();
+
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &chars {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // Silent final "e", "es" and "ed", except "-le" as in "table" and "-ted"/"-ded"
+    let n = chars.len();
+    let consonant_before = |i: usize| i > 0 && !is_vowel(chars[i - 1]);
+    let silent_e = word.ends_with('e') && !(word.ends_with("le") && consonant_before(n - 2));
+    let silent_ending = (word.ends_with("es") || word.ends_with("ed"))
+        && !word.ends_with("ted")
+        && !word.ends_with("ded")
+        && consonant_before(n - 2);
+    if (silent_e || silent_ending) && count > 1 {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+/// Drop the inflections that Gunning fog doesn't count towards complexity
+fn strip_suffix(word: &str) -> &str {
+    ["ing", "es", "ed"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .filter(|stem| stem.len() > 2)
+        .unwrap_or(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentence_boundaries() {
+        assert_eq!(
+            sentences("Dr. Smith left. “Why?” she asked. He paid $3.50!? J. R. R. Tolkien wrote"),
+            vec![
+                "Dr. Smith left.",
+                "“Why?” she asked.",
+                "He paid $3.50!?",
+                "J. R. R. Tolkien wrote"
+            ]
+        );
+        assert_eq!(sentences("So did I. Then..."), vec!["So did I.", "Then..."]);
+        assert!(sentences("* * *").is_empty());
+    }
+
+    #[test]
+    fn no_and_st_end_sentences_unless_a_number_or_name_follows() {
+        assert_eq!(
+            sentences("She said no. He left."),
+            vec!["She said no.", "He left."]
+        );
+        assert_eq!(
+            sentences("Room No. 5 was empty."),
+            vec!["Room No. 5 was empty."]
+        );
+        assert_eq!(
+            sentences("They met at St. Paul's."),
+            vec!["They met at St. Paul's."]
+        );
+    }
+
+    #[test]
+    fn words_inside_quotes() {
+        assert_eq!(
+            words("“Don't go,” she said. \"Wait-\""),
+            vec![
+                ("Don't", true),
+                ("go", true),
+                ("she", false),
+                ("said", false),
+                ("Wait", true)
+            ]
+        );
+        assert_eq!(
+            words("He said, “Come"),
+            vec![("He", false), ("said", false), ("Come", true)]
+        );
+    }
+
+    #[test]
+    fn syllable_estimates() {
+        for (word, expected) in [
+            ("the", 1),
+            ("table", 2),
+            ("make", 1),
+            ("wanted", 2),
+            ("jumped", 1),
+            ("beautiful", 3),
+            ("rhythm", 1),
+            ("42", 0),
+        ] {
+            assert_eq!(syllables(word), expected, "{}", word);
+        }
+        assert_eq!(strip_suffix("interesting"), "interest");
+        assert_eq!(strip_suffix("red"), "red");
+    }
+
+    #[test]
+    fn paragraphs_skip_headings_and_code() {
+        let markdown = "# Heading\n\nOne line\nwraps.\n\n```\ncode\n```\n\n- item\n\n> quoted";
+        assert_eq!(
+            paragraphs(markdown),
+            vec!["One line wraps.", "item", "quoted"]
+        );
+    }
+
+    #[test]
+    fn scores() {
+        let metrics =
+            ProseMetrics::analyze("The cat sat. “It is hot,” she said.\n\nThen rain came.");
+        assert_eq!(metrics.words, 11);
+        assert_eq!(metrics.sentences, 3);
+        assert_eq!(metrics.paragraphs, 2);
+        assert_eq!(metrics.dialogue_words, 3);
+        assert!(metrics.has_prose());
+        assert!(metrics.flesch_reading_ease() > 90.0);
+        assert_eq!(ease_label(metrics.flesch_reading_ease()), "very easy");
+        assert_eq!(ease_label(10.0), "very difficult");
+
+        let mut total = metrics;
+        total += metrics;
+        assert_eq!(total.words, 22);
+        assert_eq!(total.words_per_sentence(), metrics.words_per_sentence());
+        assert!(!ProseMetrics::default().has_prose());
+    }
+}
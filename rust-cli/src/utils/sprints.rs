@@ -0,0 +1,127 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{store, ProjectManager};
+
+/// Finished sprints, one JSON record per line, relative to the project root
+pub const SPRINTS_FILE: &str = ".writers/sprints.jsonl";
+
+/// Shortest sprint that can hold the speed record, so a burst of typing
+/// ended after a few seconds doesn't
+pub const MIN_FASTEST_SECONDS: u64 = 60;
+
+/// One finished sprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintRecord {
+    /// When the sprint ended
+    pub time: DateTime<Local>,
+    /// The file written in, relative to the project root
+    pub file: String,
+    /// The sprint's planned length
+    pub planned_minutes: u32,
+    /// How long was actually written, which is less if it was cut short
+    pub seconds: u64,
+    pub words: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<usize>,
+}
+
+impl SprintRecord {
+    pub fn words_per_minute(&self) -> f64 {
+        self.words as f64 * 60.0 / self.seconds.max(1) as f64
+    }
+}
+
+/// The best sprints on record
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonalBests {
+    pub sprints: usize,
+    pub total_words: i64,
+    pub most_words: Option<SprintRecord>,
+    pub fastest: Option<SprintRecord>,
+    pub longest: Option<SprintRecord>,
+}
+
+pub async fn append(project: &ProjectManager, record: &SprintRecord) -> Result<()> {
+    store::append_jsonl(project, SPRINTS_FILE, record).await
+}
+
+/// Read the sprint log, oldest first. Lines that can't be parsed are skipped.
+pub async fn load(project: &ProjectManager) -> Result<Vec<SprintRecord>> {
+    store::load_jsonl(project, SPRINTS_FILE).await
+}
+
+/// Personal bests, ignoring sprints that added no words. Ties go to the
+/// earlier sprint, so a record has to be beaten to change hands.
+pub fn personal_bests(records: &[SprintRecord]) -> PersonalBests {
+    let productive: Vec<&SprintRecord> = records.iter().filter(|r| r.words > 0).collect();
+    let best = |key: fn(&SprintRecord) -> f64, min_seconds: u64| {
+        productive
+            .iter()
+            .copied()
+            .filter(|record| record.seconds >= min_seconds)
+            .fold(None::<&SprintRecord>, |best, record| match best {
+                Some(best) if key(best) >= key(record) => Some(best),
+                _ => Some(record),
+            })
+            .cloned()
+    };
+
+    PersonalBests {
+        sprints: records.len(),
+        total_words: records.iter().map(|r| r.words).sum(),
+        most_words: best(|r| r.words as f64, 0),
+        fastest: best(SprintRecord::words_per_minute, MIN_FASTEST_SECONDS),
+        longest: best(|r| r.seconds as f64, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(file: &str, seconds: u64, words: i64) -> SprintRecord {
+        SprintRecord {
+            time: Local::now(),
+            file: file.to_string(),
+            planned_minutes: 15,
+            seconds,
+            words,
+            target: None,
+        }
+    }
+
+    #[test]
+    fn words_per_minute() {
+        assert_eq!(record("a.md", 120, 50).words_per_minute(), 25.0);
+        assert_eq!(record("a.md", 0, 5).words_per_minute(), 300.0);
+    }
+
+    #[test]
+    fn bests_skip_unproductive_and_short_sprints() {
+        let records = vec![
+            record("a.md", 900, 400),
+            record("b.md", 30, 100),
+            record("c.md", 1800, -20),
+            record("d.md", 600, 400),
+            record("e.md", 1200, 300),
+        ];
+
+        let bests = personal_bests(&records);
+        assert_eq!(bests.sprints, 5);
+        assert_eq!(bests.total_words, 1180);
+        // A tie keeps the earlier record
+        assert_eq!(bests.most_words.unwrap().file, "a.md");
+        // b.md is faster but too short to count
+        assert_eq!(bests.fastest.unwrap().file, "d.md");
+        assert_eq!(bests.longest.unwrap().file, "e.md");
+    }
+
+    #[test]
+    fn no_bests_without_words() {
+        let bests = personal_bests(&[record("a.md", 900, 0)]);
+        assert_eq!(bests.sprints, 1);
+        assert!(bests.most_words.is_none() && bests.fastest.is_none() && bests.longest.is_none());
+    }
+}
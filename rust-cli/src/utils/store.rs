@@ -0,0 +1,126 @@
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::Write;
+use tokio::fs as async_fs;
+
+use super::ProjectManager;
+
+/// Append one record as a line of JSON to a log file relative to the
+/// project root, creating the file and its directory as needed
+pub async fn append_jsonl<T: Serialize>(
+    project: &ProjectManager,
+    file: &str,
+    record: &T,
+) -> Result<()> {
+    let path = project.path(file);
+    if let Some(parent) = path.parent() {
+        async_fs::create_dir_all(parent).await?;
+    }
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(log, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Read a log file, oldest first. A missing file is an empty log, and lines
+/// that can't be parsed are skipped.
+pub async fn load_jsonl<T: DeserializeOwned>(
+    project: &ProjectManager,
+    file: &str,
+) -> Result<Vec<T>> {
+    let path = project.path(file);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = async_fs::read_to_string(&path).await?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Read a JSON file relative to the project root. A missing file gives the
+/// default value; one that can't be read is an error, so it's never overwritten.
+pub async fn load_json<T: DeserializeOwned + Default>(
+    project: &ProjectManager,
+    file: &str,
+) -> Result<T> {
+    let path = project.path(file);
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = async_fs::read_to_string(&path).await?;
+    serde_json::from_str(&content).map_err(|e| anyhow!("Can't read {}: {}", file, e))
+}
+
+/// Write a value as pretty JSON to a file relative to the project root
+pub async fn save_json<T: Serialize + ?Sized>(
+    project: &ProjectManager,
+    file: &str,
+    value: &T,
+) -> Result<()> {
+    let path = project.path(file);
+    if let Some(parent) = path.parent() {
+        async_fs::create_dir_all(parent).await?;
+    }
+    async_fs::write(&path, serde_json::to_string_pretty(value)? + "\n").await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::project_with;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn logs_round_trip_and_skip_bad_lines() {
+        let (_dir, project) = project_with(&[]);
+        let file = ".writers/log.jsonl";
+        assert!(load_jsonl::<Value>(&project, file)
+            .await
+            .unwrap()
+            .is_empty());
+
+        append_jsonl(&project, file, &json!({ "n": 1 }))
+            .await
+            .unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(project.path(file))
+            .and_then(|mut log| writeln!(log, "not json"))
+            .unwrap();
+        append_jsonl(&project, file, &json!({ "n": 2 }))
+            .await
+            .unwrap();
+
+        let records: Vec<Value> = load_jsonl(&project, file).await.unwrap();
+        assert_eq!(records, vec![json!({ "n": 1 }), json!({ "n": 2 })]);
+    }
+
+    #[tokio::test]
+    async fn json_files_round_trip_and_refuse_bad_content() {
+        let (_dir, project) = project_with(&[]);
+        let file = ".writers/state.json";
+        assert!(load_json::<Vec<u32>>(&project, file)
+            .await
+            .unwrap()
+            .is_empty());
+
+        save_json(&project, file, &[1, 2, 3]).await.unwrap();
+        assert_eq!(
+            load_json::<Vec<u32>>(&project, file).await.unwrap(),
+            vec![1, 2, 3]
+        );
+
+        std::fs::write(project.path(file), "not json").unwrap();
+        let error = load_json::<Vec<u32>>(&project, file).await.unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Can't read .writers/state.json"));
+    }
+}
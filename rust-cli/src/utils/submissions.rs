@@ -0,0 +1,272 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::{store, ProjectManager};
+
+/// The submission ledger, relative to the project root
+pub const SUBMISSIONS_FILE: &str = ".writers/submissions.json";
+
+/// What's known about the markets stories go to, relative to the project root
+pub const MARKETS_FILE: &str = ".writers/markets.json";
+
+/// Where a submission stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pending,
+    Rejected,
+    Accepted,
+    Withdrawn,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Status::Pending => "pending",
+            Status::Rejected => "rejected",
+            Status::Accepted => "accepted",
+            Status::Withdrawn => "withdrawn",
+        })
+    }
+}
+
+/// One story sent to one market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub id: u32,
+    /// The story's file name without the extension, which survives moves
+    /// between story folders and into the archive
+    pub story: String,
+    pub market: String,
+    pub sent: NaiveDate,
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responded: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<f64>,
+}
+
+impl Submission {
+    /// Days between sending and the response, once there is one
+    pub fn response_days(&self) -> Option<i64> {
+        self.responded.map(|date| (date - self.sent).num_days())
+    }
+}
+
+/// A market's submission policy, as recorded in the markets file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Market {
+    pub name: String,
+    /// Whether the market takes stories that are under consideration
+    /// elsewhere; unset when the policy isn't known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simultaneous: Option<bool>,
+    /// How long the market says it takes to respond
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// How a market has treated the project's submissions
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketStats {
+    pub market: String,
+    pub submissions: usize,
+    pub pending: usize,
+    pub accepted: usize,
+    pub rejected: usize,
+    pub withdrawn: usize,
+    /// Average days to an acceptance or rejection
+    pub average_response_days: Option<f64>,
+    pub payments: f64,
+}
+
+/// Read the ledger, oldest first. A missing ledger is an empty one.
+pub async fn load(project: &ProjectManager) -> Result<Vec<Submission>> {
+    store::load_json(project, SUBMISSIONS_FILE).await
+}
+
+pub async fn save(project: &ProjectManager, submissions: &[Submission]) -> Result<()> {
+    store::save_json(project, SUBMISSIONS_FILE, submissions).await
+}
+
+/// Read the markets file. A missing file means no policies are known.
+pub async fn load_markets(project: &ProjectManager) -> Result<Vec<Market>> {
+    store::load_json(project, MARKETS_FILE).await
+}
+
+/// Look a market up by name, ignoring case
+pub fn find_market<'a>(markets: &'a [Market], name: &str) -> Option<&'a Market> {
+    markets
+        .iter()
+        .find(|market| market.name.eq_ignore_ascii_case(name.trim()))
+}
+
+pub fn next_id(submissions: &[Submission]) -> u32 {
+    submissions.iter().map(|s| s.id).max().unwrap_or(0) + 1
+}
+
+/// The story's pending submissions that sending it to `market` would break
+/// the rules of, because either market forbids simultaneous submissions
+pub fn conflicts<'a>(
+    submissions: &'a [Submission],
+    markets: &[Market],
+    story: &str,
+    market: &str,
+) -> Vec<&'a Submission> {
+    let forbids =
+        |name: &str| find_market(markets, name).and_then(|m| m.simultaneous) == Some(false);
+    submissions
+        .iter()
+        .filter(|s| s.story == story && s.status == Status::Pending)
+        .filter(|s| !s.market.eq_ignore_ascii_case(market))
+        .filter(|s| forbids(market) || forbids(&s.market))
+        .collect()
+}
+
+/// Per-market totals, in order of market name
+pub fn market_stats(submissions: &[Submission]) -> Vec<MarketStats> {
+    let mut markets: BTreeMap<String, Vec<&Submission>> = BTreeMap::new();
+    for submission in submissions {
+        markets
+            .entry(submission.market.to_lowercase())
+            .or_default()
+            .push(submission);
+    }
+
+    markets
+        .into_values()
+        .map(|subs| {
+            let count = |status: Status| subs.iter().filter(|s| s.status == status).count();
+            let responses: Vec<i64> = subs
+                .iter()
+                .filter(|s| matches!(s.status, Status::Accepted | Status::Rejected))
+                .filter_map(|s| s.response_days())
+                .collect();
+            MarketStats {
+                market: subs[0].market.clone(),
+                submissions: subs.len(),
+                pending: count(Status::Pending),
+                accepted: count(Status::Accepted),
+                rejected: count(Status::Rejected),
+                withdrawn: count(Status::Withdrawn),
+                average_response_days: (!responses.is_empty())
+                    .then(|| responses.iter().sum::<i64>() as f64 / responses.len() as f64),
+                payments: subs
+                    .iter()
+                    .filter_map(|s| s.payment)
+                    .fold(0.0, |a, b| a + b),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::date;
+
+    fn submission(id: u32, story: &str, market: &str, status: Status) -> Submission {
+        Submission {
+            id,
+            story: story.to_string(),
+            market: market.to_string(),
+            sent: date(1),
+            status,
+            responded: None,
+            payment: None,
+        }
+    }
+
+    fn market(name: &str, simultaneous: Option<bool>) -> Market {
+        Market {
+            name: name.to_string(),
+            simultaneous,
+            response_days: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn conflicts_need_a_market_that_forbids_simultaneous_submissions() {
+        let submissions = vec![
+            submission(1, "rain", "Strict Review", Status::Pending),
+            submission(2, "rain", "Open Mag", Status::Pending),
+            submission(3, "rain", "Other Mag", Status::Rejected),
+            submission(4, "snow", "Strict Review", Status::Pending),
+        ];
+        let markets = vec![
+            market("strict review", Some(false)),
+            market("Open Mag", Some(true)),
+            market("Quiet Press", Some(false)),
+        ];
+        let ids = |found: Vec<&Submission>| found.iter().map(|s| s.id).collect::<Vec<_>>();
+
+        // The pending submission to a strict market blocks any other market
+        assert_eq!(
+            ids(conflicts(&submissions, &markets, "rain", "Unknown")),
+            vec![1]
+        );
+        // A strict new market conflicts with every pending submission
+        assert_eq!(
+            ids(conflicts(&submissions, &markets, "rain", "Quiet Press")),
+            vec![1, 2]
+        );
+        // Resubmitting to the same market isn't a conflict
+        assert!(conflicts(&submissions, &markets, "snow", "STRICT REVIEW").is_empty());
+        assert!(conflicts(&submissions, &[], "rain", "Unknown").is_empty());
+    }
+
+    #[test]
+    fn ids_count_up_from_the_highest() {
+        assert_eq!(next_id(&[]), 1);
+        let submissions = vec![
+            submission(4, "a", "m", Status::Pending),
+            submission(2, "b", "m", Status::Pending),
+        ];
+        assert_eq!(next_id(&submissions), 5);
+    }
+
+    #[test]
+    fn stats_per_market() {
+        let mut accepted = submission(1, "rain", "Open Mag", Status::Accepted);
+        accepted.responded = Some(date(11));
+        accepted.payment = Some(50.0);
+        let mut rejected = submission(2, "snow", "open mag", Status::Rejected);
+        rejected.responded = Some(date(21));
+        let mut withdrawn = submission(3, "hail", "Open Mag", Status::Withdrawn);
+        withdrawn.responded = Some(date(2));
+        let submissions = vec![
+            accepted,
+            rejected,
+            withdrawn,
+            submission(4, "rain", "Zine", Status::Pending),
+        ];
+
+        let stats = market_stats(&submissions);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].market, "Open Mag");
+        assert_eq!(stats[0].submissions, 3);
+        assert_eq!(
+            (stats[0].accepted, stats[0].rejected, stats[0].withdrawn),
+            (1, 1, 1)
+        );
+        assert_eq!(stats[0].average_response_days, Some(15.0));
+        assert_eq!(stats[0].payments, 50.0);
+        assert_eq!(stats[1].pending, 1);
+        assert_eq!(stats[1].average_response_days, None);
+    }
+
+    #[test]
+    fn response_days_once_responded() {
+        let mut sent = submission(1, "rain", "m", Status::Rejected);
+        assert_eq!(sent.response_days(), None);
+        sent.responded = Some(date(31));
+        assert_eq!(sent.response_days(), Some(30));
+    }
+}
@@ -0,0 +1,570 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tokio::fs as async_fs;
+
+use super::{ContentType, ProjectManager};
+
+/// Where a template was found. Project templates shadow user templates of the
+/// same name, which in turn shadow the built-in ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSource {
+    Project,
+    User,
+    Builtin,
+}
+
+impl TemplateSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::User => "user",
+            Self::Builtin => "built-in",
+        }
+    }
+}
+
+/// A template for new content, with `{{title}}`, `{{author}}`, `{{date}}` and
+/// `{{number}}` placeholders
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    pub body: String,
+    pub source: TemplateSource,
+}
+
+/// Values substituted into a template's placeholders
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    pub title: String,
+    pub author: String,
+    pub date: String,
+    pub number: Option<usize>,
+}
+
+impl TemplateVars {
+    pub fn render(&self, text: &str) -> String {
+        let number = self.number.map(|n| n.to_string()).unwrap_or_default();
+
+        text.replace("{{title}}", &self.title)
+            .replace("{{author}}", &self.author)
+            .replace("{{date}}", &self.date)
+            .replace("{{number}}", &number)
+    }
+}
+
+impl Template {
+    pub fn render(&self, vars: &TemplateVars) -> String {
+        vars.render(&self.body)
+    }
+}
+
+/// The project's template directory
+pub const PROJECT_DIR: &str = "templates";
+
+/// Per-user template directory, e.g. `~/.config/writers/templates` on Linux
+pub fn user_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("writers").join("templates"))
+}
+
+/// Every template for a content type: `templates/<type>/*.md` in the project,
+/// then the same layout in the user directory, then the built-in templates
+pub async fn available(
+    project: &ProjectManager,
+    content_type: ContentType,
+) -> Result<Vec<Template>> {
+    let mut templates = Vec::new();
+
+    let project_dir = project.path(PROJECT_DIR).join(content_type.name());
+    templates.extend(load_dir(&project_dir, TemplateSource::Project).await?);
+
+    if let Some(user_dir) = user_dir() {
+        let user_dir = user_dir.join(content_type.name());
+        templates.extend(load_dir(&user_dir, TemplateSource::User).await?);
+    }
+
+    templates.extend(builtin(content_type));
+
+    // Keep the first template of each name so local templates win
+    let mut seen = Vec::new();
+    templates.retain(|template: &Template| {
+        let name = template.name.to_lowercase();
+        if seen.contains(&name) {
+            false
+        } else {
+            seen.push(name);
+            true
+        }
+    });
+
+    Ok(templates)
+}
+
+/// Find a template by name for the content type
+pub async fn find(
+    project: &ProjectManager,
+    content_type: ContentType,
+    name: &str,
+) -> Result<Option<Template>> {
+    Ok(available(project, content_type)
+        .await?
+        .into_iter()
+        .find(|template| template.name.eq_ignore_ascii_case(name)))
+}
+
+async fn load_dir(dir: &Path, source: TemplateSource) -> Result<Vec<Template>> {
+    let mut templates = Vec::new();
+
+    if !dir.is_dir() {
+        return Ok(templates);
+    }
+
+    let mut entries = async_fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+
+        if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+            templates.push(Template {
+                name: name.to_string(),
+                description: path.display().to_string(),
+                body: async_fs::read_to_string(&path).await?,
+                source,
+            });
+        }
+    }
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Built-in templates for a content type; the first is the default
+pub fn builtin(content_type: ContentType) -> Vec<Template> {
+    BUILTIN
+        .iter()
+        .filter(|(ty, ..)| *ty == content_type)
+        .map(|&(_, name, description, body)| Template {
+            name: name.to_string(),
+            description: description.to_string(),
+            body: body.to_string(),
+            source: TemplateSource::Builtin,
+        })
+        .collect()
+}
+
+/// Project templates are directories under `<user dir>/projects/` whose files
+/// are copied into a new project
+pub async fn project_templates() -> Result<Vec<(String, PathBuf)>> {
+    let mut found = Vec::new();
+
+    let dir = match user_dir() {
+        Some(dir) => dir.join("projects"),
+        None => return Ok(found),
+    };
+
+    if !dir.is_dir() {
+        return Ok(found);
+    }
+
+    let mut entries = async_fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
+                found.push((name.to_string(), path.clone()));
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// Copy a project template into the project root, filling in placeholders
+/// in markdown files. Existing files are left alone. Returns the files written.
+pub async fn apply_project_template(
+    project: &ProjectManager,
+    template_dir: &Path,
+    vars: &TemplateVars,
+) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative) = pending.pop() {
+        let mut entries = async_fs::read_dir(template_dir.join(&relative)).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let relative = relative.join(entry.file_name());
+            let target = project.path(&relative);
+
+            if entry.file_type().await?.is_dir() {
+                async_fs::create_dir_all(&target).await?;
+                pending.push(relative);
+                continue;
+            }
+
+            if target.exists() {
+                continue;
+            }
+
+            if target.extension().and_then(|s| s.to_str()) == Some("md") {
+                let content = async_fs::read_to_string(entry.path()).await?;
+                async_fs::write(&target, vars.render(&content)).await?;
+            } else {
+                async_fs::copy(entry.path(), &target).await?;
+            }
+            written.push(relative);
+        }
+    }
+
+    written.sort();
+    Ok(written)
+}
+
+// Planning notes live in HTML comments so they never end up in exports
+const BUILTIN: &[(ContentType, &str, &str, &str)] = &[
+    (
+        ContentType::Chapter,
+        "basic",
+        "Basic chapter",
+        "# {{title}}
+
+",
+    ),
+    (
+        ContentType::Chapter,
+        "action",
+        "Action chapter",
+        "# {{title}}
+
+<!--
+Key elements
+- Conflict:
+- Stakes:
+- Pacing: fast
+- Mood: tense
+
+Scene breakdown
+1. Opening: set the scene, establish tension
+2. Rising action: build to the main conflict
+3. Climax: peak action moment
+4. Resolution: immediate aftermath
+-->
+
+",
+    ),
+    (
+        ContentType::Chapter,
+        "dialogue",
+        "Dialogue-heavy chapter",
+        "# {{title}}
+
+<!--
+Key elements
+- Main characters:
+- Relationship dynamic:
+- Conflict/tension:
+- Character development:
+
+Dialogue notes
+- Character voices and speech patterns
+- Subtext and hidden meanings
+- Emotional undertones
+-->
+
+",
+    ),
+    (
+        ContentType::Chapter,
+        "flashback",
+        "Flashback chapter",
+        "# {{title}}
+
+<!--
+Key elements
+- Timeline: when this occurred
+- Perspective: who is remembering
+- Relevance: how this connects to current events
+- Emotional impact: what this reveals
+-->
+
+",
+    ),
+    (
+        ContentType::Scene,
+        "basic",
+        "Basic scene",
+        "# {{title}}
+
+<!--
+Setting:
+Characters:
+Purpose:
+-->
+
+",
+    ),
+    (
+        ContentType::Scene,
+        "conflict",
+        "Conflict scene",
+        "# {{title}}
+
+<!--
+Setting:
+Characters:
+Type of conflict: internal / external / interpersonal
+Stakes:
+Obstacles:
+Character A wants:
+Character B wants:
+Why they can't both have it:
+-->
+
+",
+    ),
+    (
+        ContentType::Scene,
+        "setting",
+        "Setting description",
+        "# {{title}}
+
+<!--
+Location:
+Time period:
+Atmosphere/mood:
+Sight, sound, smell, texture:
+How does this setting serve the plot?
+-->
+
+",
+    ),
+    (
+        ContentType::Character,
+        "basic",
+        "Character profile",
+        "# {{title}}
+
+*Created: {{date}}*
+
+## Basic Information
+- **Full Name:**
+- **Age:**
+- **Occupation:**
+- **Location:**
+
+## Physical Description
+- **Appearance:**
+- **Distinguishing Features:**
+
+## Personality
+- **Traits:**
+- **Motivations:**
+- **Fears:**
+- **Flaws:**
+
+## Background
+- **History:**
+- **Family:**
+- **Important Events:**
+
+## Role in Story
+- **Purpose:**
+- **Character Arc:**
+- **Relationships:**
+
+## Notes
+",
+    ),
+    (
+        ContentType::Character,
+        "minor",
+        "Minor character",
+        "# {{title}}
+
+*Created: {{date}}*
+
+- **Role:**
+- **Appearance:**
+- **Defining trait:**
+- **Scenes:**
+",
+    ),
+    (
+        ContentType::Note,
+        "basic",
+        "Blank note",
+        "# {{title}}
+
+*Created: {{date}}*
+
+",
+    ),
+    (
+        ContentType::Note,
+        "plot",
+        "Plot note",
+        "# {{title}}
+
+*Created: {{date}}*
+
+## Main Plot
+- **Inciting incident:**
+- **Midpoint:**
+- **Climax:**
+- **Resolution:**
+
+## Subplots
+
+## Open Questions
+",
+    ),
+    (
+        ContentType::Note,
+        "research",
+        "Research note",
+        "# {{title}}
+
+*Created: {{date}}*
+
+## Topic
+
+## Findings
+
+## Sources
+",
+    ),
+    (
+        ContentType::Story,
+        "basic",
+        "Short story",
+        "# {{title}}
+
+<!--
+Genre:
+Target length:
+Theme:
+Opening / conflict / climax / resolution:
+-->
+
+",
+    ),
+    (
+        ContentType::Story,
+        "flash",
+        "Flash fiction (under 1,000 words)",
+        "# {{title}}
+
+<!--
+One moment, one character, one change.
+Target length: under 1,000 words
+-->
+
+",
+    ),
+    (
+        ContentType::BlogPost,
+        "basic",
+        "Blog post",
+        "---
+title: \"{{title}}\"
+date: {{date}}
+published: false
+---
+
+# {{title}}
+
+",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::project_with;
+
+    #[test]
+    fn placeholders_are_filled() {
+        let vars = TemplateVars {
+            title: "Rain".to_string(),
+            author: "Jane".to_string(),
+            date: "2024-03-01".to_string(),
+            number: Some(7),
+        };
+        assert_eq!(
+            vars.render("# {{title}} {{number}}\n{{author}}, {{date}} {{other}}"),
+            "# Rain 7\nJane, 2024-03-01 {{other}}"
+        );
+        assert_eq!(TemplateVars::default().render("{{number}}"), "");
+    }
+
+    #[test]
+    fn every_type_has_a_basic_default() {
+        for content_type in ContentType::ALL {
+            let templates = builtin(content_type);
+            assert_eq!(templates[0].name, "basic", "{:?}", content_type);
+            assert!(templates[0].body.contains("{{title}}"));
+            assert!(templates
+                .iter()
+                .all(|t| t.source == TemplateSource::Builtin));
+        }
+    }
+
+    #[tokio::test]
+    async fn project_templates_shadow_builtin_ones() {
+        let (_dir, project) = project_with(&[
+            ("templates/chapter/Basic.md", "## {{title}}\n"),
+            ("templates/chapter/heist.md", "# {{title}}\n"),
+            ("templates/chapter/notes.txt", ""),
+        ]);
+
+        let available = available(&project, ContentType::Chapter).await.unwrap();
+        let basic: Vec<_> = available
+            .iter()
+            .filter(|t| t.name.eq_ignore_ascii_case("basic"))
+            .collect();
+        assert_eq!(basic.len(), 1);
+        assert_eq!(basic[0].source, TemplateSource::Project);
+        assert!(available.iter().any(|t| t.name == "action"));
+
+        let heist = find(&project, ContentType::Chapter, "HEIST")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(heist.body, "# {{title}}\n");
+        assert!(find(&project, ContentType::Chapter, "txt")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn project_template_copies_without_overwriting() {
+        let (template, _) = project_with(&[
+            ("notes/outline.md", "# {{title}}"),
+            ("cover.txt", "{{title}}"),
+            ("README.md", "template"),
+        ]);
+        let (root, project) = project_with(&[("README.md", "mine")]);
+        let vars = TemplateVars {
+            title: "Rain".to_string(),
+            ..Default::default()
+        };
+
+        let written = apply_project_template(&project, template.path(), &vars)
+            .await
+            .unwrap();
+        assert_eq!(
+            written,
+            vec![
+                PathBuf::from("cover.txt"),
+                PathBuf::from("notes/outline.md")
+            ]
+        );
+        let read = |path: &str| std::fs::read_to_string(root.path().join(path)).unwrap();
+        assert_eq!(read("notes/outline.md"), "# Rain");
+        assert_eq!(read("cover.txt"), "{{title}}");
+        assert_eq!(read("README.md"), "mine");
+    }
+}
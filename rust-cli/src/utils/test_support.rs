@@ -0,0 +1,36 @@
+//! Fixtures shared by the unit tests
+
+use chrono::NaiveDate;
+use tempfile::TempDir;
+
+use super::ProjectManager;
+
+/// A temporary project holding `files`, given as paths relative to its root
+/// with their content. Keep the `TempDir` alive for as long as the project.
+pub fn project_with(files: &[(&str, &str)]) -> (TempDir, ProjectManager) {
+    let dir = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let project = ProjectManager::at(dir.path());
+    (dir, project)
+}
+
+/// The smallest valid `writers.config.json` for a project type
+pub fn config_json(project_type: &str) -> String {
+    serde_json::json!({
+        "name": "T",
+        "author": "A",
+        "type": project_type,
+        "version": "1.0.0",
+        "created": "",
+    })
+    .to_string()
+}
+
+/// A day in March 2026, the month the dated tests are set in
+pub fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+}
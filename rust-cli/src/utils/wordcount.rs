@@ -0,0 +1,120 @@
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use super::frontmatter;
+
+/// Count the words in a content file, skipping its frontmatter
+pub fn count(content: &str) -> usize {
+    count_markdown(frontmatter::body(content))
+}
+
+/// Count the words a reader would see in a markdown document: markup, HTML
+/// comments, link targets, images and code blocks are left out
+pub fn count_markdown(markdown: &str) -> usize {
+    count_text(&prose(markdown))
+}
+
+/// The readable text of a markdown document, one block per line
+pub fn prose(markdown: &str) -> String {
+    let mut text = String::new();
+    // Depth inside code blocks and images, whose text isn't prose
+    let mut skipped = 0;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) | Event::Start(Tag::Image(..)) => skipped += 1,
+            Event::End(Tag::CodeBlock(_)) | Event::End(Tag::Image(..)) => skipped -= 1,
+            Event::Text(t) | Event::Code(t) if skipped == 0 => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(
+                Tag::Paragraph
+                | Tag::Heading(..)
+                | Tag::Item
+                | Tag::TableCell
+                | Tag::BlockQuote
+                | Tag::FootnoteDefinition(_),
+            ) => text.push('\n'),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Count words in plain text. Hyphenated words count once, words joined by a
+/// dash count separately, and each Chinese or Japanese character counts as a
+/// word since those scripts don't separate words with spaces.
+pub fn count_text(text: &str) -> usize {
+    let mut words = 0;
+    let mut in_word = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if is_separator(c) || (c == '-' && chars.peek() == Some(&'-')) {
+            in_word = false;
+        } else if c.is_alphanumeric() && !in_word {
+            // Runs of punctuation like "***" or "..." never start a word
+            words += 1;
+            in_word = true;
+        }
+    }
+
+    words
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '—' | '–' | '―' | '…')
+}
+
+/// Han ideographs, hiragana and katakana. Korean separates words with spaces,
+/// so Hangul is counted like any other script.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}'
+        | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(count_text(""), 0);
+        assert_eq!(count_text("  The rain,  it fell. "), 4);
+        assert_eq!(count_text("a well-known fact"), 3);
+        assert_eq!(count_text("wait—no--stop…now"), 4);
+        assert_eq!(count_text("*** ... — 42"), 1);
+        assert_eq!(count_text("don't"), 1);
+    }
+
+    #[test]
+    fn cjk_characters_count_as_words() {
+        assert_eq!(count_text("我爱你"), 3);
+        assert_eq!(count_text("こんにちは world"), 6);
+        assert_eq!(count_text("안녕하세요 친구"), 2);
+    }
+
+    #[test]
+    fn markdown_markup_is_left_out() {
+        let markdown = "# Title\n\nSome **bold** [link](http://example.com/a-b-c) \
+                        ![alt text](img.png)\n\n<!-- a note -->\n\n```\nlet x = 1;\n```\n\n\
+                        - one\n- `two`\n";
+        assert_eq!(count_markdown(markdown), 6);
+        assert_eq!(prose("# A\n\nB\nC\n\n> D"), "A\nB C\nD\n\n");
+    }
+
+    #[test]
+    fn frontmatter_is_not_counted() {
+        assert_eq!(count("---\ntitle: Many words here\n---\nTwo words\n"), 2);
+        assert_eq!(count("No frontmatter here\n"), 3);
+    }
+}